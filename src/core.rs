@@ -7,32 +7,367 @@ use egui::output;
 use uuid::Uuid;
 
 use std::arch::x86_64;
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
 use std::ops::Index;
+use std::sync::LazyLock;
 // ==========EVENTTABLE==========
-use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, TimeZone, Utc};
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, NaiveDateTime, NaiveTime, Offset, TimeZone, Utc};
+use chrono_tz::Tz;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
 use rand::Rng;
 use rusqlite::types::Type;
 
+use crate::recurrence::Recurrence;
 
-type DateTimeType = DateTime<Utc>;
+
+pub type DateTimeType = DateTime<Utc>;
+
+/// Time zone of Event. Event times are always stored as DateTimeType, zone defines how they behave on shifts and how they are displayed
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EventZone {
+    /// Absolute time
+    #[default]
+    Utc,
+    /// IANA time zone: Event keeps its wall-clock time in this zone when shifted over DST change
+    Tz(Tz),
+    /// Wall-clock time that is same in any zone (e.g. "wake up at 7:00"). Stored as if it was UTC
+    Floating,
+}
+
+impl EventZone {
+    /// Wall-clock time of t in this zone
+    pub fn to_local(self, t: DateTimeType)->NaiveDateTime {
+        match self {
+            EventZone::Tz(tz) => t.with_timezone(&tz).naive_local(),
+            EventZone::Utc | EventZone::Floating => t.naive_utc(),
+        }
+    }
+
+    /// Absolute time of wall-clock time in this zone.
+    /// Ambiguous time (DST end) → earlier one, nonexistent time (DST start) → shifted by length of the gap, as RFC 5545 does
+    pub fn resolve_local(self, local: NaiveDateTime)->DateTimeType {
+        match self {
+            EventZone::Tz(tz) => match tz.from_local_datetime(&local).earliest() {
+                Some(t) => t.with_timezone(&Utc),
+                None => {
+                    let offset_before_gap = tz.offset_from_utc_datetime(&(local - Duration::days(1))).fix();
+                    (local - Duration::seconds(offset_before_gap.local_minus_utc().into())).and_utc()
+                }
+            },
+            EventZone::Utc | EventZone::Floating => local.and_utc(),
+        }
+    }
+
+    /// Absolute times of wall-clock range [start, end) in this zone, see resolve_local().
+    /// Start in DST gap may be shifted past end (e.g. 02:30-03:00 on DST start day), then range keeps its wall-clock length
+    pub fn resolve_local_range(self, start: NaiveDateTime, end: NaiveDateTime)->(DateTimeType, DateTimeType) {
+        let (resolved_start, resolved_end) = (self.resolve_local(start), self.resolve_local(end));
+        if resolved_end < resolved_start && start <= end {
+            (resolved_start, resolved_start + (end - start))
+        } else {
+            (resolved_start, resolved_end)
+        }
+    }
+}
 
 ///Reponsible for single Event information handling. Generally must be static (mush have no impl), but can be interactive too
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Event {
     pub uuid: Uuid,
     name: String,            //TODO?: E/As MUST NOT read/write this field directly → use append_name()
     pub start: DateTimeType,
     pub end:   DateTimeType,
+    /// User-defined fields (e.g. "assignee"→"Anna"). Unlike name, can be read by E/As
+    pub fields: BTreeMap<String, String>,
+    /// If Some: this Event is the first occurrence (master) of recurring Event
+    pub recurrence: Option<Recurrence>,
+    /// Event this one was created from (recurrence master)
+    pub parent: Option<Uuid>,
+    /// Original start of occurrence if this Event is materialised occurrence of parent, stays same if Event is moved
+    pub recurrence_id: Option<DateTimeType>,
+    pub zone: EventZone,
+    /// (first day, day after the last one) of all-day Event, None for timed Event.
+    /// start and end of all-day Event are midnights of those days in Event zone (Floating by default, so dates are same in any zone)
+    pub all_day: Option<(NaiveDate, NaiveDate)>,
+}
+
+impl Event {
+    /// Name of Event for Renders (they have full access to ET). Apps MUST NOT read names, see append_name()
+    pub fn get_name(&self)->&str {
+        &self.name
+    }
+
+    /// Wall-clock start for viewer in zone viewer (useful for Renders). Floating Events have same wall-clock time in any zone
+    pub fn start_in(&self, viewer: Tz)->NaiveDateTime {
+        Self::time_in(self.zone, self.start, viewer)
+    }
+
+    /// Wall-clock end for viewer in zone viewer, see start_in()
+    pub fn end_in(&self, viewer: Tz)->NaiveDateTime {
+        Self::time_in(self.zone, self.end, viewer)
+    }
+
+    /// Wall-clock time t (start/end of Event or of its occurrence) of Event in zone for viewer in zone viewer, see start_in()
+    pub fn time_in(zone: EventZone, t: DateTimeType, viewer: Tz)->NaiveDateTime {
+        match zone {
+            EventZone::Floating => zone.to_local(t),
+            EventZone::Utc | EventZone::Tz(_) => t.with_timezone(&viewer).naive_local(),
+        }
+    }
+
+    /// Absolute (start, end) of Event for viewer in zone viewer: all-day and Floating Events are placed in viewer zone, other are as they are
+    pub fn timed_in(&self, viewer: Tz)->(DateTimeType, DateTimeType) {
+        match self.zone {
+            EventZone::Floating => {
+                EventZone::Tz(viewer).resolve_local_range(self.start.naive_utc(), self.end.naive_utc())
+            }
+            EventZone::Utc | EventZone::Tz(_) => (self.start, self.end),
+        }
+    }
+
+    /// (first day, day after the last one) if [start, end) are midnights in zone
+    fn dates_of(zone: EventZone, start: DateTimeType, end: DateTimeType)->Option<(NaiveDate, NaiveDate)> {
+        let (start, end) = (zone.to_local(start), zone.to_local(end));
+        if start.time() == NaiveTime::MIN && end.time() == NaiveTime::MIN && start < end {
+            Some((start.date(), end.date()))
+        } else {
+            None
+        }
+    }
+}
+
+/// Description of Event that is not in ET yet (for example read by Extension from file), added with EventTable::add_spec()
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct EventSpec {
+    /// None → random UUID. Deterministic UUIDs make repeated imports recognisable
+    pub uuid: Option<Uuid>,
+    pub name: String,
+    pub start: DateTimeType,
+    pub end: DateTimeType,
+    pub zone: EventZone,
+    /// See Event.all_day; if Some, start and end are ignored
+    pub all_day: Option<(NaiveDate, NaiveDate)>,
+    pub recurrence: Option<Recurrence>,
+    pub fields: BTreeMap<String, String>,
+    /// See Event.parent, set when Events are restored from storage (e.g. SqliteLoad)
+    #[serde(default)]
+    pub parent: Option<Uuid>,
+    /// See Event.recurrence_id
+    #[serde(default)]
+    pub recurrence_id: Option<DateTimeType>,
+}
+
+impl EventSpec {
+    /// Timed UTC Event with random UUID
+    pub fn new(name: String, start: DateTimeType, end: DateTimeType)->Self {
+        Self {
+            uuid: None,
+            name, start, end,
+            zone: EventZone::Utc,
+            all_day: None,
+            recurrence: None,
+            fields: BTreeMap::new(),
+            parent: None,
+            recurrence_id: None,
+        }
+    }
+
+    /// All-day Event for days [start, end) with random UUID
+    pub fn new_all_day(name: String, start: NaiveDate, end: NaiveDate)->Self {
+        let zone = EventZone::Floating;
+        Self {
+            zone,
+            all_day: Some((start, end)),
+            ..Self::new(name, zone.resolve_local(start.and_time(NaiveTime::MIN)), zone.resolve_local(end.and_time(NaiveTime::MIN)))
+        }
+    }
+}
+
+/// Description of Event as it is (same UUID), e.g. to add it to another ET
+impl From<&Event> for EventSpec {
+    fn from(event: &Event)->Self {
+        Self {
+            uuid: Some(event.uuid),
+            name: event.name.clone(),
+            start: event.start,
+            end: event.end,
+            zone: event.zone,
+            all_day: event.all_day,
+            recurrence: event.recurrence.clone(),
+            fields: event.fields.clone(),
+            parent: event.parent,
+            recurrence_id: event.recurrence_id,
+        }
+    }
 }
 
 ///Responsible for Events storage and control over their correctness.
-#[derive(Clone, Debug, Default)]
+/// Serialized as {"events":[...]}, deserialization rebuilds ids and runs check_self()
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(try_from = "EventTableRepr")]
 pub struct EventTable {
     events: Vec<Event>,
-    ids:    HashSet<Uuid>,
+    ids:    HashMap<Uuid, usize>,   // Uuid -> position of Event in events
+    /// Updated in place by every times modification
+    index:  IntervalIndex,
+}
+
+/// Serialized form of EventTable: only Events themselves, everything else is rebuilt on load
+#[derive(Serialize, Deserialize)]
+struct EventTableRepr {
+    events: Vec<Event>,
+}
+
+impl TryFrom<EventTableRepr> for EventTable {
+    type Error = String;
+
+    fn try_from(repr: EventTableRepr)->Result<Self, String> {
+        let mut et = EventTable::new();
+        for event in repr.events {
+            Self::check_event(&event)?;
+            if et.ids.insert(event.uuid, et.events.len()).is_some() {
+                return Err(format!("UUID {} is duplicated", event.uuid));
+            }
+            et.index.insert(&event);
+            et.events.push(event);
+        }
+        et.check_self()?;
+        Ok(et)
+    }
+}
+
+impl Serialize for EventTable {
+    fn serialize<S: serde::Serializer>(&self, serializer: S)->Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+        let mut repr = serializer.serialize_struct("EventTableRepr", 1)?;
+        repr.serialize_field("events", &self.events)?;
+        repr.end()
+    }
 }
 
+/// Events ordered by (start, end, uuid) in treap (binary search tree balanced by random priorities), every node knows max end and last start of its subtree.
+/// Insertion and removal are O(log n), time queries on ET are O(log n + k), k = amount of found Events
+#[derive(Clone, Debug, Default)]
+struct IntervalIndex {
+    root: Option<Box<IndexNode>>,
+}
+
+type IndexKey = (DateTimeType, DateTimeType, Uuid);
+
+#[derive(Clone, Debug)]
+struct IndexNode {
+    key:        IndexKey,
+    priority:   u64,
+    max_end:    DateTimeType,
+    last_start: DateTimeType,
+    left:       Option<Box<IndexNode>>,
+    right:      Option<Box<IndexNode>>,
+}
+
+impl IndexNode {
+    fn new(key: IndexKey)->Box<Self> {
+        // UUIDs are random (or hashes for v5), their bits are good enough as priorities
+        let bits = key.2.as_u128();
+        Box::new(Self { key, priority: (bits as u64) ^ ((bits >> 64) as u64), max_end: key.1, last_start: key.0, left: None, right: None })
+    }
+
+    fn update(&mut self) {
+        self.max_end = [&self.left, &self.right].into_iter().flatten().map(|n| n.max_end).fold(self.key.1, DateTimeType::max);
+        self.last_start = self.right.as_ref().map_or(self.key.0, |n| n.last_start);
+    }
+}
+
+impl IntervalIndex {
+    /// (keys < key, keys >= key), or (keys <= key, keys > key) if inclusive
+    fn split(node: Option<Box<IndexNode>>, key: &IndexKey, inclusive: bool)->(Option<Box<IndexNode>>, Option<Box<IndexNode>>) {
+        let Some(mut node) = node else { return (None, None) };
+        if node.key < *key || (inclusive && node.key == *key) {
+            let (left, right) = Self::split(node.right.take(), key, inclusive);
+            node.right = left;
+            node.update();
+            (Some(node), right)
+        } else {
+            let (left, right) = Self::split(node.left.take(), key, inclusive);
+            node.left = right;
+            node.update();
+            (left, Some(node))
+        }
+    }
+
+    /// All keys of left are less than keys of right
+    fn merge(left: Option<Box<IndexNode>>, right: Option<Box<IndexNode>>)->Option<Box<IndexNode>> {
+        match (left, right) {
+            (None, node) | (node, None) => node,
+            (Some(mut left), Some(mut right)) => {
+                if left.priority >= right.priority {
+                    left.right = Self::merge(left.right.take(), Some(right));
+                    left.update();
+                    Some(left)
+                } else {
+                    right.left = Self::merge(Some(left), right.left.take());
+                    right.update();
+                    Some(right)
+                }
+            }
+        }
+    }
+
+    fn insert(&mut self, event: &Event) {
+        let key = (event.start, event.end, event.uuid);
+        let (left, right) = Self::split(self.root.take(), &key, false);
+        self.root = Self::merge(Self::merge(left, Some(IndexNode::new(key))), right);
+    }
+
+    fn remove(&mut self, event: &Event) {
+        let key = (event.start, event.end, event.uuid);
+        let (left, rest) = Self::split(self.root.take(), &key, false);
+        let (_, right) = Self::split(rest, &key, true);
+        self.root = Self::merge(left, right);
+    }
+
+    /// UUIDs of all Events ordered by start
+    fn sorted(&self)->Vec<Uuid> {
+        fn walk(node: &Option<Box<IndexNode>>, found: &mut Vec<Uuid>) {
+            if let Some(node) = node {
+                walk(&node.left, found);
+                found.push(node.key.2);
+                walk(&node.right, found);
+            }
+        }
+        let mut found = Vec::new();
+        walk(&self.root, &mut found);
+        found
+    }
+
+    /// UUIDs of Events overlapping [start, end), ordered by Event start.
+    /// Zero-length Events overlap if they are inside [start, end)
+    fn overlapping(&self, start: DateTimeType, end: DateTimeType)->Vec<Uuid> {
+        fn walk(node: &Option<Box<IndexNode>>, start: DateTimeType, end: DateTimeType, found: &mut Vec<Uuid>) {
+            let Some(node) = node else { return };
+            // all Events of subtree end too early and start before window → nothing to find here
+            if node.max_end <= start && node.last_start < start {
+                return;
+            }
+            walk(&node.left, start, end, found);
+            let (ev_start, ev_end, uuid) = node.key;
+            // this Event and right subtree start after window
+            if ev_start >= end {
+                return;
+            }
+            if ev_end > start || ev_start >= start {
+                found.push(uuid);
+            }
+            walk(&node.right, start, end, found);
+        }
+        let mut found = Vec::new();
+        walk(&self.root, start, end, &mut found);
+        found
+    }
+}
+
+
+
 impl EventTable {
 
     // ====ET MODIFICATIONS====
@@ -48,6 +383,15 @@ impl EventTable {
         if event.start > event.end {
             return Err(format!("Event {} has end < start", event.uuid));
         }
+        if let Some(recurrence) = &event.recurrence {
+            recurrence.check().map_err(|e| format!("Event {}: {}", event.uuid, e))?;
+            if event.recurrence_id.is_some() {
+                return Err(format!("Event {} is an occurrence of {:?}, it can not be recurring itself", event.uuid, event.parent));
+            }
+        }
+        if event.all_day.is_some() && event.all_day != Event::dates_of(event.zone, event.start, event.end) {
+            return Err(format!("All-day Event {} has dates {:?} that do not match its times", event.uuid, event.all_day));
+        }
         Ok(())
     }
     /// Whole check_event + specific for ET checks that single Event does not have
@@ -58,8 +402,8 @@ impl EventTable {
             return Err("EventTable corrupted: length mismatch between events and ids".into());
         }
 
-        for event in &self.events {
-            if !self.ids.contains(&event.uuid) {
+        for (pos, event) in self.events.iter().enumerate() {
+            if self.ids.get(&event.uuid) != Some(&pos) {
                 return Err(format!("Missing UUID {} in id set", event.uuid));
             }
             Self::check_event(event)?;
@@ -73,31 +417,141 @@ impl EventTable {
         start: DateTimeType,
         end: DateTimeType
     )->Result<(), String> {
-        let uuid = Uuid::new_v4();
+        self.add_spec(EventSpec::new(name, start, end))?;
+        Ok(())
+    }
 
-        let event=Event { uuid, name, start, end };
-        Self::check_event(&event)?;
+    /// Add a new all-day Event for days [start, end) (end is exclusive). Random UUID is automatically generated
+    pub fn add_all_day(&mut self,
+        name: String,
+        start: NaiveDate,
+        end: NaiveDate
+    )->Result<(), String> {
+        if start >= end {
+            return Err(format!("All-day Event must last at least 1 day, got {}..{}", start, end));
+        }
+        self.add_spec(EventSpec::new_all_day(name, start, end))?;
+        Ok(())
+    }
 
-        self.events.push(event);
-        self.ids.insert(uuid);
+    /// Add a new Event described by spec. Fails if spec UUID already exists in ET
+    pub fn add_spec(&mut self,
+        spec: EventSpec
+    )->Result<Uuid, String> {
+        let (start, end) = match spec.all_day {
+            Some((first, after_last)) => (
+                spec.zone.resolve_local(first.and_time(NaiveTime::MIN)),
+                spec.zone.resolve_local(after_last.and_time(NaiveTime::MIN)),
+            ),
+            None => (spec.start, spec.end),
+        };
+        let event=Event {
+            uuid: spec.uuid.unwrap_or_else(Uuid::new_v4),
+            name: spec.name,
+            start, end,
+            fields: spec.fields,
+            recurrence: spec.recurrence,
+            parent: spec.parent,
+            recurrence_id: spec.recurrence_id,
+            zone: spec.zone,
+            all_day: spec.all_day,
+        };
+        let uuid = event.uuid;
+        self.push(event)?;
+        Ok(uuid)
+    }
 
-        // Check must be done after any modification;
-        self.check_self()?;
+    /// Insertion of already assembled Event
+    fn push(&mut self,
+        event: Event
+    )->Result<(), String> {
+        Self::check_event(&event)?;
+        if self.ids.contains_key(&event.uuid) {
+            return Err(format!("UUID {} already exists", event.uuid));
+        }
 
+        self.ids.insert(event.uuid, self.events.len());
+        self.index.insert(&event);
+        self.events.push(event);
         Ok(())
     }
 
     /// Splits one Event in a set of events. All of child-Events have [parent-Events name]+suffix
     /// input list of child-Events info: Vec<(StartTime,EndTime,Suffix)>
+    /// Parent Event is replaced by children (they have parent=uuid, same fields and zone). Children of all-day Event stay all-day if they start and end at midnights.
+    /// Returns Uuids of children in same order as data
     pub fn split(&mut self,
+        uuid: Uuid,
         data:Vec<(
             DateTimeType,
             DateTimeType,
             String)>
-    ){
-        //TODO
-        todo!();
+    )->Result<Vec<Uuid>, String>{
+        let parent = self.get(uuid).ok_or(format!("Missing UUID {}", uuid))?;
+        if data.is_empty() {
+            return Err(format!("Event {} can not be split in 0 Events", uuid));
+        }
+        if parent.recurrence.is_some() {
+            return Err(format!("Event {} is recurring, materialise its occurrences before splitting", uuid));
+        }
 
+        let children: Vec<Event> = data.into_iter()
+            .map(|(start, end, suffix)| Event {
+                uuid: Uuid::new_v4(),
+                name: parent.name.clone() + &suffix,
+                start, end,
+                fields: parent.fields.clone(),
+                recurrence: None,
+                parent: Some(uuid),
+                recurrence_id: None,
+                zone: parent.zone,
+                all_day: parent.all_day.and_then(|_| Event::dates_of(parent.zone, start, end)),
+            })
+            .collect();
+        for child in &children {
+            Self::check_event(child)?;
+        }
+
+        // removal of parent, last Event takes its position
+        let pos = self.ids.remove(&uuid).ok_or(format!("Missing UUID {}", uuid))?;
+        let parent = self.events.swap_remove(pos);
+        self.index.remove(&parent);
+        if let Some(moved) = self.events.get(pos) {
+            self.ids.insert(moved.uuid, pos);
+        }
+
+        let uuids = children.iter().map(|c| c.uuid).collect();
+        for child in children {
+            self.push(child)?;
+        }
+        Ok(uuids)
+    }
+
+
+
+    // ====SERIALIZATION====
+
+    pub fn to_json(&self
+    )->Result<String, String> {
+        serde_json::to_string_pretty(self).map_err(|e| format!("EventTable to JSON: {}", e))
+    }
+
+    /// Rejects duplicated UUIDs and Events with end < start
+    pub fn from_json(json: &str
+    )->Result<Self, String> {
+        serde_json::from_str(json).map_err(|e| format!("EventTable from JSON: {}", e))
+    }
+
+    /// Compact binary format (bincode)
+    pub fn to_binary(&self
+    )->Result<Vec<u8>, String> {
+        bincode::serialize(self).map_err(|e| format!("EventTable to binary: {}", e))
+    }
+
+    /// Same checks as from_json()
+    pub fn from_binary(bytes: &[u8]
+    )->Result<Self, String> {
+        bincode::deserialize(bytes).map_err(|e| format!("EventTable from binary: {}", e))
     }
 
 
@@ -108,11 +562,8 @@ impl EventTable {
     fn get_mut(&mut self,
         uuid: Uuid
     )->Option<&mut Event> {
-        if self.ids.contains(&uuid) {
-            self.events.iter_mut().find(|e| e.uuid == uuid)
-        } else {
-            None
-        }
+        let pos = *self.ids.get(&uuid)?;
+        self.events.get_mut(pos)
     }
 
     /// Apps may only append to name, never read it → no information leak between Apps
@@ -130,18 +581,116 @@ impl EventTable {
         Ok(())
     }
 
-    /// Change times safely
+    /// Change times safely, Event is not changed if new times are invalid
     pub fn set_times(&mut self,
         uuid: Uuid,
         start: DateTimeType,
         end: DateTimeType
-    ){
-        if let Some(ev) = self.get_mut(uuid) {
-            ev.start = start;
-            ev.end = end;
+    )->Result<(), String> {
+        if start > end {
+            return Err(format!("Event {} would have end < start", uuid));
         }
+        let pos = *self.ids.get(&uuid).ok_or(format!("Missing UUID {}", uuid))?;
+        self.index.remove(&self.events[pos]);
+        let ev = &mut self.events[pos];
+        ev.start = start;
+        ev.end = end;
+        // all-day Event moved off midnights becomes timed
+        if ev.all_day.is_some() {
+            ev.all_day = Event::dates_of(ev.zone, start, end);
+        }
+        self.index.insert(&self.events[pos]);
+        Ok(())
+    }
+
+    /// Move Event by given time keeping its wall-clock times in its zone (9:00-10:00 Europe/Berlin + 1 day is 9:00-10:00 next day even over DST change)
+    pub fn shift(&mut self,
+        uuid: Uuid,
+        by: Duration
+    )->Result<(), String> {
+        let ev = self.get(uuid).ok_or(format!("Missing UUID {}", uuid))?;
+        let (start, end) = (ev.zone.to_local(ev.start) + by, ev.zone.to_local(ev.end) + by);
+        self.set_local_times(uuid, start, end)
+    }
 
-        self.check_self();
+    /// Change times with wall-clock times in zone of Event
+    pub fn set_local_times(&mut self,
+        uuid: Uuid,
+        start: NaiveDateTime,
+        end: NaiveDateTime
+    )->Result<(), String> {
+        let zone = self.get(uuid).ok_or(format!("Missing UUID {}", uuid))?.zone;
+        if start > end {
+            return Err(format!("Event {} would have end < start", uuid));
+        }
+        let (start, end) = zone.resolve_local_range(start, end);
+        self.set_times(uuid, start, end)
+    }
+
+    /// Change zone of Event. Absolute times are kept, except of Floating and all-day Events (for them wall-clock time/dates are kept).
+    /// Event is not changed if new times are invalid (e.g. Event over DST end becomes Floating with end < start)
+    pub fn set_zone(&mut self,
+        uuid: Uuid,
+        zone: EventZone
+    )->Result<(), String> {
+        let ev = self.get(uuid).ok_or(format!("Missing UUID {}", uuid))?;
+        let (start, end) = if let Some((first, after_last)) = ev.all_day {
+            zone.resolve_local_range(first.and_time(NaiveTime::MIN), after_last.and_time(NaiveTime::MIN))
+        } else if ev.zone == EventZone::Floating {
+            zone.resolve_local_range(ev.start.naive_utc(), ev.end.naive_utc())
+        } else if zone == EventZone::Floating {
+            (ev.zone.to_local(ev.start).and_utc(), ev.zone.to_local(ev.end).and_utc())
+        } else {
+            (ev.start, ev.end)
+        };
+        let changed = Event { start, end, zone, ..ev.clone() };
+        Self::check_event(&changed).map_err(|e| format!("{} in zone {:?}", e, zone))?;
+        let pos = *self.ids.get(&uuid).ok_or(format!("Missing UUID {}", uuid))?;
+        self.index.remove(&self.events[pos]);
+        self.index.insert(&changed);
+        self.events[pos] = changed;
+        Ok(())
+    }
+
+    /// Make all-day Event timed: from midnight of its first day till midnight after its last day in zone
+    pub fn make_timed(&mut self,
+        uuid: Uuid,
+        zone: EventZone
+    )->Result<(), String> {
+        self.set_zone(uuid, zone)?;
+        let ev = self.get_mut(uuid).ok_or(format!("Missing UUID {}", uuid))?;
+        ev.all_day = None;
+        Ok(())
+    }
+
+    /// Set (or with value=None remove) user-defined field of Event
+    pub fn set_field(&mut self,
+        uuid: Uuid,
+        key: &str,
+        value: Option<String>
+    )->Result<(), String> {
+        let ev = self.get_mut(uuid).ok_or(format!("Missing UUID {}", uuid))?;
+        match value {
+            Some(value) => ev.fields.insert(key.to_string(), value),
+            None => ev.fields.remove(key),
+        };
+        Ok(())
+    }
+
+    /// Make Event recurring (or single with None). Already materialised occurrences are not changed
+    pub fn set_recurrence(&mut self,
+        uuid: Uuid,
+        recurrence: Option<Recurrence>
+    )->Result<(), String> {
+        if let Some(recurrence) = &recurrence {
+            recurrence.check()?;
+        }
+        let ev = self.get_mut(uuid).ok_or(format!("Missing UUID {}", uuid))?;
+        if ev.recurrence_id.is_some() {
+            return Err(format!("Event {} is an occurrence of {:?}, it can not be recurring itself", uuid, ev.parent));
+        }
+        ev.recurrence = recurrence;
+        Ok(())
     }
 
     /// Read-only iterator (useful for Extensions)
@@ -149,6 +698,261 @@ impl EventTable {
     )->std::slice::Iter<'_, Event> {
         self.events.iter()
     }
+
+    /// Read-only access by UUID
+    pub fn get(&self,
+        uuid: Uuid
+    )->Option<&Event> {
+        self.events.get(*self.ids.get(&uuid)?)
+    }
+
+
+
+    // ====TIME QUERIES====
+    // All of them are O(log n + k), k = amount of returned Events
+
+    /// Event of UUID taken from index
+    fn indexed(&self,
+        uuid: Uuid
+    )->&Event {
+        &self.events[self.ids[&uuid]]
+    }
+
+    /// Events overlapping [start, end), ordered by start
+    pub fn overlapping(&self,
+        start: DateTimeType,
+        end: DateTimeType
+    )->Vec<&Event> {
+        self.index.overlapping(start, end)
+            .into_iter()
+            .map(|uuid| self.indexed(uuid))
+            .collect()
+    }
+
+    /// Events overlapping date (UTC day, for all-day and Floating Events same as their date), ordered by start
+    pub fn on_date(&self,
+        date: NaiveDate
+    )->Vec<&Event> {
+        let start = date.and_time(NaiveTime::MIN).and_utc();
+        self.overlapping(start, start + Duration::days(1))
+    }
+
+    /// First Event starting at or after t
+    pub fn next_after(&self,
+        t: DateTimeType
+    )->Option<&Event> {
+        let mut node = &self.index.root;
+        let mut found = None;
+        while let Some(n) = node {
+            if n.key.0 >= t {
+                found = Some(n.key.2);
+                node = &n.left;
+            } else {
+                node = &n.right;
+            }
+        }
+        found.map(|uuid| self.indexed(uuid))
+    }
+
+    /// Time periods inside [start, end) that are not covered by any Event (zero-length Events cover nothing), ordered by start
+    pub fn free_gaps(&self,
+        start: DateTimeType,
+        end: DateTimeType
+    )->Vec<(DateTimeType, DateTimeType)> {
+        let mut gaps = Vec::new();
+        let mut cursor = start;
+        for event in self.overlapping(start, end).into_iter().filter(|e| e.start < e.end) {
+            if event.start > cursor {
+                gaps.push((cursor, event.start));
+            }
+            cursor = cursor.max(event.end);
+        }
+        if cursor < end {
+            gaps.push((cursor, end));
+        }
+        gaps
+    }
+
+
+
+
+    // ====RECURRENCE====
+
+    /// (start, end) of all occurrences of Event uuid that overlap [start, end), including Event itself. Nothing is added to ET.
+    /// Single Event has only itself as occurrence
+    pub fn occurrences(&self,
+        uuid: Uuid,
+        start: DateTimeType,
+        end: DateTimeType
+    )->Result<Vec<(DateTimeType, DateTimeType)>, String> {
+        let master = self.get(uuid).ok_or(format!("Missing UUID {}", uuid))?;
+        let duration = master.end - master.start;
+        let starts = match &master.recurrence {
+            Some(recurrence) => recurrence.occurrences(master.start, duration, master.zone, start, end),
+            None if master.start < end && (master.end > start || master.start >= start) => vec![master.start],
+            None => vec![],
+        };
+        Ok(starts.into_iter().map(|s| (s, s + duration)).collect())
+    }
+
+    /// Adds occurrences of recurring Event uuid that overlap [start, end) to ET as separate Events (with parent=uuid, same name and fields).
+    /// Occurrences materialised earlier (even if they were moved after) are not added again. Master Event is the first occurrence itself.
+    /// Returns Uuids of new Events
+    pub fn materialise(&mut self,
+        uuid: Uuid,
+        start: DateTimeType,
+        end: DateTimeType
+    )->Result<Vec<Uuid>, String> {
+        let master = self.get(uuid).ok_or(format!("Missing UUID {}", uuid))?.clone();
+        if master.recurrence.is_none() {
+            return Err(format!("Event {} is not recurring", uuid));
+        }
+        let existing: HashSet<DateTimeType> = self.events.iter()
+            .filter(|e| e.parent == Some(uuid))
+            .filter_map(|e| e.recurrence_id)
+            .collect();
+
+        let mut added = Vec::new();
+        for (occ_start, occ_end) in self.occurrences(uuid, start, end)? {
+            if occ_start == master.start || existing.contains(&occ_start) {
+                continue;
+            }
+            let event = Event {
+                uuid: Uuid::new_v4(),
+                name: master.name.clone(),
+                start: occ_start,
+                end: occ_end,
+                fields: master.fields.clone(),
+                recurrence: None,
+                parent: Some(uuid),
+                recurrence_id: Some(occ_start),
+                zone: master.zone,
+                all_day: master.all_day.and_then(|_| Event::dates_of(master.zone, occ_start, occ_end)),
+            };
+            added.push(event.uuid);
+            self.push(event)?;
+        }
+        Ok(added)
+    }
+
+    // ====CONFLICTS====
+    // Events can overlap, those overlaps are conflicts. Zero-length Events conflict with nothing.
+    // scope: if Some(field) → only Events with same value of this field conflict with each other (Events without this field are skipped)
+
+    /// Original Event of uuid: parent of split part or occurrence (recursively), even if it is not in ET any more
+    pub fn origin(&self,
+        uuid: Uuid
+    )->Uuid {
+        let mut origin = uuid;
+        while let Some(parent) = self.get(origin).and_then(|e| e.parent) {
+            origin = parent;
+        }
+        origin
+    }
+
+    /// Events in order of index, split by scope
+    fn scoped_groups(&self,
+        scope: Option<&str>
+    )->Vec<Vec<&Event>> {
+        let mut groups: BTreeMap<Option<&str>, Vec<&Event>> = BTreeMap::new();
+        for uuid in self.index.sorted() {
+            let event = self.indexed(uuid);
+            let key = match scope {
+                Some(field) => match event.fields.get(field) {
+                    Some(value) => Some(value.as_str()),
+                    None => continue,
+                },
+                None => None,
+            };
+            groups.entry(key).or_default().push(event);
+        }
+        groups.into_values().collect()
+    }
+
+    /// All pairs of overlapping Events, (earlier started, later started)
+    pub fn conflict_pairs(&self,
+        scope: Option<&str>
+    )->Vec<(Uuid, Uuid)> {
+        let mut pairs = Vec::new();
+        for group in self.scoped_groups(scope) {
+            let mut active: Vec<&Event> = Vec::new(); // Events that did not end before current one
+            for event in group {
+                active.retain(|a| a.end > event.start);
+                if event.start == event.end {
+                    continue;
+                }
+                pairs.extend(active.iter().map(|a| (a.uuid, event.uuid)));
+                active.push(event);
+            }
+        }
+        pairs
+    }
+
+    /// Sets of Events connected by overlaps (A overlaps B, B overlaps C → [A,B,C]), each set has at least 2 Events
+    pub fn conflict_groups(&self,
+        scope: Option<&str>
+    )->Vec<Vec<Uuid>> {
+        let mut result = Vec::new();
+        for group in self.scoped_groups(scope) {
+            let mut current: Vec<Uuid> = Vec::new();
+            let mut current_end = DateTimeType::MIN_UTC;
+            for event in group.into_iter().filter(|e| e.start != e.end) {
+                if event.start >= current_end {
+                    if current.len() > 1 {
+                        result.push(std::mem::take(&mut current));
+                    }
+                    current.clear();
+                }
+                current.push(event.uuid);
+                current_end = current_end.max(event.end);
+            }
+            if current.len() > 1 {
+                result.push(current);
+            }
+        }
+        result
+    }
+}
+
+/// Busy time as ordered disjoint intervals (overlapping and touching ones are merged), e.g. to place Events around fixed ones
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BusyTime(Vec<(DateTimeType, DateTimeType)>);
+
+impl BusyTime {
+    /// Empty intervals are dropped
+    pub fn new(intervals: impl IntoIterator<Item=(DateTimeType, DateTimeType)>)->Self {
+        let mut intervals: Vec<(DateTimeType, DateTimeType)> = intervals.into_iter().filter(|(start, end)| start < end).collect();
+        intervals.sort();
+        let mut merged: Vec<(DateTimeType, DateTimeType)> = Vec::new();
+        for (start, end) in intervals {
+            match merged.last_mut() {
+                Some(last) if start <= last.1 => last.1 = last.1.max(end),
+                _ => merged.push((start, end)),
+            }
+        }
+        Self(merged)
+    }
+
+    /// Intervals ending after t, ordered
+    pub fn after(&self, t: DateTimeType)->&[(DateTimeType, DateTimeType)] {
+        &self.0[self.0.partition_point(|(_, end)| *end <= t)..]
+    }
+
+    /// End of last interval
+    pub fn end(&self)->Option<DateTimeType> {
+        self.0.last().map(|(_, end)| *end)
+    }
+
+    /// Earliest start not earlier than t of slot of given length that does not overlap busy time
+    pub fn free_slot(&self, mut t: DateTimeType, length: Duration)->DateTimeType {
+        for (start, end) in self.after(t) {
+            if *start >= t + length {
+                break;
+            }
+            t = *end;
+        }
+        t
+    }
 }
 
 
@@ -158,12 +962,29 @@ use std::any::{Any, TypeId};
 use std::collections::HashMap;
 
 /// All [IO of R/E/As] except [ET in Baseline]. Don't be confused, it is NOT TYPE of TYPE of IO; it is type of IO; it already contains IO info that is piped to make R/E/As communicate.
-type IOType<'a> = HashMap<
+pub type IOType<'a> = HashMap<
     String,                     // Name
     &'a Box<dyn Any+Send+Sync>      // Input/output itself
 >;
 
-enum R_E_AClass {
+/// Outputs of Extension. Same as IOType, but owned: they are stored in Pipeline until they are piped into R/E/As
+pub type IOOutputType = HashMap<
+    String,                     // Name
+    Box<dyn Any+Send+Sync>      // Output itself
+>;
+
+/// Typed access to input of R/E/A by its name
+pub fn get_input<'a, T: 'static>(
+    inputs: &IOType<'a>,
+    name: &str
+)->Result<&'a T, String> {
+    inputs.get(name)
+        .ok_or(format!("Input {} is missing", name))?
+        .downcast_ref::<T>()
+        .ok_or(format!("Input {} has wrong type, {} expected", name, std::any::type_name::<T>()))
+}
+
+pub enum R_E_AClass {
     Render,
     Extension,
     App
@@ -173,7 +994,7 @@ enum R_E_AClass {
 // (check ALL classes run() inputs after redacting any of them)
 
 /// Datatype for listing inputs/outputs of R/E/As
-type IOListOfTypes=HashMap<
+pub type IOListOfTypes=HashMap<
     String,
     TypeId
 >;
@@ -183,9 +1004,9 @@ pub trait R_E_AGeneric{
     fn get_class(&self)->&'static R_E_AClass;
 
     /// Inputs of this R/E/A (except ET for R/A), can be empty.
-    /// Inputs names, types and amount can be static(declared in code before compiling) or dynamic(procedurally generated)
+    /// Inputs names, types and amount can be static(declared in code before compiling) or dynamic(procedurally generated, stored in R/E/A itself)
     fn get_inputs(&self)
-    ->&'static IOListOfTypes;
+    ->&IOListOfTypes;
 }
 pub trait Render: R_E_AGeneric{
     fn run(&self,
@@ -194,13 +1015,14 @@ pub trait Render: R_E_AGeneric{
     )->Result<(), String>;
 }
 pub trait Extension: R_E_AGeneric{
+    /// Same as get_inputs: static or dynamic
     fn get_outputs(&self)
-    ->&'static IOListOfTypes;
+    ->&IOListOfTypes;
     
     fn run(&self,
         inputs: IOType
     )->Result<
-        IOType, // outputs of Extension
+        IOOutputType, // outputs of Extension
         String //Err
     >;
 }
@@ -212,6 +1034,24 @@ pub trait App: R_E_AGeneric{
         EventTable, // for further baseline R/A
         String //Err
     >;
+
+    /// Reports of App (e.g. Events that could not be placed), piped like outputs of Extensions. Most Apps have none
+    fn get_outputs(&self)
+    ->&IOListOfTypes {
+        static NO_OUTPUTS: LazyLock<IOListOfTypes> = LazyLock::new(IOListOfTypes::new);
+        &NO_OUTPUTS
+    }
+
+    /// Same as run() with reports listed in get_outputs(). Apps with reports override this (and call it from run())
+    fn run_with_outputs(&self,
+        et: EventTable,
+        inputs: IOType
+    )->Result<
+        (EventTable, IOOutputType),
+        String
+    > {
+        Ok((self.run(et, inputs)?, IOOutputType::new()))
+    }
 }
 
 pub enum R_E_A {
@@ -222,7 +1062,11 @@ pub enum R_E_A {
 
 impl R_E_AGeneric for R_E_A {
     fn get_name(&self) -> &'static str {
-        self.get_name()
+        match self {
+            R_E_A::Render(R) => R.get_name(),
+            R_E_A::Extension(E) => E.get_name(),
+            R_E_A::App(A) => A.get_name(),
+        }
     }
 
     fn get_class(&self) -> &'static R_E_AClass { // TODO: can be optimized?
@@ -233,7 +1077,7 @@ impl R_E_AGeneric for R_E_A {
         }
     }
 
-    fn get_inputs(&self) ->&'static IOListOfTypes{
+    fn get_inputs(&self) ->&IOListOfTypes{
         match self {
             R_E_A::Render(R) => R.get_inputs(),
             R_E_A::Extension(E) => E.get_inputs(),
@@ -242,19 +1086,207 @@ impl R_E_AGeneric for R_E_A {
     }
 }
 
+impl R_E_A {
+    /// Outputs of Extension or reports of App, Renders have no outputs
+    pub fn get_outputs(&self) ->Option<&IOListOfTypes>{
+        match self {
+            R_E_A::Extension(E) => Some(E.get_outputs()),
+            R_E_A::App(A) => Some(A.get_outputs()),
+            R_E_A::Render(_) => None,
+        }
+    }
+}
+
+/// Input of R/E/A set directly (by GUI or pipeline file), not piped from Extension
+pub struct InputValue {
+    pub value: Box<dyn Any+Send+Sync>,
+    to_json: fn(&(dyn Any+Send+Sync))->Result<serde_json::Value, String>,
+}
+
+impl InputValue {
+    pub fn new<T: Serialize+Send+Sync+'static>(value: T)->Self {
+        Self {
+            value: Box::new(value),
+            to_json: |value| {
+                let value = value.downcast_ref::<T>().ok_or("Input value has wrong type")?;
+                serde_json::to_value(value).map_err(|e| e.to_string())
+            },
+        }
+    }
+
+    pub fn to_json(&self)->Result<serde_json::Value, String> {
+        (self.to_json)(self.value.as_ref())
+    }
+}
+
+/// Constructors of R/E/As by their names and readable names of IO types. Used to load Pipeline from file and by GUI
+pub struct Registry {
+    /// Name → constructor. Name is usually get_name() of R/E/A, but differently configured R/E/As of one type can be registered with different names
+    components: BTreeMap<String, Box<dyn Fn()->R_E_A>>,
+    type_names: HashMap<TypeId, &'static str>,
+    /// Readers of input values from pipeline file
+    value_parsers: HashMap<TypeId, ValueParser>,
+    /// Used by GUI to show values flowing through connections
+    formatters: HashMap<TypeId, Formatter>,
+}
+
+type ValueParser = fn(serde_json::Value)->Result<InputValue, String>;
+type Formatter = fn(&(dyn Any+Send+Sync))->String;
+
+impl Registry {
+    pub fn new()->Self {
+        let mut registry = Self { components: BTreeMap::new(), type_names: HashMap::new(), value_parsers: HashMap::new(), formatters: HashMap::new() };
+        registry.register_type::<EventTable>();
+        registry.register_value::<String>();
+        registry.register_value::<bool>();
+        registry.register_value::<i64>();
+        registry.register_value::<u32>();
+        registry.register_value::<f64>();
+        registry.register_value::<NaiveDate>();
+        registry.register_value::<Vec<NaiveDate>>();
+        registry.register_value::<DateTimeType>();
+        registry.register_value::<Option<DateTimeType>>();
+        registry.register_value::<Tz>();
+        registry.register_value::<EventZone>();
+        registry.register_value::<Vec<String>>();
+        registry.register_value::<Vec<Uuid>>();
+        registry.register_value::<Vec<EventSpec>>();
+        registry
+    }
+
+    pub fn register(&mut self,
+        name: &str,
+        constructor: impl Fn()->R_E_A+'static
+    ) {
+        self.components.insert(name.to_string(), Box::new(constructor));
+    }
+
+    /// Makes type T readable in GUI and error messages
+    pub fn register_type<T: 'static>(&mut self) {
+        self.type_names.insert(TypeId::of::<T>(), std::any::type_name::<T>());
+    }
+
+    /// Allows values of type T to be set directly as inputs in pipeline file and GUI, see Pipeline.values
+    pub fn register_value<T: Serialize+DeserializeOwned+std::fmt::Debug+Send+Sync+'static>(&mut self) {
+        self.register_debug::<T>();
+        self.value_parsers.insert(TypeId::of::<T>(), |json| {
+            serde_json::from_value::<T>(json).map(InputValue::new).map_err(|e| e.to_string())
+        });
+    }
+
+    /// Makes values of type T (e.g. outputs of Extension) viewable in GUI, pretty-printed with Debug
+    pub fn register_debug<T: std::fmt::Debug+Send+Sync+'static>(&mut self) {
+        self.register_type::<T>();
+        self.formatters.insert(TypeId::of::<T>(), |value| {
+            value.downcast_ref::<T>().map(|value| format!("{:#?}", value)).unwrap_or_default()
+        });
+    }
+
+    /// None if type of value is not registered with register_debug
+    pub fn format_value(&self, value: &(dyn Any+Send+Sync))->Option<String> {
+        self.formatters.get(&value.type_id()).map(|format| format(value))
+    }
+
+    pub fn has_value(&self, type_id: TypeId)->bool {
+        self.value_parsers.contains_key(&type_id)
+    }
+
+    pub fn parse_value(&self,
+        type_id: TypeId,
+        json: serde_json::Value
+    )->Result<InputValue, String> {
+        let parse = self.value_parsers.get(&type_id)
+            .ok_or(format!("Values of type {} can not be set directly", self.type_name(type_id)))?;
+        parse(json)
+    }
+
+    pub fn create(&self,
+        name: &str
+    )->Result<R_E_A, String> {
+        self.components.get(name)
+            .map(|constructor| constructor())
+            .ok_or(format!("R/E/A {} is not registered", name))
+    }
+
+    /// Registered names, ordered
+    pub fn names(&self)->impl Iterator<Item=&str> {
+        self.components.keys().map(String::as_str)
+    }
+
+    /// Type name without module paths, e.g. "Vec<EventSpec>"
+    pub fn type_name(&self, id: TypeId)->String {
+        let Some(name) = self.type_names.get(&id) else {
+            return format!("{:?}", id);
+        };
+        let mut short = String::new();
+        let mut path = String::new();
+        for c in name.chars().chain([' ']) {
+            if c.is_alphanumeric() || c == '_' || c == ':' {
+                path.push(c);
+            } else {
+                short.push_str(path.rsplit("::").next().unwrap_or_default());
+                path.clear();
+                short.push(c);
+            }
+        }
+        short.pop();
+        short
+    }
+}
+
 
 
 // ==========PIPELINE==========
 // Each R/E/A in full run of Pipeline can be executed only once. If one program will be used repeatedly, they will be stored in alias multiple times with different Uuids
 
-struct Pipeline<'a>{
+/// What Pipeline does when App introduces new conflicts (see EventTable::conflict_pairs) to ET
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ConflictPolicy {
+    #[default]
+    Ignore,
+    /// Add message to Pipeline.warnings
+    Warn,
+    /// Stop execution with Err
+    Fail,
+}
+
+impl ConflictPolicy {
+    pub const ALL: [ConflictPolicy; 3] = [ConflictPolicy::Ignore, ConflictPolicy::Warn, ConflictPolicy::Fail];
+
+    fn is_ignore(&self)->bool {
+        *self == ConflictPolicy::Ignore
+    }
+}
+
+pub struct Pipeline{
     /// If 'true': checkup [if all categories have correct class type] will be executed after any modification or import. Some checkups (for example check of "connections" if selected Element has this iutput/input) will always be done.
     pub check_classes:bool,
 
+    pub conflict_policy:ConflictPolicy,
+    /// Field conflicts are scoped by (for example "assignee"), see EventTable::conflict_pairs
+    pub conflict_scope:Option<String>,
+    /// Non-fatal messages of last run
+    pub warnings:Vec<String>,
+
     pub R_E_AList:HashMap<
         Uuid,
         Box<R_E_A>
     >,
+    /// (alias, registered name) of each R/E/A, see Registry. Alias is unique and names R/E/A in pipeline file
+    pub names:HashMap<
+        Uuid,
+        (String, String)
+    >,
+    /// Inputs that are set directly (by GUI or pipeline file), used only if input has no connection
+    pub values:HashMap<
+        (Uuid, String),   // Element uuid + input name
+        InputValue
+    >,
+    /// Positions of Extensions in GUI editor, saved in pipeline file
+    pub layout:HashMap<
+        Uuid,
+        (f32, f32)
+    >,
     pub baseline:Vec<(
         Uuid,             // App
         Vec<Uuid>         // List of Renders
@@ -271,13 +1303,150 @@ struct Pipeline<'a>{
     >,
     /// Buffer where E/A outputs will be stored until [R/E/A to which they are piped] are executed.
     memory_buffer:HashMap<Uuid, // Uuid of element that gives output
-        IOType<'a> // Name of output+Output itself
+        IOOutputType // Name of output+Output itself
     >,
-    current_et:EventTable
+    current_et:EventTable,
+    /// ET after each baseline App of last run, by index of App
+    pub et_history:Vec<EventTable>,
 }
-impl Pipeline<'_> {
+impl Pipeline {
+    pub fn new()->Self {
+        Self {
+            check_classes: true,
+            conflict_policy: ConflictPolicy::default(),
+            conflict_scope: None,
+            warnings: Vec::new(),
+            R_E_AList: HashMap::new(),
+            names: HashMap::new(),
+            values: HashMap::new(),
+            layout: HashMap::new(),
+            baseline: Vec::new(),
+            connections: Vec::new(),
+            execution_order: Vec::new(),
+            memory_buffer: HashMap::new(),
+            current_et: EventTable::new(),
+            et_history: Vec::new(),
+        }
+    }
+
+    // === Editing (baseline is edited directly)
+
+    /// Adds R/E/A (not attached to baseline yet) created from registered name. Alias is made unique by adding number
+    pub fn add_element(&mut self,
+        alias: &str,
+        name: &str,
+        element: R_E_A
+    )->Uuid {
+        let mut unique = alias.to_string();
+        let mut n = 1;
+        while self.names.values().any(|(a, _)| *a == unique) {
+            n += 1;
+            unique = format!("{}_{}", alias, n);
+        }
+        let uuid = Uuid::new_v4();
+        self.R_E_AList.insert(uuid, Box::new(element));
+        self.names.insert(uuid, (unique, name.to_string()));
+        uuid
+    }
+
+    /// Removes R/E/A with all its connections (and its Renders if it is baseline App)
+    pub fn remove_element(&mut self,
+        uuid: Uuid
+    ) {
+        let mut removed = vec![uuid];
+        if let Some(i) = self.baseline.iter().position(|(app, _)| *app == uuid) {
+            removed.extend(self.baseline.remove(i).1);
+        }
+        for (_, renders) in &mut self.baseline {
+            renders.retain(|r| *r != uuid);
+        }
+        for uuid in removed {
+            self.connections.retain(|(from, _, to, _)| *from != uuid && *to != uuid);
+            self.values.retain(|(to, _), _| *to != uuid);
+            self.R_E_AList.remove(&uuid);
+            self.names.remove(&uuid);
+            self.layout.remove(&uuid);
+            self.memory_buffer.remove(&uuid);
+        }
+    }
+
+    /// Ok if output of Extension from can be piped into input of R/E/A to
+    pub fn check_connection(&self,
+        from: Uuid,
+        output: &str,
+        to: Uuid,
+        input: &str
+    )->Result<(), String> {
+        let source = self.R_E_AList.get(&from).ok_or(format!("R/E/A {} not found", from))?;
+        let target = self.R_E_AList.get(&to).ok_or(format!("R/E/A {} not found", to))?;
+        let output_type = source.get_outputs()
+            .ok_or(format!("{} is Render, Renders have no outputs", source.get_name()))?
+            .get(output)
+            .ok_or(format!("{} has no output {}", source.get_name(), output))?;
+        let input_type = target.get_inputs().get(input)
+            .ok_or(format!("{} has no input {}", target.get_name(), input))?;
+        if output_type != input_type {
+            return Err(format!("Output {} of {} and input {} of {} have different types", output, source.get_name(), input, target.get_name()));
+        }
+        if from == to {
+            return Err(format!("{} can not be piped into itself", source.get_name()));
+        }
+        if self.connections.iter().any(|c| c.2 == to && c.3 == input) {
+            return Err(format!("Input {} of {} is already connected", input, target.get_name()));
+        }
+        Ok(())
+    }
+
+    pub fn connect(&mut self,
+        from: Uuid,
+        output: &str,
+        to: Uuid,
+        input: &str
+    )->Result<(), String> {
+        self.check_connection(from, output, to, input)?;
+        self.connections.push((from, output.to_string(), to, input.to_string()));
+        Ok(())
+    }
+
     // === Checks
 
+    /// Applies conflict_policy to conflicts that exist in new_et but did not exist in old_et.
+    /// Split parts and occurrences count as their original Event, so conflict of parts of Events that already conflicted is not new.
+    /// Ok(Some(warning)) if policy is Warn
+    fn check_new_conflicts(&self,
+        app_uuid: Uuid,
+        old_et: &EventTable,
+        new_et: &EventTable
+    )->Result<Option<String>, String> {
+        if self.conflict_policy == ConflictPolicy::Ignore {
+            return Ok(None);
+        }
+        let scope = self.conflict_scope.as_deref();
+        let origins = |et: &EventTable| -> HashSet<(Uuid, Uuid)> {
+            et.conflict_pairs(scope).into_iter()
+                .map(|(a, b)| (et.origin(a), et.origin(b)))
+                .flat_map(|(a, b)| [(a, b), (b, a)])
+                .collect()
+        };
+        let old = origins(old_et);
+        let mut new: Vec<(Uuid, Uuid)> = origins(new_et).into_iter()
+            .filter(|(a, b)| a <= b && !old.contains(&(*a, *b)))
+            .collect();
+        new.sort();
+        let new: Vec<String> = new.into_iter()
+            .map(|(a, b)| format!("{}<->{}", a, b))
+            .collect();
+        if new.is_empty() {
+            return Ok(None);
+        }
+
+        let message = format!("App {} introduced {} new conflict(s): {}", app_uuid, new.len(), new.join(", "));
+        match self.conflict_policy {
+            ConflictPolicy::Fail => Err(message),
+            _ => Ok(Some(message)),
+        }
+    }
+
     // === runs + required functions
 
     /// Assembles input for R/E/A from [connections and memory_buffer] for [element with UUID]
@@ -305,13 +1474,21 @@ impl Pipeline<'_> {
 
                     if let Some(index)=index_try{
                         (index.0,index.1.clone())
+                    }else if let Some(value)=self.values.get(&(*elementID,curr_input_name.clone())){ // not connected → value set directly
+                        if value.value.as_ref().type_id()!=*curr_input_type_id{
+                            return Err(format!("pull_inputs(): value of input {} of R/E/A {} has wrong type",curr_input_name,elementID));
+                        }
+                        inputs.insert(curr_input_name.to_string(), &value.value);
+                        continue;
                     }else{ //if None
-                        return Err(format!("pull_inputs(): connection for R/E/A {} with input name {} not found",
+                        return Err(format!("pull_inputs(): connection or value for R/E/A {} with input name {} not found",
                         elementID,curr_input_name));
                     }
                 };
 
-                let output_type=*(self.R_E_AList.get(&source_uuid).unwrap()).get_inputs().get(&source_name).unwrap();
+                let output_type=*(self.R_E_AList.get(&source_uuid).unwrap()).get_outputs()
+                    .and_then(|outputs| outputs.get(&source_name))
+                    .ok_or(format!("pull_inputs(): R/E/A {} has no output named {}",source_uuid,source_name))?;
 
                 let debug_string=format!("pull_inputs(): output of Extension {} named {} has type {:?}\n input of R/E/A {} with input name {} has type {:?}\n",source_uuid,source_name,output_type,
                 elementID,curr_input_name,curr_input_type_id);
@@ -323,7 +1500,7 @@ impl Pipeline<'_> {
 
 
                 // Finally getting input from self.memory_buffer yay!!
-                let wrapped_input_itself:Option<&&Box<dyn Any + Send + Sync + 'static>>=self.memory_buffer.get(&source_uuid).unwrap().get(&source_name);
+                let wrapped_input_itself:Option<&Box<dyn Any + Send + Sync + 'static>>=self.memory_buffer.get(&source_uuid).and_then(|outputs| outputs.get(&source_name));
                 if let Some(input_itself)=wrapped_input_itself{
                     inputs.insert(
                         curr_input_name.to_string(),
@@ -367,8 +1544,74 @@ impl Pipeline<'_> {
     }
 
     /// Define order of R/E/A execution with Kahn's Algorithm
-    fn generate_order_of_processing(){
+    /// Extensions are executed as early as their inputs allow: before App0, or right after App whose report they read.
+    /// Renders of App n are executed right after it (after Extensions of that chunk), before App n+1. Last Vec holds Renders of last App
+    fn generate_order_of_processing(&mut self)->Result<(), String> {
+        let extensions: Vec<Uuid> = self.R_E_AList.iter()
+            .filter(|(_, element)| matches!(***element, R_E_A::Extension(_)))
+            .map(|(uuid, _)| *uuid)
+            .collect();
+
+        let mut in_degree: HashMap<Uuid, usize> = extensions.iter().map(|uuid| (*uuid, 0)).collect();
+        for (from, _, to, _) in &self.connections {
+            if extensions.contains(from) && let Some(degree) = in_degree.get_mut(to) {
+                *degree += 1;
+            }
+        }
+        let mut ready: Vec<Uuid> = in_degree.iter().filter(|(_, degree)| **degree == 0).map(|(uuid, _)| *uuid).collect();
+        let mut ordered = Vec::new();
+        while let Some(uuid) = ready.pop() {
+            ordered.push(uuid);
+            for (from, _, to, _) in &self.connections {
+                if *from == uuid && let Some(degree) = in_degree.get_mut(to) {
+                    *degree -= 1;
+                    if *degree == 0 {
+                        ready.push(*to);
+                    }
+                }
+            }
+        }
+        if ordered.len() != extensions.len() {
+            let cycle: Vec<String> = extensions.iter()
+                .filter(|uuid| !ordered.contains(uuid))
+                .map(|uuid| self.alias_of(uuid))
+                .collect();
+            return Err(format!("Connections of Extensions {} form a cycle", cycle.join(", ")));
+        }
 
+        // chunk = index of App before which element is executed
+        let mut chunks: HashMap<Uuid, usize> = HashMap::new();
+        for (i, (app, renders)) in self.baseline.iter().enumerate() {
+            chunks.insert(*app, i);
+            chunks.extend(renders.iter().map(|render| (*render, i + 1)));
+        }
+        // earliest chunk in which output of element is available
+        let available = |chunks: &HashMap<Uuid, usize>, uuid: &Uuid| match self.R_E_AList.get(uuid).map(|element| &**element) {
+            Some(R_E_A::App(_)) => chunks.get(uuid).map(|chunk| chunk + 1),
+            _ => chunks.get(uuid).copied(),
+        };
+        for uuid in &ordered {
+            let chunk = self.connections.iter()
+                .filter(|(_, _, to, _)| to == uuid)
+                .map(|(from, _, _, _)| available(&chunks, from).ok_or(format!("{} reads output of {} that is not attached to baseline", self.alias_of(uuid), self.alias_of(from))))
+                .collect::<Result<Vec<usize>, String>>()?
+                .into_iter().max().unwrap_or(0);
+            chunks.insert(*uuid, chunk);
+        }
+        for (from, _, to, _) in &self.connections {
+            if let (Some(source), Some(target)) = (available(&chunks, from), chunks.get(to)) && source > *target {
+                return Err(format!("{} reads output of {} that is executed later", self.alias_of(to), self.alias_of(from)));
+            }
+        }
+
+        self.execution_order = vec![Vec::new(); self.baseline.len() + 1];
+        for uuid in ordered {
+            self.execution_order[chunks[&uuid]].push(uuid);
+        }
+        for (i, (_, renders)) in self.baseline.iter().enumerate() {
+            self.execution_order[i + 1].extend(renders);
+        }
+        Ok(())
     }
 
 
@@ -398,52 +1641,694 @@ impl Pipeline<'_> {
             }  // End match, borrow drops
     
             R_E_A::App(app) => {
-                let new_et = app.run(et_clone, inputs)?;
+                let (new_et, outputs) = app.run_with_outputs(et_clone, inputs)?;
+                outputs_box = Some(outputs);
+                new_et.check_self()?;
+                if let Some(warning) = self.check_new_conflicts(r_e_a_uuid, &self.current_et, &new_et)? {
+                    self.warnings.push(warning);
+                }
                 self.current_et = new_et;
-                self.current_et.check_self()?;
             }
         }
 }
         // Now safe to borrow memory_buffer
-        if let Some(outputs)=outputs_box{ //TODO
-            // let memory_cell = self.memory_buffer.get_mut(&r_e_a_uuid)
-            // .ok_or(format!("Memory cell for {} not found", r_e_a_uuid))?;
-            // memory_cell.extend(outputs);
-            todo!();
+        if let Some(outputs)=outputs_box{
+            self.memory_buffer.entry(r_e_a_uuid).or_default().extend(outputs);
         }
 
         Ok(())
     }
 
-    /// Execute part of baseline until (including) nth.
-    /// All previous parts also must be executed since it is.
-    fn run_baseline_until(&mut self, index:usize){
-        for chunk in 0..index{
-            for R_E_AUuid in self.execution_order.index(chunk){
-                // self.R_E_AList.index(R_E_AUuid).;
-                todo!();
+    /// Execute part of baseline until (including) nth App, Renders of nth App are not executed.
+    /// All previous parts also must be executed since it is, so execution always starts from empty ET.
+    /// ET after each executed App is kept in et_history
+    pub fn run_baseline_until(&mut self, index:usize)->Result<(), String>{
+        self.generate_order_of_processing()?;
+        self.memory_buffer.clear();
+        self.warnings.clear();
+        self.et_history.clear();
+        self.current_et = EventTable::new();
+
+        for chunk in 0..=index.min(self.baseline.len()){
+            for element in self.execution_order.index(chunk).clone(){
+                self.execute_element(element).map_err(|e| format!("{}: {}", self.alias_of(&element), e))?;
+            }
+            if let Some((app, _)) = self.baseline.get(chunk) {
+                let app = *app;
+                self.execute_element(app).map_err(|e| format!("{}: {}", self.alias_of(&app), e))?;
+                self.et_history.push(self.current_et.clone());
             }
         }
+        Ok(())
     }
 
-    fn run_full(&mut self){
+    /// Execute whole baseline including Renders of last App
+    pub fn run_full(&mut self)->Result<(), String>{
         self.run_baseline_until(self.baseline.len())
     }
 
+    /// Output of Extension from last run
+    pub fn output(&self,
+        element: &Uuid,
+        name: &str
+    )->Option<&(dyn Any+Send+Sync)> {
+        self.memory_buffer.get(element)?.get(name).map(|output| output.as_ref())
+    }
+
+    fn alias_of(&self, element: &Uuid)->String {
+        self.names.get(element).map(|(alias, _)| alias.clone()).unwrap_or(element.to_string())
+    }
+
 
 
     // Work with files
 
-    /// Pull pipeline from file
-    pub fn import(
-    ){
+    /// Pull pipeline from file, R/E/As are created with registry
+    pub fn import(path: &str,
+        registry: &Registry
+    )->Result<Self, String> {
+        let json = std::fs::read_to_string(path).map_err(|e| format!("Can not read {}: {}", path, e))?;
+        Self::from_json(&json, registry).map_err(|e| format!("{}: {}", path, e))
+    }
+    /// Push pipeline to file
+    /// Useful after changing pipeline with GUI
+    pub fn export(&self,
+        path: &str
+    )->Result<(), String> {
+        std::fs::write(path, self.to_json()?).map_err(|e| format!("Can not write {}: {}", path, e))
+    }
 
+    /// See README_for_devs.md "Storage of piping order"
+    pub fn from_json(json: &str,
+        registry: &Registry
+    )->Result<Self, String> {
+        let file: PipelineFile = serde_json::from_str(json).map_err(|e| format!("Wrong pipeline file: {}", e))?;
+        let mut pipeline = Self::new();
+        let mut by_alias: HashMap<String, Uuid> = HashMap::new();
+        // R/E/A is created on first mention of its alias (or direct name)
+        let mut element = |pipeline: &mut Self, alias: &str| -> Result<Uuid, String> {
+            if let Some(uuid) = by_alias.get(alias) {
+                return Ok(*uuid);
+            }
+            let name = file.alias.get(alias).map(String::as_str).unwrap_or(alias);
+            let uuid = pipeline.add_element(alias, name, registry.create(name)?);
+            by_alias.insert(alias.to_string(), uuid);
+            Ok(uuid)
+        };
+
+        let amount = file.pipeline.len();
+        for (i, entry) in file.pipeline.iter().enumerate() {
+            let (alias, renders) = match entry {
+                BaselineEntry::Single(alias) => (alias, vec![]),
+                BaselineEntry::WithRenders(map) => {
+                    let (alias, renders) = map.iter().next().filter(|_| map.len() == 1)
+                        .ok_or(format!("Baseline entry {} must have exactly one App", i))?;
+                    (alias, match renders {
+                        Renders::One(render) => vec![render.clone()],
+                        Renders::Many(renders) => renders.clone(),
+                    })
+                }
+            };
+            let uuid = element(&mut pipeline, alias)?;
+            let render_uuids = renders.iter()
+                .map(|render| element(&mut pipeline, render))
+                .collect::<Result<Vec<Uuid>, String>>()?;
+
+            match pipeline.R_E_AList[&uuid].get_class() {
+                // ending Render belongs to last App
+                R_E_AClass::Render if i == amount - 1 && renders.is_empty() => match pipeline.baseline.last_mut() {
+                    Some((_, last_renders)) => last_renders.push(uuid),
+                    None => return Err(format!("Baseline must start with App, {} is Render", alias)),
+                },
+                R_E_AClass::App => pipeline.baseline.push((uuid, vec![])),
+                _ => return Err(format!("{} in baseline must be App", alias)),
+            }
+            for (render, render_uuid) in renders.iter().zip(render_uuids) {
+                if !matches!(pipeline.R_E_AList[&render_uuid].get_class(), R_E_AClass::Render) {
+                    return Err(format!("{} is attached to {} as Render, but it is not Render", render, alias));
+                }
+                if let Some((_, renders)) = pipeline.baseline.last_mut() {
+                    renders.push(render_uuid);
+                }
+            }
+        }
+
+        for connection in &file.connections {
+            let (from, output) = connection.from.rsplit_once('.')
+                .ok_or(format!("Connection source {} must be \"Alias.output\"", connection.from))?;
+            let (to, input) = connection.to.rsplit_once('.')
+                .ok_or(format!("Connection target {} must be \"Alias.input\"", connection.to))?;
+            let (from, to) = (element(&mut pipeline, from)?, element(&mut pipeline, to)?);
+            pipeline.connect(from, output, to, input)?;
+        }
+
+        for (target, json) in &file.inputs {
+            let (alias, input) = target.rsplit_once('.')
+                .ok_or(format!("Input {} must be \"Alias.input\"", target))?;
+            let uuid = element(&mut pipeline, alias)?;
+            let type_id = *pipeline.R_E_AList[&uuid].get_inputs().get(input)
+                .ok_or(format!("{} has no input {}", alias, input))?;
+            let value = registry.parse_value(type_id, json.clone()).map_err(|e| format!("Input {}: {}", target, e))?;
+            pipeline.values.insert((uuid, input.to_string()), value);
+        }
+
+        for (alias, position) in &file.layout {
+            if let Some(uuid) = by_alias.get(alias) {
+                pipeline.layout.insert(*uuid, *position);
+            }
+        }
+        pipeline.conflict_policy = file.conflict_policy;
+        pipeline.conflict_scope = file.conflict_scope.filter(|scope| !scope.is_empty());
+        Ok(pipeline)
+    }
+
+    pub fn to_json(&self
+    )->Result<String, String> {
+        let alias_of = |uuid: &Uuid| self.names.get(uuid).map(|n| n.0.clone()).ok_or(format!("R/E/A {} has no alias", uuid));
+        let mut file = PipelineFile::default();
+        for (alias, name) in self.names.values() {
+            if alias != name {
+                file.alias.insert(alias.clone(), name.clone());
+            }
+        }
+        for (app, renders) in &self.baseline {
+            file.pipeline.push(match renders.len() {
+                0 => BaselineEntry::Single(alias_of(app)?),
+                1 => BaselineEntry::WithRenders(BTreeMap::from([(alias_of(app)?, Renders::One(alias_of(&renders[0])?))])),
+                _ => BaselineEntry::WithRenders(BTreeMap::from([(alias_of(app)?, Renders::Many(renders.iter().map(alias_of).collect::<Result<_, _>>()?))])),
+            });
+        }
+        for (from, output, to, input) in &self.connections {
+            file.connections.push(ConnectionEntry {
+                from: format!("{}.{}", alias_of(from)?, output),
+                to: format!("{}.{}", alias_of(to)?, input),
+            });
+        }
+        for ((uuid, input), value) in &self.values {
+            file.inputs.insert(format!("{}.{}", alias_of(uuid)?, input), value.to_json()?);
+        }
+        for (uuid, position) in &self.layout {
+            file.layout.insert(alias_of(uuid)?, *position);
+        }
+        file.conflict_policy = self.conflict_policy;
+        file.conflict_scope = self.conflict_scope.clone();
+        serde_json::to_string_pretty(&file).map_err(|e| e.to_string())
+    }
+}
+
+/// Pipeline file, see README_for_devs.md "Storage of piping order"
+#[derive(Default, Serialize, Deserialize)]
+struct PipelineFile {
+    /// Alias → registered name of R/E/A
+    #[serde(rename = "Alias", default, skip_serializing_if = "BTreeMap::is_empty")]
+    alias: BTreeMap<String, String>,
+    #[serde(rename = "Pipeline", default)]
+    pipeline: Vec<BaselineEntry>,
+    #[serde(rename = "Connections", default)]
+    connections: Vec<ConnectionEntry>,
+    /// "Alias.input" → value of not connected input
+    #[serde(rename = "Inputs", default, skip_serializing_if = "BTreeMap::is_empty")]
+    inputs: BTreeMap<String, serde_json::Value>,
+    /// Alias → position of node in GUI editor
+    #[serde(rename = "Layout", default, skip_serializing_if = "BTreeMap::is_empty")]
+    layout: BTreeMap<String, (f32, f32)>,
+    #[serde(rename = "ConflictPolicy", default, skip_serializing_if = "ConflictPolicy::is_ignore")]
+    conflict_policy: ConflictPolicy,
+    #[serde(rename = "ConflictScope", default, skip_serializing_if = "Option::is_none")]
+    conflict_scope: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum BaselineEntry {
+    Single(String),
+    WithRenders(BTreeMap<String, Renders>),
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum Renders {
+    One(String),
+    Many(Vec<String>),
+}
+
+/// "Alias.output" → "Alias.input"
+#[derive(Serialize, Deserialize)]
+struct ConnectionEntry {
+    from: String,
+    to: String,
+}
+
+
+
+#[cfg(test)]
+pub mod testing {
+    //! Helpers shared by tests of R/E/As
+    use super::*;
+
+    /// Inputs of R/E/A: TestInputs::new().with("from", from).with("to", to), passed as inputs.get()
+    #[derive(Default)]
+    pub struct TestInputs(Vec<(String, Box<dyn Any+Send+Sync>)>);
+
+    impl TestInputs {
+        pub fn new()->Self {
+            Self::default()
+        }
+
+        pub fn with<T: Any+Send+Sync>(mut self, name: &str, value: T)->Self {
+            self.0.push((name.to_string(), Box::new(value)));
+            self
+        }
+
+        pub fn get(&self)->IOType<'_> {
+            self.0.iter().map(|(name, value)| (name.clone(), value)).collect()
+        }
+    }
+
+    /// day.05.2026 h:m UTC (2026-05-04 is Monday)
+    pub fn at(day: u32, h: u32, m: u32)->DateTimeType {
+        Utc.with_ymd_and_hms(2026, 5, day, h, m, 0).unwrap()
+    }
+
+    /// (start, end) of Event
+    pub fn times(et: &EventTable, uuid: Uuid)->(DateTimeType, DateTimeType) {
+        let event = et.get(uuid).unwrap();
+        (event.start, event.end)
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::testing::{at, times};
+
+    fn et_of(times: &[(DateTimeType, DateTimeType)])->(EventTable, Vec<Uuid>) {
+        let mut et = EventTable::new();
+        let uuids = times.iter()
+            .map(|(start, end)| et.add_spec(EventSpec::new("E".to_string(), *start, *end)).unwrap())
+            .collect();
+        (et, uuids)
+    }
+
+    fn uuids_of(events: Vec<&Event>)->Vec<Uuid> {
+        events.into_iter().map(|e| e.uuid).collect()
+    }
+
+    /// Deterministic pseudo-random Events of different lengths over ~1.5 days, some long ones cover many short ones
+    fn random_times(mut seed: u64, n: usize)->Vec<(DateTimeType, DateTimeType)> {
+        let mut times = Vec::new();
+        for _ in 0..n {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            let start = at(4, 0, 0) + Duration::minutes((seed >> 33) as i64 % 2000);
+            let length = Duration::minutes([0, 5, 30, 90, 600][(seed >> 20) as usize % 5]);
+            times.push((start, start + length));
+        }
+        times
+    }
+
+    /// Compares overlapping() with linear scan over all Events
+    fn check_overlapping(et: &EventTable) {
+        for (from, minutes) in [(0, 1), (100, 30), (500, 0), (1500, 600), (-100, 5000)] {
+            let (start, end) = (at(4, 0, 0) + Duration::minutes(from), at(4, 0, 0) + Duration::minutes(from + minutes));
+            let expected: Vec<&Event> = et.get_events()
+                .filter(|e| e.start < end && (e.end > start || e.start >= start))
+                .collect();
+            let found = et.overlapping(start, end);
+            assert!(found.windows(2).all(|w| w[0].start <= w[1].start), "not ordered by start");
+            let (mut found, mut expected) = (uuids_of(found), uuids_of(expected));
+            found.sort();
+            expected.sort();
+            assert_eq!(found, expected, "window {}+{} min", from, minutes);
+        }
+    }
+
+    #[test]
+    fn overlapping_matches_linear_scan() {
+        let (et, _) = et_of(&random_times(7, 200));
+        check_overlapping(&et);
+    }
+
+    #[test]
+    fn on_date_and_next_after_match_linear_scan() {
+        let (et, _) = et_of(&random_times(11, 200));
+        for day in 3..=6 {
+            let date = NaiveDate::from_ymd_opt(2026, 5, day).unwrap();
+            let (start, end) = (date.and_time(NaiveTime::MIN).and_utc(), (date + Duration::days(1)).and_time(NaiveTime::MIN).and_utc());
+            let mut expected = uuids_of(et.get_events().filter(|e| e.start < end && (e.end > start || e.start >= start)).collect());
+            let mut found = uuids_of(et.on_date(date));
+            found.sort();
+            expected.sort();
+            assert_eq!(found, expected, "{}", date);
+        }
+        for minutes in [-10, 0, 1, 333, 1000, 1999, 2000, 5000] {
+            let t = at(4, 0, 0) + Duration::minutes(minutes);
+            let expected = et.get_events().filter(|e| e.start >= t).min_by_key(|e| (e.start, e.end, e.uuid));
+            assert_eq!(et.next_after(t).map(|e| e.uuid), expected.map(|e| e.uuid), "{} min", minutes);
+        }
+    }
+
+    #[test]
+    fn free_gaps_match_linear_scan() {
+        let (et, _) = et_of(&random_times(13, 40));
+        for (from, minutes) in [(0, 60), (-100, 2500), (700, 300), (1990, 100)] {
+            let (start, end) = (at(4, 0, 0) + Duration::minutes(from), at(4, 0, 0) + Duration::minutes(from + minutes));
+            let gaps = et.free_gaps(start, end);
+            assert!(gaps.iter().all(|(s, e)| start <= *s && s < e && *e <= end));
+            assert!(gaps.windows(2).all(|w| w[0].1 < w[1].0), "gaps are not ordered or touch each other");
+            // every minute is either free or covered by some Event
+            for minute in from..from + minutes {
+                let t = at(4, 0, 0) + Duration::minutes(minute);
+                let covered = et.get_events().any(|e| e.start <= t && t < e.end);
+                let free = gaps.iter().any(|(s, e)| *s <= t && t < *e);
+                assert_ne!(covered, free, "minute {}", minute);
+            }
+        }
+        assert_eq!(EventTable::new().free_gaps(at(4, 9, 0), at(4, 10, 0)), vec![(at(4, 9, 0), at(4, 10, 0))]);
+    }
+
+    #[test]
+    fn free_slot_matches_linear_scan() {
+        let intervals = random_times(17, 40);
+        let busy = BusyTime::new(intervals.iter().copied());
+        assert!(busy.after(DateTimeType::MIN_UTC).windows(2).all(|w| w[0].1 < w[1].0), "intervals are not merged");
+        for (from, minutes) in [(-60, 30), (0, 1), (100, 45), (600, 120), (1200, 0), (2500, 60)] {
+            let (t, length) = (at(4, 0, 0) + Duration::minutes(from), Duration::minutes(minutes));
+            // repeat until slot overlaps no interval
+            let mut expected = t;
+            while let Some((_, end)) = intervals.iter().find(|(start, end)| start < end && *start < expected + length && *end > expected) {
+                expected = *end;
+            }
+            assert_eq!(busy.free_slot(t, length), expected, "from {} for {} min", from, minutes);
+        }
+        assert_eq!(busy.end(), intervals.iter().map(|(_, end)| *end).max());
+    }
+
+    #[test]
+    fn free_slot_skips_busy_time() {
+        let busy = BusyTime::new([(at(4, 12, 0), at(4, 13, 0)), (at(4, 10, 0), at(4, 11, 0)), (at(4, 9, 0), at(4, 10, 0)), (at(4, 9, 30), at(4, 9, 30))]);
+        assert_eq!(busy.after(DateTimeType::MIN_UTC), &[(at(4, 9, 0), at(4, 11, 0)), (at(4, 12, 0), at(4, 13, 0))]);
+        assert_eq!(busy.free_slot(at(4, 8, 0), Duration::hours(1)), at(4, 8, 0));
+        assert_eq!(busy.free_slot(at(4, 9, 30), Duration::hours(1)), at(4, 11, 0));
+        assert_eq!(busy.free_slot(at(4, 9, 30), Duration::hours(2)), at(4, 13, 0));
+        assert_eq!(busy.after(at(4, 11, 0)), &[(at(4, 12, 0), at(4, 13, 0))]);
+        assert_eq!(BusyTime::default().free_slot(at(4, 9, 30), Duration::hours(2)), at(4, 9, 30));
+    }
+
+    #[test]
+    fn overlapping_uses_half_open_intervals() {
+        let (et, uuids) = et_of(&[(at(4, 9, 0), at(4, 10, 0)), (at(4, 10, 0), at(4, 10, 0)), (at(4, 10, 0), at(4, 11, 0))]);
+        assert_eq!(uuids_of(et.overlapping(at(4, 9, 0), at(4, 10, 0))), vec![uuids[0]]);
+        // zero-length Event at window start is inside the window
+        assert_eq!(uuids_of(et.overlapping(at(4, 10, 0), at(4, 10, 30))), vec![uuids[1], uuids[2]]);
+        assert!(et.overlapping(at(4, 11, 0), at(4, 12, 0)).is_empty());
+    }
+
+    #[test]
+    fn index_is_updated_after_modifications() {
+        let (mut et, uuids) = et_of(&[(at(4, 9, 0), at(4, 10, 0)), (at(4, 12, 0), at(4, 13, 0))]);
+        assert_eq!(uuids_of(et.overlapping(at(4, 12, 0), at(4, 12, 30))), vec![uuids[1]]);
+
+        et.set_times(uuids[0], at(4, 12, 15), at(4, 12, 45)).unwrap();
+        assert_eq!(uuids_of(et.overlapping(at(4, 12, 0), at(4, 12, 30))), vec![uuids[1], uuids[0]]);
+
+        let children = et.split(uuids[1], vec![(at(4, 14, 0), at(4, 14, 30), " (1)".to_string()), (at(4, 15, 0), at(4, 15, 30), " (2)".to_string())]).unwrap();
+        assert_eq!(uuids_of(et.overlapping(at(4, 12, 0), at(4, 12, 30))), vec![uuids[0]]);
+        assert_eq!(uuids_of(et.overlapping(at(4, 14, 0), at(4, 16, 0))), children);
+
+        et.add_spec(EventSpec::new("New".to_string(), at(4, 14, 10), at(4, 14, 20))).unwrap();
+        assert_eq!(et.overlapping(at(4, 14, 0), at(4, 14, 30)).len(), 2);
+    }
+
+    #[test]
+    fn index_matches_linear_scan_after_many_modifications() {
+        let (mut et, uuids) = et_of(&random_times(17, 150));
+        let mut seed: u64 = 19;
+        for (i, uuid) in uuids.into_iter().enumerate() {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            let start = at(4, 0, 0) + Duration::minutes((seed >> 33) as i64 % 2000);
+            match i % 3 {
+                0 => et.set_times(uuid, start, start + Duration::minutes(45)).unwrap(),
+                1 => {
+                    et.split(uuid, vec![(start, start + Duration::minutes(20), " (1)".to_string()), (start, start, " (2)".to_string())]).unwrap();
+                }
+                _ => {
+                    et.add_spec(EventSpec::new("New".to_string(), start, start + Duration::minutes(300))).unwrap();
+                }
+            }
+        }
+        check_overlapping(&et);
+        assert_eq!(et.index.sorted().len(), et.get_events().count());
+    }
+
+    #[test]
+    fn invalid_changes_are_rejected() {
+        let (mut et, uuids) = et_of(&[(at(4, 9, 0), at(4, 10, 0))]);
+        assert!(et.set_times(uuids[0], at(4, 11, 0), at(4, 10, 0)).is_err());
+        assert_eq!(times(&et, uuids[0]), (at(4, 9, 0), at(4, 10, 0)));
+        assert!(et.set_times(Uuid::new_v4(), at(4, 9, 0), at(4, 10, 0)).is_err());
+
+        let mut spec = EventSpec::new("Same".to_string(), at(4, 9, 0), at(4, 10, 0));
+        spec.uuid = Some(uuids[0]);
+        assert!(et.add_spec(spec).is_err());
+        assert!(et.add_spec(EventSpec::new("Reversed".to_string(), at(4, 10, 0), at(4, 9, 0))).is_err());
+        assert_eq!(et.get_events().count(), 1);
+        assert!(et.check_self().is_ok());
+    }
+
+    #[test]
+    fn serialization_roundtrip_checks_invariants() {
+        let (mut et, uuids) = et_of(&[(at(4, 9, 0), at(4, 10, 0)), (at(4, 12, 0), at(4, 13, 0))]);
+        et.set_field(uuids[0], "room", Some("A".to_string())).unwrap();
+        et.split(uuids[1], vec![(at(4, 12, 0), at(4, 12, 30), " (1)".to_string()), (at(4, 12, 30), at(4, 13, 0), " (2)".to_string())]).unwrap();
+
+        for loaded in [EventTable::from_json(&et.to_json().unwrap()).unwrap(), EventTable::from_binary(&et.to_binary().unwrap()).unwrap()] {
+            assert_eq!(loaded.get_events().count(), 3);
+            for event in et.get_events() {
+                let same = loaded.get(event.uuid).unwrap();
+                assert_eq!((same.get_name(), same.start, same.end, &same.fields, same.parent), (event.get_name(), event.start, event.end, &event.fields, event.parent));
+            }
+            // ids and index are rebuilt
+            assert_eq!(uuids_of(loaded.overlapping(at(4, 9, 0), at(4, 9, 30))), vec![uuids[0]]);
+        }
+
+        let json: serde_json::Value = serde_json::from_str(&et.to_json().unwrap()).unwrap();
+        let mut duplicated = json.clone();
+        duplicated["events"][1]["uuid"] = json["events"][0]["uuid"].clone();
+        assert!(EventTable::from_json(&duplicated.to_string()).unwrap_err().contains("duplicated"));
+        let mut reversed = json.clone();
+        reversed["events"][0]["end"] = serde_json::json!("2026-05-04T08:00:00Z");
+        assert!(EventTable::from_json(&reversed.to_string()).is_err());
     }
-    /// Push pipeline from file
-    /// Currently obsolete, but can be useful after changing pipeline with GUI
-    pub fn export(){
-        // std::fs::
 
-        //use std::path::{Path, PathBuf};
+    /// Wall-clock time in 2026 (DST starts 2026-03-29 02:00, ends 2026-10-25 03:00 in Europe/Berlin)
+    fn local(month: u32, day: u32, h: u32, m: u32)->NaiveDateTime {
+        NaiveDate::from_ymd_opt(2026, month, day).unwrap().and_hms_opt(h, m, 0).unwrap()
     }
-}
\ No newline at end of file
+
+    fn utc(month: u32, day: u32, h: u32, m: u32)->DateTimeType {
+        local(month, day, h, m).and_utc()
+    }
+
+    fn berlin_event(et: &mut EventTable, start: NaiveDateTime, end: NaiveDateTime)->Uuid {
+        let zone = EventZone::Tz(chrono_tz::Europe::Berlin);
+        let mut spec = EventSpec::new("E".to_string(), zone.resolve_local(start), zone.resolve_local(end));
+        spec.zone = zone;
+        et.add_spec(spec).unwrap()
+    }
+
+    #[test]
+    fn local_times_over_dst_transitions() {
+        let mut et = EventTable::new();
+        let uuid = berlin_event(&mut et, local(3, 28, 1, 0), local(3, 28, 3, 0));
+        // nonexistent 02:30 is shifted by the gap (→ 03:30 CEST = 01:30 UTC), 04:00 CEST = 02:00 UTC
+        et.set_local_times(uuid, local(3, 29, 2, 30), local(3, 29, 4, 0)).unwrap();
+        assert_eq!(times(&et, uuid), (utc(3, 29, 1, 30), utc(3, 29, 2, 0)));
+        // ambiguous 02:30 is the earlier one (CEST = 00:30 UTC)
+        et.set_local_times(uuid, local(10, 25, 2, 30), local(10, 25, 4, 0)).unwrap();
+        assert_eq!(times(&et, uuid), (utc(10, 25, 0, 30), utc(10, 25, 3, 0)));
+        assert!(et.set_local_times(uuid, local(10, 25, 4, 0), local(10, 25, 3, 0)).is_err());
+    }
+
+    #[test]
+    fn shift_keeps_wall_clock_time() {
+        let mut et = EventTable::new();
+        let uuid = berlin_event(&mut et, local(3, 28, 9, 0), local(3, 28, 10, 0));
+        assert_eq!(et.get(uuid).unwrap().start, utc(3, 28, 8, 0));
+        et.shift(uuid, Duration::days(1)).unwrap();
+        // still 9:00-10:00 local, but in CEST
+        assert_eq!(times(&et, uuid), (utc(3, 29, 7, 0), utc(3, 29, 8, 0)));
+
+        // UTC Event keeps absolute length
+        let (mut et, uuids) = et_of(&[(utc(3, 28, 9, 0), utc(3, 28, 10, 0))]);
+        et.shift(uuids[0], Duration::days(1)).unwrap();
+        assert_eq!(et.get(uuids[0]).unwrap().start, utc(3, 29, 9, 0));
+    }
+
+    #[test]
+    fn set_zone_and_make_timed() {
+        let berlin = EventZone::Tz(chrono_tz::Europe::Berlin);
+        // absolute time is kept when zone of timed Event changes
+        let (mut et, uuids) = et_of(&[(utc(3, 29, 7, 0), utc(3, 29, 8, 0))]);
+        et.set_zone(uuids[0], berlin).unwrap();
+        assert_eq!(et.get(uuids[0]).unwrap().start, utc(3, 29, 7, 0));
+        // Floating keeps wall-clock time: 9:00 CEST
+        et.set_zone(uuids[0], EventZone::Floating).unwrap();
+        assert_eq!(et.get(uuids[0]).unwrap().start, utc(3, 29, 9, 0));
+        et.set_zone(uuids[0], berlin).unwrap();
+        assert_eq!(et.get(uuids[0]).unwrap().start, utc(3, 29, 7, 0));
+        // Floating 02:30-03:00 on DST start day: start is shifted by the gap (03:30 CEST) and length is kept
+        et.set_zone(uuids[0], EventZone::Floating).unwrap();
+        et.set_times(uuids[0], utc(3, 29, 2, 30), utc(3, 29, 3, 0)).unwrap();
+        et.set_zone(uuids[0], berlin).unwrap();
+        assert_eq!(times(&et, uuids[0]), (utc(3, 29, 1, 30), utc(3, 29, 2, 0)));
+
+        // 02:30 CEST - 02:10 CET is 40 minutes long, but would be Floating 02:30-02:10 → rejected, Event is not changed
+        let (mut et, uuids) = et_of(&[(utc(10, 25, 0, 30), utc(10, 25, 1, 10))]);
+        et.set_zone(uuids[0], berlin).unwrap();
+        assert!(et.set_zone(uuids[0], EventZone::Floating).is_err());
+        let event = et.get(uuids[0]).unwrap();
+        assert_eq!((event.start, event.end, event.zone), (utc(10, 25, 0, 30), utc(10, 25, 1, 10), berlin));
+
+        // all-day day of DST start is 23 hours long in Berlin
+        let mut et = EventTable::new();
+        let uuid = et.add_spec(EventSpec::new_all_day("Day".to_string(), local(3, 29, 0, 0).date(), local(3, 30, 0, 0).date())).unwrap();
+        et.make_timed(uuid, berlin).unwrap();
+        let event = et.get(uuid).unwrap();
+        assert_eq!((event.start, event.end, event.all_day, event.zone), (utc(3, 28, 23, 0), utc(3, 29, 22, 0), None, berlin));
+        // timed Event stays timed with its zone
+        et.set_zone(uuid, EventZone::Utc).unwrap();
+        assert_eq!((et.get(uuid).unwrap().start, et.get(uuid).unwrap().all_day), (utc(3, 28, 23, 0), None));
+    }
+
+    fn all_day(et: &mut EventTable, first: u32, after_last: u32)->Uuid {
+        let date = |day| NaiveDate::from_ymd_opt(2026, 5, day).unwrap();
+        et.add_spec(EventSpec::new_all_day("Day".to_string(), date(first), date(after_last))).unwrap()
+    }
+
+    #[test]
+    fn all_day_events_use_half_open_date_ranges() {
+        let mut et = EventTable::new();
+        let monday = all_day(&mut et, 4, 5);
+        let tuesday = all_day(&mut et, 5, 6);
+        let two_days = all_day(&mut et, 5, 7);
+        let date = |day| NaiveDate::from_ymd_opt(2026, 5, day).unwrap();
+
+        // day after the last one is not part of Event
+        assert_eq!(uuids_of(et.on_date(date(4))), vec![monday]);
+        let mut on_tuesday = uuids_of(et.on_date(date(5)));
+        on_tuesday.sort();
+        let mut expected = vec![tuesday, two_days];
+        expected.sort();
+        assert_eq!(on_tuesday, expected);
+        assert_eq!(uuids_of(et.on_date(date(6))), vec![two_days]);
+        assert!(et.on_date(date(7)).is_empty());
+
+        // neighbouring days do not conflict, overlapping days do
+        let pairs = et.conflict_pairs(None);
+        assert_eq!(pairs.len(), 1);
+        assert!(pairs[0] == (tuesday, two_days) || pairs[0] == (two_days, tuesday));
+        assert_eq!(et.free_gaps(at(3, 0, 0), at(8, 0, 0)), vec![(at(3, 0, 0), at(4, 0, 0)), (at(7, 0, 0), at(8, 0, 0))]);
+    }
+
+    #[test]
+    fn split_children_stay_all_day_only_on_midnights() {
+        let mut et = EventTable::new();
+        let trip = all_day(&mut et, 4, 8);
+        et.set_field(trip, "place", Some("Rome".to_string())).unwrap();
+        let children = et.split(trip, vec![
+            (at(4, 0, 0), at(5, 0, 0), " (1)".to_string()),
+            (at(5, 0, 0), at(5, 12, 0), " (2)".to_string()),
+            (at(5, 12, 0), at(6, 0, 0), " (3)".to_string()),
+            (at(6, 0, 0), at(8, 0, 0), " (4)".to_string()),
+        ]).unwrap();
+        let date = |day| NaiveDate::from_ymd_opt(2026, 5, day).unwrap();
+        let all_days: Vec<_> = children.iter().map(|uuid| et.get(*uuid).unwrap().all_day).collect();
+        assert_eq!(all_days, vec![Some((date(4), date(5))), None, None, Some((date(6), date(8)))]);
+        assert!(children.iter().map(|uuid| et.get(*uuid).unwrap())
+            .all(|e| e.parent == Some(trip) && e.zone == EventZone::Floating && e.fields["place"] == "Rome"));
+        assert!(et.get(trip).is_none());
+
+        // midnights are taken in zone of Event: UTC midnight is not midnight in Berlin
+        let mut et = EventTable::new();
+        let trip = all_day(&mut et, 4, 6);
+        et.set_zone(trip, EventZone::Tz(chrono_tz::Europe::Berlin)).unwrap();
+        let children = et.split(trip, vec![
+            (at(3, 22, 0), at(4, 22, 0), " (1)".to_string()),
+            (at(4, 22, 0), at(5, 0, 0), " (2)".to_string()),
+            (at(5, 0, 0), at(5, 22, 0), " (3)".to_string()),
+        ]).unwrap();
+        let all_days: Vec<_> = children.iter().map(|uuid| et.get(*uuid).unwrap().all_day).collect();
+        assert_eq!(all_days, vec![Some((date(4), date(5))), None, None]);
+    }
+
+    fn pipeline_with(policy: ConflictPolicy, scope: Option<&str>)->Pipeline {
+        let mut pipeline = Pipeline::new();
+        pipeline.conflict_policy = policy;
+        pipeline.conflict_scope = scope.map(str::to_string);
+        pipeline
+    }
+
+    #[test]
+    fn warn_policy_reports_new_conflicts() {
+        let pipeline = pipeline_with(ConflictPolicy::Warn, None);
+        let (old, _) = et_of(&[(at(4, 9, 0), at(4, 11, 0)), (at(4, 13, 0), at(4, 14, 0))]);
+        let mut new = old.clone();
+        new.add("Overlapping".to_string(), at(4, 10, 0), at(4, 12, 0)).unwrap();
+        let warning = pipeline.check_new_conflicts(Uuid::nil(), &old, &new).unwrap().unwrap();
+        assert!(warning.contains("1 new conflict"), "{}", warning);
+
+        // conflict that already existed is not new
+        assert_eq!(pipeline.check_new_conflicts(Uuid::nil(), &new, &new.clone()), Ok(None));
+    }
+
+    #[test]
+    fn fail_policy_returns_error_and_ignore_checks_nothing() {
+        let (old, _) = et_of(&[(at(4, 9, 0), at(4, 11, 0))]);
+        let mut new = old.clone();
+        new.add("Overlapping".to_string(), at(4, 10, 0), at(4, 12, 0)).unwrap();
+        let error = pipeline_with(ConflictPolicy::Fail, None).check_new_conflicts(Uuid::nil(), &old, &new).unwrap_err();
+        assert!(error.contains("new conflict"), "{}", error);
+        assert_eq!(pipeline_with(ConflictPolicy::Ignore, None).check_new_conflicts(Uuid::nil(), &old, &new), Ok(None));
+    }
+
+    #[test]
+    fn split_parts_of_conflicting_events_are_not_new_conflicts() {
+        let (old, uuids) = et_of(&[(at(4, 9, 0), at(4, 11, 0)), (at(4, 10, 0), at(4, 12, 0))]);
+        let mut new = old.clone();
+        for uuid in uuids {
+            let (start, end) = times(&new, uuid);
+            let middle = start + (end - start) / 2;
+            new.split(uuid, vec![(start, middle, " (1/2)".to_string()), (middle, end, " (2/2)".to_string())]).unwrap();
+        }
+        // halves of both Events conflict, but their originals already did
+        assert_eq!(new.conflict_pairs(None).len(), 1);
+        assert_eq!(pipeline_with(ConflictPolicy::Warn, None).check_new_conflicts(Uuid::nil(), &old, &new), Ok(None));
+    }
+
+    #[test]
+    fn scoped_policy_reports_only_conflicts_within_scope() {
+        let (mut old, uuids) = et_of(&[(at(4, 9, 0), at(4, 11, 0))]);
+        old.set_field(uuids[0], "assignee", Some("Anna".to_string())).unwrap();
+        let mut new = old.clone();
+        new.add("Other".to_string(), at(4, 10, 0), at(4, 12, 0)).unwrap();
+        let other = new.get_events().find(|e| e.uuid != uuids[0]).unwrap().uuid;
+        new.set_field(other, "assignee", Some("Ben".to_string())).unwrap();
+        let pipeline = pipeline_with(ConflictPolicy::Warn, Some("assignee"));
+        assert_eq!(pipeline.check_new_conflicts(Uuid::nil(), &old, &new), Ok(None));
+
+        new.set_field(other, "assignee", Some("Anna".to_string())).unwrap();
+        assert!(pipeline.check_new_conflicts(Uuid::nil(), &old, &new).unwrap().is_some());
+    }
+
+    #[test]
+    fn conflict_settings_are_saved_in_pipeline_file() {
+        let pipeline = pipeline_with(ConflictPolicy::Fail, Some("assignee"));
+        let loaded = Pipeline::from_json(&pipeline.to_json().unwrap(), &Registry::new()).unwrap();
+        assert_eq!((loaded.conflict_policy, loaded.conflict_scope.as_deref()), (ConflictPolicy::Fail, Some("assignee")));
+
+        let json = pipeline_with(ConflictPolicy::Ignore, None).to_json().unwrap();
+        assert!(!json.contains("Conflict"), "{}", json);
+    }
+}
@@ -1,30 +1,893 @@
 //Responsible for default GUI of the REA project
 
+use std::any::TypeId;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use chrono::{Duration, DurationRound, NaiveDate, NaiveDateTime, Utc};
+use chrono_tz::Tz;
 use eframe::egui;
+use egui::epaint::CubicBezierShape;
+use egui::{Align2, Color32, FontId, Pos2, Rect, Sense, Stroke, Vec2};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::core::{ConflictPolicy, DateTimeType, Event, EventSpec, EventTable, EventZone, InputValue, Pipeline, R_E_AClass, R_E_AGeneric, Registry, R_E_A};
+use crate::std::calendar::CalendarView;
+use crate::std::filter::stream_of;
+
+/// winit allows only one event loop per process, even after its window is closed
+static EVENT_LOOP_CLAIMED: AtomicBool = AtomicBool::new(false);
+
+/// true if caller may create the event loop (e.g. eframe::run_native), false if it was already created
+pub fn claim_event_loop()->bool {
+    !EVENT_LOOP_CLAIMED.swap(true, Ordering::SeqCst)
+}
+
 pub fn main() -> Result<(), eframe::Error> {
+    claim_event_loop();
     eframe::run_native(
-        "app_name",
+        "REA",
         eframe::NativeOptions::default(),
-        Box::new(|_cc| Box::new(GUIrea::default())),
+        Box::new(|_cc| Box::new(GUIrea::new())),
     )
 }
 
+/// What is selected in PipeView, shared by all panels
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Selection {
+    Element(Uuid),
+    /// Index in Pipeline.connections
+    Connection(usize),
+    /// ET pipe after n-th baseline App (to next App and Renders of n-th App)
+    BaselineEdge(usize),
+}
+
 /// Master view, controls panels of the window(s)
-struct GUIrea {}
+struct GUIrea {
+    pipeline: Pipeline,
+    registry: Registry,
+    editors: InputEditors,
+    selected: Option<Selection>,
+    pipe_view: PipeView,
+    inputs_view: InputsView,
+    data_view: DataView,
+}
 
-/// Panel, shows which info is transmitted in selected pipe
-struct DataView {}
+impl GUIrea {
+    fn new()->Self {
+        let mut registry = Registry::new();
+        crate::std::register_all(&mut registry);
+        Self {
+            pipeline: Pipeline::new(),
+            registry,
+            editors: InputEditors::new(),
+            selected: None,
+            pipe_view: PipeView::default(),
+            inputs_view: InputsView {},
+            data_view: DataView::default(),
+        }
+    }
+}
 
-/// Panel, graphically shows connections of the R/E/As
-struct PipeView {}
+impl eframe::App for GUIrea {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        egui::TopBottomPanel::top("toolbar")
+            .show(ctx, |ui| self.pipe_view.toolbar_ui(ui, &mut self.pipeline, &self.registry, &mut self.selected));
+        egui::SidePanel::right("inputs")
+            .resizable(true)
+            .show(ctx, |ui| self.inputs_view.ui(ui, &mut self.pipeline, &self.registry, &self.editors, self.selected));
+        egui::TopBottomPanel::bottom("data")
+            .resizable(true)
+            .show(ctx, |ui| self.data_view.ui(ui, &mut self.pipeline, &self.registry, self.selected));
+        egui::SidePanel::left("palette")
+            .resizable(true)
+            .show(ctx, |ui| self.pipe_view.palette_ui(ui, &mut self.pipeline, &self.registry, &mut self.selected));
+        egui::CentralPanel::default()
+            .show(ctx, |ui| self.pipe_view.ui(ui, &mut self.pipeline, &self.registry, &mut self.selected));
+    }
+}
+
+
+
+// ==========PIPE VIEW==========
+
+const NODE_WIDTH: f32 = 170.0;
+const NODE_HEADER: f32 = 34.0;
+const PORT_SPACING: f32 = 18.0;
+const PORT_RADIUS: f32 = 5.0;
+/// Distance between baseline Apps
+const SPINE_STEP: f32 = 260.0;
+/// Baseline Apps are placed on this height, Extensions above, Renders below
+const SPINE_Y: f32 = 260.0;
+const RENDER_GAP: f32 = 40.0;
+/// Pointer closer than this to pipe selects it
+const PIPE_HIT_DISTANCE: f32 = 6.0;
+
+/// Panel, graphically shows connections of the R/E/As: baseline as horizontal spine, Extensions above, Renders below their Apps
+#[derive(Default)]
+struct PipeView {
+    /// Pipeline file
+    path: String,
+    /// Result of last action
+    status: Option<String>,
+    /// (Extension, output name) from which connection is being dragged
+    dragging: Option<(Uuid, String)>,
+    /// Panning of canvas
+    offset: Vec2,
+}
+
+/// Changes collected while pipeline is drawn, applied after
+enum EditAction {
+    Select(Option<Selection>),
+    Move(Uuid, Vec2),
+    Remove(Uuid),
+    /// Move baseline App by index one step left (false) or right (true)
+    Reorder(usize, bool),
+    Disconnect(usize),
+    StartDrag(Uuid, String),
+    Connect(Uuid, String, Uuid, String),
+}
+
+/// (name, type) of inputs or outputs
+type PortList = Vec<(String, TypeId)>;
 
-/// Panel, shows input of the selected R/E/A
+/// Inputs and outputs of R/E/A, ordered by name
+fn ports(element: &R_E_A)->(PortList, PortList) {
+    let sorted = |list: &crate::core::IOListOfTypes| {
+        let mut list: PortList = list.iter().map(|(n, t)| (n.clone(), *t)).collect();
+        list.sort();
+        list
+    };
+    (sorted(element.get_inputs()), element.get_outputs().map(sorted).unwrap_or_default())
+}
+
+fn node_size(element: &R_E_A)->Vec2 {
+    let (inputs, outputs) = ports(element);
+    Vec2::new(NODE_WIDTH, NODE_HEADER + PORT_SPACING * inputs.len().max(outputs.len()) as f32 + 6.0)
+}
+
+fn class_color(class: &R_E_AClass)->Color32 {
+    match class {
+        R_E_AClass::App => Color32::from_rgb(60, 90, 150),
+        R_E_AClass::Extension => Color32::from_rgb(60, 130, 80),
+        R_E_AClass::Render => Color32::from_rgb(160, 100, 50),
+    }
+}
+
+/// Distance from p to cubic Bézier curve (approximated)
+fn distance_to_curve(points: [Pos2; 4], p: Pos2)->f32 {
+    let shape = CubicBezierShape::from_points_stroke(points, false, Color32::TRANSPARENT, Stroke::NONE);
+    shape.flatten(Some(1.0)).windows(2)
+        .map(|w| distance_to_segment(w[0], w[1], p))
+        .fold(f32::INFINITY, f32::min)
+}
+
+fn distance_to_segment(a: Pos2, b: Pos2, p: Pos2)->f32 {
+    let ab = b - a;
+    let t = if ab.length_sq() == 0.0 { 0.0 } else { ((p - a).dot(ab) / ab.length_sq()).clamp(0.0, 1.0) };
+    (a + ab * t).distance(p)
+}
+
+/// Curve from output port to input port
+fn pipe_points(from: Pos2, to: Pos2)->[Pos2; 4] {
+    let bend = ((to.x - from.x).abs() / 2.0).max(40.0);
+    [from, from + Vec2::new(bend, 0.0), to - Vec2::new(bend, 0.0), to]
+}
+
+impl PipeView {
+    /// Top-left corners of nodes in canvas coordinates. Extensions without saved position get one
+    fn positions(pipeline: &mut Pipeline)->HashMap<Uuid, Pos2> {
+        let mut positions = HashMap::new();
+        for (i, (app, renders)) in pipeline.baseline.iter().enumerate() {
+            let x = i as f32 * SPINE_STEP;
+            positions.insert(*app, Pos2::new(x, SPINE_Y));
+            let mut y = SPINE_Y + node_size(&pipeline.R_E_AList[app]).y + RENDER_GAP;
+            for render in renders {
+                positions.insert(*render, Pos2::new(x, y));
+                y += node_size(&pipeline.R_E_AList[render]).y + RENDER_GAP / 2.0;
+            }
+        }
+
+        let mut free: Vec<(String, Uuid)> = pipeline.R_E_AList.keys()
+            .filter(|uuid| !positions.contains_key(uuid))
+            .map(|uuid| (pipeline.names.get(uuid).map(|n| n.0.clone()).unwrap_or_default(), *uuid))
+            .collect();
+        free.sort();
+        for (i, (_, uuid)) in free.into_iter().enumerate() {
+            let (x, y) = *pipeline.layout.entry(uuid).or_insert((i as f32 * (NODE_WIDTH + 30.0), 20.0));
+            positions.insert(uuid, Pos2::new(x, y));
+        }
+        positions
+    }
+
+    fn toolbar_ui(&mut self, ui: &mut egui::Ui, pipeline: &mut Pipeline, registry: &Registry, selected: &mut Option<Selection>) {
+        ui.horizontal(|ui| {
+            ui.label("Pipeline file:");
+            ui.text_edit_singleline(&mut self.path);
+            if ui.button("Load").clicked() {
+                match Pipeline::import(&self.path, registry) {
+                    Ok(loaded) => {
+                        *pipeline = loaded;
+                        *selected = None;
+                        self.status = Some(format!("Loaded {}", self.path));
+                    }
+                    Err(e) => self.status = Some(e),
+                }
+            }
+            if ui.button("Save").clicked() {
+                self.status = Some(match pipeline.export(&self.path) {
+                    Ok(()) => format!("Saved {}", self.path),
+                    Err(e) => e,
+                });
+            }
+            if ui.button("New").clicked() {
+                *pipeline = Pipeline::new();
+                *selected = None;
+                self.status = None;
+            }
+            ui.separator();
+            ui.label("New conflicts:");
+            egui::ComboBox::from_id_source("conflict_policy")
+                .selected_text(format!("{:?}", pipeline.conflict_policy))
+                .show_ui(ui, |ui| {
+                    for policy in ConflictPolicy::ALL {
+                        ui.selectable_value(&mut pipeline.conflict_policy, policy, format!("{:?}", policy));
+                    }
+                });
+            let mut scope = pipeline.conflict_scope.clone().unwrap_or_default();
+            let scope_edit = egui::TextEdit::singleline(&mut scope).hint_text("scope field").desired_width(100.0);
+            if ui.add(scope_edit).on_hover_text("Only Events with same value of this field conflict (e.g. assignee)").changed() {
+                pipeline.conflict_scope = Some(scope).filter(|scope| !scope.is_empty());
+            }
+            if let Some(status) = &self.status {
+                ui.label(status);
+            }
+        });
+    }
+
+    /// List of registered R/E/As that can be added
+    fn palette_ui(&mut self, ui: &mut egui::Ui, pipeline: &mut Pipeline, registry: &Registry, selected: &mut Option<Selection>) {
+        ui.heading("R/E/As");
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            for name in registry.names() {
+                let Ok(element) = registry.create(name) else { continue };
+                let class = element.get_class();
+                let hint = match class {
+                    R_E_AClass::App => "App: appended to baseline",
+                    R_E_AClass::Extension => "Extension",
+                    R_E_AClass::Render => "Render: attached to selected (or last) App",
+                };
+                let button = egui::Button::new(egui::RichText::new(name).color(Color32::WHITE)).fill(class_color(class));
+                if !ui.add(button).on_hover_text(hint).clicked() {
+                    continue;
+                }
+
+                let target_app = match *selected {
+                    Some(Selection::Element(uuid)) => pipeline.baseline.iter().position(|(app, _)| *app == uuid),
+                    _ => None,
+                }.or(pipeline.baseline.len().checked_sub(1));
+                if matches!(class, R_E_AClass::Render) && target_app.is_none() {
+                    self.status = Some("Add App before adding Render".into());
+                    continue;
+                }
+                let is_app = matches!(class, R_E_AClass::App);
+                let is_render = matches!(class, R_E_AClass::Render);
+                let uuid = pipeline.add_element(name, name, element);
+                if is_app {
+                    pipeline.baseline.push((uuid, vec![]));
+                } else if let (true, Some(i)) = (is_render, target_app) {
+                    pipeline.baseline[i].1.push(uuid);
+                }
+                *selected = Some(Selection::Element(uuid));
+            }
+        });
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui, pipeline: &mut Pipeline, registry: &Registry, selected: &mut Option<Selection>) {
+        let (canvas_response, painter) = ui.allocate_painter(ui.available_size(), Sense::click_and_drag());
+        let canvas = canvas_response.rect;
+        if canvas_response.dragged() && self.dragging.is_none() {
+            self.offset += canvas_response.drag_delta();
+        }
+        let origin = canvas.min + self.offset + Vec2::new(20.0, 0.0);
+        let pointer = ui.input(|i| i.pointer.hover_pos());
+        let released = ui.input(|i| i.pointer.any_released());
+        let text_color = ui.visuals().text_color();
+        let highlight = ui.visuals().selection.stroke.color;
+
+        let positions = Self::positions(pipeline);
+        let rect_of = |uuid: &Uuid| Rect::from_min_size(origin + positions[uuid].to_vec2(), node_size(&pipeline.R_E_AList[uuid]));
+        let port_pos = |uuid: &Uuid, name: &str, output: bool| -> Option<Pos2> {
+            let (inputs, outputs) = ports(&pipeline.R_E_AList[uuid]);
+            let list = if output { outputs } else { inputs };
+            let k = list.iter().position(|(n, _)| n == name)?;
+            let rect = rect_of(uuid);
+            Some(Pos2::new(if output { rect.right() } else { rect.left() }, rect.top() + NODE_HEADER + (k as f32 + 0.5) * PORT_SPACING))
+        };
+        let mut actions: Vec<EditAction> = Vec::new();
+        let mut pipe_clicked = false;
+        let clicked = canvas_response.clicked();
+
+        // baseline: ET pipes between Apps and to Renders
+        for (i, (app, renders)) in pipeline.baseline.iter().enumerate() {
+            let from = rect_of(app);
+            let mut segments = Vec::new();
+            if let Some((next, _)) = pipeline.baseline.get(i + 1) {
+                segments.push((from.right_center(), rect_of(next).left_center()));
+            }
+            for render in renders {
+                let to = rect_of(render);
+                segments.push((from.center_bottom(), to.center_top()));
+            }
+            let is_selected = *selected == Some(Selection::BaselineEdge(i));
+            let stroke = Stroke::new(if is_selected { 5.0 } else { 3.0 }, if is_selected { highlight } else { Color32::GRAY });
+            for (a, b) in segments {
+                painter.arrow(a, b - a, stroke);
+                if clicked && pointer.is_some_and(|p| distance_to_segment(a, b, p) < PIPE_HIT_DISTANCE) {
+                    actions.push(EditAction::Select(Some(Selection::BaselineEdge(i))));
+                    pipe_clicked = true;
+                }
+            }
+        }
+
+        // connections
+        for (i, (from, output, to, input)) in pipeline.connections.iter().enumerate() {
+            let (Some(a), Some(b)) = (port_pos(from, output, true), port_pos(to, input, false)) else { continue };
+            let points = pipe_points(a, b);
+            let is_selected = *selected == Some(Selection::Connection(i));
+            let stroke = Stroke::new(if is_selected { 4.0 } else { 2.0 }, if is_selected { highlight } else { Color32::LIGHT_GRAY });
+            painter.add(CubicBezierShape::from_points_stroke(points, false, Color32::TRANSPARENT, stroke));
+            if clicked && !pipe_clicked && pointer.is_some_and(|p| distance_to_curve(points, p) < PIPE_HIT_DISTANCE) {
+                actions.push(EditAction::Select(Some(Selection::Connection(i))));
+                pipe_clicked = true;
+            }
+        }
+        if clicked && !pipe_clicked {
+            actions.push(EditAction::Select(None));
+        }
+
+        // nodes
+        let mut uuids: Vec<&Uuid> = pipeline.R_E_AList.keys().collect();
+        uuids.sort_by_key(|uuid| pipeline.names.get(uuid).map(|n| n.0.as_str()));
+        for uuid in uuids {
+            let element = &pipeline.R_E_AList[uuid];
+            let (alias, name) = pipeline.names.get(uuid).cloned().unwrap_or_default();
+            let rect = rect_of(uuid);
+            let is_selected = *selected == Some(Selection::Element(*uuid));
+            let spine_index = pipeline.baseline.iter().position(|(app, _)| app == uuid);
+
+            let response = ui.interact(rect, ui.id().with(("pipe_node", uuid)), Sense::click_and_drag());
+            painter.rect(rect, 4.0, ui.visuals().extreme_bg_color, Stroke::new(if is_selected { 2.5 } else { 1.0 }, if is_selected { highlight } else { Color32::GRAY }));
+            let header = Rect::from_min_size(rect.min, Vec2::new(rect.width(), NODE_HEADER));
+            painter.rect_filled(header.shrink(1.0), 3.0, class_color(element.get_class()));
+            painter.text(header.left_top() + Vec2::new(6.0, 3.0), Align2::LEFT_TOP, &alias, FontId::proportional(14.0), Color32::WHITE);
+            painter.text(header.left_top() + Vec2::new(6.0, 19.0), Align2::LEFT_TOP, &name, FontId::proportional(10.0), Color32::LIGHT_GRAY);
+
+            if response.clicked() {
+                actions.push(EditAction::Select(Some(Selection::Element(*uuid))));
+            }
+            // only Extensions are placed freely, baseline and Renders follow spine
+            if response.dragged() && self.dragging.is_none() && matches!(element.get_class(), R_E_AClass::Extension) {
+                actions.push(EditAction::Move(*uuid, response.drag_delta()));
+            }
+            response.context_menu(|ui| {
+                if let Some(i) = spine_index {
+                    if ui.add_enabled(i > 0, egui::Button::new("Move left")).clicked() {
+                        actions.push(EditAction::Reorder(i, false));
+                        ui.close_menu();
+                    }
+                    if ui.add_enabled(i + 1 < pipeline.baseline.len(), egui::Button::new("Move right")).clicked() {
+                        actions.push(EditAction::Reorder(i, true));
+                        ui.close_menu();
+                    }
+                }
+                if ui.button("Remove").clicked() {
+                    actions.push(EditAction::Remove(*uuid));
+                    ui.close_menu();
+                }
+            });
+
+            // ports: inputs on the left, outputs on the right
+            let (inputs, outputs) = ports(element);
+            for (input, type_id) in &inputs {
+                let Some(pos) = port_pos(uuid, input, false) else { continue };
+                let connected = pipeline.connections.iter().any(|c| c.2 == *uuid && c.3 == *input);
+                let mut color = if connected { Color32::LIGHT_GRAY } else { Color32::DARK_GRAY };
+                // live type check of dragged connection
+                if let Some((from, output)) = &self.dragging {
+                    let check = pipeline.check_connection(*from, output, *uuid, input);
+                    color = if check.is_ok() { Color32::GREEN } else { Color32::RED };
+                    let hovered = pointer.is_some_and(|p| p.distance(pos) < PORT_RADIUS * 2.5);
+                    if hovered && released {
+                        actions.push(EditAction::Connect(*from, output.clone(), *uuid, input.clone()));
+                    } else if hovered && let Err(e) = check {
+                        painter.text(pos - Vec2::new(8.0, 0.0), Align2::RIGHT_CENTER, e, FontId::proportional(11.0), Color32::RED);
+                    }
+                }
+                painter.circle_filled(pos, PORT_RADIUS, color);
+                painter.text(pos + Vec2::new(PORT_RADIUS + 3.0, 0.0), Align2::LEFT_CENTER, input, FontId::proportional(11.0), text_color);
+                let port_rect = Rect::from_center_size(pos, Vec2::splat(PORT_RADIUS * 2.5));
+                ui.interact(port_rect, ui.id().with(("pipe_input", uuid, input)), Sense::hover())
+                    .on_hover_text(format!("{}: {}", input, registry.type_name(*type_id)));
+            }
+            for (output, type_id) in &outputs {
+                let Some(pos) = port_pos(uuid, output, true) else { continue };
+                painter.circle_filled(pos, PORT_RADIUS, Color32::LIGHT_GRAY);
+                painter.text(pos - Vec2::new(PORT_RADIUS + 3.0, 0.0), Align2::RIGHT_CENTER, output, FontId::proportional(11.0), text_color);
+                let port_rect = Rect::from_center_size(pos, Vec2::splat(PORT_RADIUS * 2.5));
+                let port = ui.interact(port_rect, ui.id().with(("pipe_output", uuid, output)), Sense::drag())
+                    .on_hover_text(format!("{}: {}", output, registry.type_name(*type_id)));
+                if port.drag_started() {
+                    actions.push(EditAction::StartDrag(*uuid, output.clone()));
+                }
+            }
+        }
+
+        // connection being dragged
+        if let (Some((from, output)), Some(p)) = (&self.dragging, pointer)
+        && let Some(a) = port_pos(from, output, true) {
+            painter.add(CubicBezierShape::from_points_stroke(pipe_points(a, p), false, Color32::TRANSPARENT, Stroke::new(2.0, highlight)));
+        }
+        if released {
+            self.dragging = None;
+        }
+        if let Some(Selection::Connection(i)) = *selected
+        && ui.input(|input| input.key_pressed(egui::Key::Delete)) {
+            actions.push(EditAction::Disconnect(i));
+        }
+
+        for action in actions {
+            match action {
+                EditAction::Select(selection) => *selected = selection,
+                EditAction::Move(uuid, delta) => {
+                    let position = pipeline.layout.entry(uuid).or_default();
+                    position.0 += delta.x;
+                    position.1 += delta.y;
+                }
+                EditAction::Remove(uuid) => {
+                    pipeline.remove_element(uuid);
+                    *selected = None;
+                }
+                EditAction::Reorder(i, right) => {
+                    let j = if right { i + 1 } else { i - 1 };
+                    pipeline.baseline.swap(i, j);
+                    *selected = None;
+                }
+                EditAction::Disconnect(i) => {
+                    pipeline.connections.remove(i);
+                    *selected = None;
+                }
+                EditAction::StartDrag(uuid, output) => self.dragging = Some((uuid, output)),
+                EditAction::Connect(from, output, to, input) => {
+                    self.status = pipeline.connect(from, &output, to, &input).err();
+                }
+            }
+        }
+    }
+}
+
+
+
+// ==========INPUTS VIEW==========
+
+/// Editor of value of type T, returns true if value was changed
+pub type Editor<T> = fn(&mut egui::Ui, &mut T)->bool;
+
+/// Editor working with value of any type, see InputEditors::register()
+type ErasedEditor = Box<dyn Fn(&mut egui::Ui, &mut InputValue)->bool>;
+
+/// TypeId → editor of inputs that are set directly (see Pipeline.values).
+/// Types without editor that are registered with Registry::register_value() are edited as JSON
+pub struct InputEditors {
+    editors: HashMap<TypeId, ErasedEditor>,
+    defaults: HashMap<TypeId, fn()->InputValue>,
+}
+
+impl InputEditors {
+    /// With editors of basic types
+    pub fn new()->Self {
+        let mut editors = Self { editors: HashMap::new(), defaults: HashMap::new() };
+        editors.register::<String>(|ui, value| ui.text_edit_singleline(value).changed());
+        editors.register::<bool>(|ui, value| ui.checkbox(value, "").changed());
+        editors.register::<i64>(|ui, value| ui.add(egui::DragValue::new(value)).changed());
+        editors.register::<u32>(|ui, value| ui.add(egui::DragValue::new(value)).changed());
+        editors.register::<f64>(|ui, value| ui.add(egui::DragValue::new(value).speed(0.1)).changed());
+        editors.register::<NaiveDate>(date_editor);
+        editors.register::<DateTimeType>(datetime_editor);
+        editors.register::<Tz>(tz_editor);
+        editors.register::<EventZone>(zone_editor);
+        editors.register::<Vec<String>>(|ui, value| {
+            let mut text = value.join("\n");
+            let changed = ui.text_edit_multiline(&mut text).on_hover_text("One value per line").changed();
+            if changed {
+                *value = if text.is_empty() { Vec::new() } else { text.split('\n').map(String::from).collect() };
+            }
+            changed
+        });
+        editors.register::<Vec<Uuid>>(|ui, value| {
+            parsed_text_edit(ui, || value.iter().map(Uuid::to_string).collect::<Vec<_>>().join("\n"), |text| {
+                text.lines()
+                    .filter(|line| !line.trim().is_empty())
+                    .map(|line| Uuid::parse_str(line.trim()).map_err(|_| format!("Wrong UUID {}", line.trim())))
+                    .collect()
+            }, true).map(|uuids| *value = uuids).is_some()
+        });
+        editors.register::<Vec<EventSpec>>(events_editor);
+        editors
+    }
+
+    /// Editor for values of type T, new values are T::default()
+    pub fn register<T: Default+Serialize+Send+Sync+'static>(&mut self, editor: Editor<T>) {
+        self.editors.insert(TypeId::of::<T>(), Box::new(move |ui, value| {
+            value.value.downcast_mut::<T>().is_some_and(|value| editor(ui, value))
+        }));
+        self.defaults.insert(TypeId::of::<T>(), || InputValue::new(T::default()));
+    }
+
+    /// Editor of input slot (None = not set), returns true if slot was changed
+    fn edit(&self, ui: &mut egui::Ui, registry: &Registry, type_id: TypeId, slot: &mut Option<InputValue>)->bool {
+        if let (Some(editor), Some(default)) = (self.editors.get(&type_id), self.defaults.get(&type_id)) {
+            return match slot {
+                Some(value) => editor(ui, value),
+                None if ui.button("Set").clicked() => {
+                    *slot = Some(default());
+                    true
+                }
+                None => false,
+            };
+        }
+        if registry.has_value(type_id) {
+            let current = || slot.as_ref()
+                .and_then(|value| value.to_json().ok())
+                .and_then(|json| serde_json::to_string_pretty(&json).ok())
+                .unwrap_or_default();
+            let parsed = parsed_text_edit(ui, current, |text| {
+                let json = serde_json::from_str(text).map_err(|e| e.to_string())?;
+                registry.parse_value(type_id, json)
+            }, true);
+            return parsed.map(|value| *slot = Some(value)).is_some();
+        }
+        ui.weak("Can not be set directly, connect Extension");
+        false
+    }
+}
+
+/// Text field that keeps text being typed (in egui memory) and returns parsed value when it is valid.
+/// Text is reset to current value when field loses focus
+fn parsed_text_edit<T>(ui: &mut egui::Ui,
+    current: impl FnOnce()->String,
+    parse: impl Fn(&str)->Result<T, String>,
+    multiline: bool
+)->Option<T> {
+    let id = ui.make_persistent_id("parsed_text");
+    let mut text = ui.data_mut(|d| d.get_temp::<String>(id)).unwrap_or_else(current);
+    let response = if multiline {
+        ui.add(egui::TextEdit::multiline(&mut text).code_editor().desired_rows(3))
+    } else {
+        ui.text_edit_singleline(&mut text)
+    };
+    let parsed = parse(&text);
+    if let (Err(e), false) = (&parsed, text.is_empty()) {
+        ui.colored_label(Color32::RED, e);
+    }
+    if response.has_focus() {
+        ui.data_mut(|d| d.insert_temp(id, text));
+    } else {
+        ui.data_mut(|d| d.remove::<String>(id));
+    }
+    parsed.ok().filter(|_| response.changed())
+}
+
+fn date_editor(ui: &mut egui::Ui, value: &mut NaiveDate)->bool {
+    parsed_text_edit(ui, || value.to_string(), |text| {
+        NaiveDate::parse_from_str(text.trim(), "%Y-%m-%d").map_err(|_| "Expected YYYY-MM-DD".to_string())
+    }, false).map(|date| *value = date).is_some()
+}
+
+fn datetime_editor(ui: &mut egui::Ui, value: &mut DateTimeType)->bool {
+    let changed = parsed_text_edit(ui, || value.format("%Y-%m-%d %H:%M").to_string(), |text| {
+        NaiveDateTime::parse_from_str(text.trim(), "%Y-%m-%d %H:%M").map(|t| t.and_utc()).map_err(|_| "Expected YYYY-MM-DD HH:MM".to_string())
+    }, false).map(|time| *value = time).is_some();
+    ui.weak("UTC");
+    changed
+}
+
+fn zone_editor(ui: &mut egui::Ui, value: &mut EventZone)->bool {
+    let mut changed = false;
+    egui::ComboBox::from_id_source("zone_kind")
+        .selected_text(match value {
+            EventZone::Utc => "UTC",
+            EventZone::Tz(_) => "Time zone",
+            EventZone::Floating => "Floating",
+        })
+        .show_ui(ui, |ui| {
+            changed |= ui.selectable_value(value, EventZone::Utc, "UTC").changed();
+            let is_tz = matches!(value, EventZone::Tz(_));
+            if ui.selectable_label(is_tz, "Time zone").clicked() && !is_tz {
+                *value = EventZone::Tz(Tz::UTC);
+                changed = true;
+            }
+            changed |= ui.selectable_value(value, EventZone::Floating, "Floating").changed();
+        });
+    if let EventZone::Tz(tz) = value {
+        changed |= tz_editor(ui, tz);
+    }
+    changed
+}
+
+/// Events entered by hand (e.g. input of std::adder::ManualEvents). New Events get UUID at once, so it stays same between runs
+fn events_editor(ui: &mut egui::Ui, value: &mut Vec<EventSpec>)->bool {
+    let mut changed = false;
+    let mut removed = None;
+    for (i, spec) in value.iter_mut().enumerate() {
+        ui.push_id(i, |ui| {
+            egui::Frame::group(ui.style()).show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    changed |= ui.text_edit_singleline(&mut spec.name).changed();
+                    if ui.small_button("x").on_hover_text("Remove Event").clicked() {
+                        removed = Some(i);
+                    }
+                });
+                let mut all_day = spec.all_day.is_some();
+                if ui.checkbox(&mut all_day, "All day").changed() {
+                    spec.all_day = all_day.then(|| {
+                        let first = spec.start.date_naive();
+                        (first, spec.end.date_naive().max(first + Duration::days(1)))
+                    });
+                    changed = true;
+                }
+                egui::Grid::new("event").num_columns(2).show(ui, |ui| {
+                    match &mut spec.all_day {
+                        Some((first, after_last)) => {
+                            ui.label("First day");
+                            ui.horizontal(|ui| ui.push_id("first", |ui| changed |= date_editor(ui, first)));
+                            ui.end_row();
+                            ui.label("Day after last");
+                            ui.horizontal(|ui| ui.push_id("after_last", |ui| changed |= date_editor(ui, after_last)));
+                            ui.end_row();
+                        }
+                        None => {
+                            ui.label("Start");
+                            ui.horizontal(|ui| ui.push_id("start", |ui| changed |= datetime_editor(ui, &mut spec.start)));
+                            ui.end_row();
+                            ui.label("End");
+                            ui.horizontal(|ui| ui.push_id("end", |ui| changed |= datetime_editor(ui, &mut spec.end)));
+                            ui.end_row();
+                        }
+                    }
+                    ui.label("Zone");
+                    ui.horizontal(|ui| changed |= zone_editor(ui, &mut spec.zone));
+                    ui.end_row();
+                });
+                ui.push_id("fields", |ui| changed |= fields_editor(ui, &mut spec.fields));
+            });
+        });
+    }
+    if let Some(i) = removed {
+        value.remove(i);
+        changed = true;
+    }
+    if ui.button("Add Event").clicked() {
+        let start = Utc::now().duration_trunc(Duration::hours(1)).unwrap_or_else(|_| Utc::now()) + Duration::hours(1);
+        let mut spec = EventSpec::new("New Event".to_string(), start, start + Duration::hours(1));
+        spec.uuid = Some(Uuid::new_v4());
+        value.push(spec);
+        changed = true;
+    }
+    changed
+}
+
+/// Custom fields of Event, key → value
+fn fields_editor(ui: &mut egui::Ui, fields: &mut BTreeMap<String, String>)->bool {
+    let mut changed = false;
+    let mut removed = None;
+    for (key, value) in fields.iter_mut() {
+        ui.horizontal(|ui| {
+            ui.label(key);
+            changed |= ui.text_edit_singleline(value).changed();
+            if ui.small_button("x").on_hover_text("Remove field").clicked() {
+                removed = Some(key.clone());
+            }
+        });
+    }
+    if let Some(key) = removed {
+        fields.remove(&key);
+        changed = true;
+    }
+    ui.horizontal(|ui| {
+        let id = ui.make_persistent_id("new_field");
+        let mut key = ui.data_mut(|d| d.get_temp::<String>(id)).unwrap_or_default();
+        ui.add(egui::TextEdit::singleline(&mut key).hint_text("New field").desired_width(100.0));
+        if ui.small_button("+").clicked() && !key.trim().is_empty() {
+            fields.entry(key.trim().to_string()).or_default();
+            key.clear();
+            changed = true;
+        }
+        ui.data_mut(|d| d.insert_temp(id, key));
+    });
+    changed
+}
+
+fn tz_editor(ui: &mut egui::Ui, value: &mut Tz)->bool {
+    let mut changed = false;
+    egui::ComboBox::from_id_source("tz")
+        .selected_text(value.name())
+        .show_ui(ui, |ui| {
+            for tz in chrono_tz::TZ_VARIANTS {
+                changed |= ui.selectable_value(value, tz, tz.name()).changed();
+            }
+        });
+    changed
+}
+
+/// Panel, shows input of the selected R/E/A. Inputs without connection can be set here
 struct InputsView {}
 
-impl eframe::App for GUIrea {
-    fn update(&mut self, ctx: &egui::Context, frame: &mut eframe::Frame) {
-        egui::TopBottomPanel::top("")
-            .resizable(true)
-            .show(ctx, |ui| {});
+impl InputsView {
+    fn ui(&mut self, ui: &mut egui::Ui, pipeline: &mut Pipeline, registry: &Registry, editors: &InputEditors, selected: Option<Selection>) {
+        ui.heading("Inputs");
+        let Some(Selection::Element(uuid)) = selected else {
+            ui.weak("Select R/E/A to see its inputs");
+            return;
+        };
+        let Some(element) = pipeline.R_E_AList.get(&uuid) else { return };
+        let (alias, name) = pipeline.names.get(&uuid).cloned().unwrap_or_default();
+        ui.label(format!("{} ({})", alias, name));
+        ui.separator();
+
+        let (inputs, _) = ports(element);
+        if inputs.is_empty() {
+            ui.weak("No inputs");
+        }
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            for (input, type_id) in inputs {
+                ui.push_id(&input, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.strong(&input);
+                        ui.weak(registry.type_name(type_id));
+                    });
+                    let source = pipeline.connections.iter().find(|c| c.2 == uuid && c.3 == input);
+                    if let Some((from, output, _, _)) = source {
+                        let from = pipeline.names.get(from).map(|n| n.0.as_str()).unwrap_or_default();
+                        ui.label(format!("← {}.{}", from, output));
+                        return;
+                    }
+
+                    let key = (uuid, input.clone());
+                    let mut slot = pipeline.values.remove(&key);
+                    editors.edit(ui, registry, type_id, &mut slot);
+                    if slot.is_some() && ui.small_button("Clear").clicked() {
+                        slot = None;
+                    }
+                    if slot.is_none() {
+                        ui.colored_label(Color32::YELLOW, "Not set");
+                    }
+                    if let Some(value) = slot {
+                        pipeline.values.insert(key, value);
+                    }
+                });
+                ui.separator();
+            }
+        });
+    }
+}
+
+
+
+// ==========DATA VIEW==========
+
+/// Panel, shows which info is transmitted in selected pipe during last run of pipeline
+#[derive(Default)]
+struct DataView {
+    /// Result of last run
+    status: Option<String>,
+    /// ET pipes are shown in calendar instead of list of Events
+    as_calendar: bool,
+    /// Same view is reused for all ET pipes, so mode and shown date stay when other pipe is selected
+    calendar: Option<CalendarView>,
+    /// Index in et_history of ET shown in calendar, None if it is outdated (e.g. after run)
+    calendar_source: Option<usize>,
+}
+
+impl DataView {
+    fn ui(&mut self, ui: &mut egui::Ui, pipeline: &mut Pipeline, registry: &Registry, selected: Option<Selection>) {
+        ui.horizontal(|ui| {
+            ui.heading("Data");
+            if ui.button("Run").clicked() {
+                self.status = Some(match pipeline.run_full() {
+                    Ok(()) => format!("Done, {} warning(s)", pipeline.warnings.len()),
+                    Err(e) => e,
+                });
+                self.calendar_source = None;
+            }
+            ui.checkbox(&mut self.as_calendar, "Calendar");
+            if let Some(status) = &self.status {
+                ui.label(status);
+            }
+        });
+        for warning in &pipeline.warnings {
+            ui.colored_label(Color32::YELLOW, warning);
+        }
+        ui.separator();
+
+        egui::ScrollArea::vertical().auto_shrink([false; 2]).show(ui, |ui| match selected {
+            None => {
+                ui.weak("Select pipe to see its data");
+            }
+            Some(Selection::BaselineEdge(i)) => match pipeline.et_history.get(i) {
+                Some(et) if self.as_calendar => {
+                    let view = self.calendar.get_or_insert_with(|| CalendarView::new(EventTable::new(), Tz::UTC));
+                    if self.calendar_source != Some(i) {
+                        view.set_et(et.clone());
+                        self.calendar_source = Some(i);
+                    }
+                    view.ui(ui);
+                }
+                Some(et) => et_ui(ui, et),
+                None => {
+                    ui.weak("Not executed yet, run pipeline");
+                }
+            },
+            Some(Selection::Connection(i)) => {
+                let Some((from, output, _, _)) = pipeline.connections.get(i) else { return };
+                output_ui(ui, pipeline, registry, *from, output);
+            }
+            Some(Selection::Element(uuid)) => {
+                let Some(element) = pipeline.R_E_AList.get(&uuid) else { return };
+                let (_, outputs) = ports(element);
+                if outputs.is_empty() {
+                    ui.weak("Select pipe to see its data");
+                }
+                for (output, _) in outputs {
+                    ui.strong(&output);
+                    output_ui(ui, pipeline, registry, uuid, &output);
+                }
+            }
+        });
+    }
+}
+
+/// Events of ET with times (all-day Events as dates, UTC/zoned times in UTC, floating as wall-clock time) and streams of hidden Events
+fn et_ui(ui: &mut egui::Ui, et: &EventTable) {
+    let mut events: Vec<&Event> = et.get_events().collect();
+    events.sort_by_key(|event| event.start);
+    ui.label(format!("{} Event(s)", events.len()));
+    egui::Grid::new("data_events").striped(true).show(ui, |ui| {
+        for event in events {
+            let (start, end) = (event.start_in(Tz::UTC), event.end_in(Tz::UTC));
+            ui.label(event.get_name());
+            match event.all_day {
+                Some(_) => ui.label(format!("{} – {}", start.date(), (end - Duration::days(1)).date())),
+                None => ui.label(format!("{} – {}", start.format("%Y-%m-%d %H:%M"), end.format("%Y-%m-%d %H:%M"))),
+            };
+            match event.zone {
+                EventZone::Utc => ui.weak("UTC"),
+                EventZone::Tz(tz) => ui.weak(format!("UTC ({})", tz.name())),
+                EventZone::Floating => ui.weak("floating"),
+            };
+            ui.weak(if event.recurrence.is_some() { "recurring" } else { "" });
+            match stream_of(event) {
+                Some(stream) => ui.weak(format!("hidden → {}", stream)),
+                None => ui.weak(""),
+            };
+            ui.end_row();
+        }
+    });
+}
+
+/// Output of Extension, pretty-printed if its type is registered with Registry::register_debug
+fn output_ui(ui: &mut egui::Ui, pipeline: &Pipeline, registry: &Registry, element: Uuid, output: &str) {
+    let Some(value) = pipeline.output(&element, output) else {
+        ui.weak("Not executed yet, run pipeline");
+        return;
+    };
+    match registry.format_value(value) {
+        Some(text) => {
+            ui.monospace(text);
+        }
+        None => {
+            ui.weak(format!("Value of type {} can not be shown", registry.type_name(value.type_id())));
+        }
     }
 }
@@ -5,12 +5,14 @@ use eframe::{App, egui};
 use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, TimeZone, Utc};
 use rusqlite::{Connection, Result as SqlResult};
 
-use std::any::TypeId;
+use ::std::any::TypeId; // `::` since crate has its own std module
 
 use crate::core::EventTable;
 
 mod core;
 mod gui;
+mod recurrence;
+mod std;
 
 // Definitions
 
@@ -38,19 +40,3 @@ impl REAAppBackend {
         // for app in APPS_PIPELINE[starting_index.unwrap_or(0)..]{}
     }
 }
-
-
-
-
-// struct PomodoroApp(){
-
-// }
-// impl App for PomodoroApp{
-//     fn get_name(){"Pomodoro"}
-//     fn get_inputs()->{
-//         EventTable,
-//         Vec<core::UUIDType> //Events to apply pomodoro (TODO:checking if piped by E events exist in ET recieved by A) //????????????????????
-//     };
-//     fn process(&self,app_input:InputList)->Option<EventTable>;
-
-// }
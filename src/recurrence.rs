@@ -0,0 +1,315 @@
+//Responsible for recurring Events: RRULE-like (RFC 5545) rules and their expansion into separate occurrences.
+// Supported subset: FREQ (DAILY/WEEKLY/MONTHLY/YEARLY), INTERVAL, BYDAY (with ordinals for MONTHLY/YEARLY), COUNT, UNTIL, EXDATE
+
+use chrono::{Datelike, Days, Duration, Months, NaiveDate, NaiveDateTime, Weekday};
+use serde::{Deserialize, Serialize};
+
+use crate::core::{DateTimeType, EventZone};
+
+/// Protection from endless expansion (for example BYDAY that never matches)
+const MAX_EMPTY_PERIODS: u32 = 10_000;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+/// Single BYDAY entry: weekday + optional ordinal (1 = first in month/year, -1 = last, ...).
+/// Ordinal is used only with Monthly/Yearly frequency
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ByDay {
+    pub ordinal: Option<i8>,
+    pub weekday: Weekday,
+}
+
+///Responsible for description of Event repetition. First occurrence is always the Event itself (as DTSTART in RFC 5545)
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Recurrence {
+    pub frequency: Frequency,
+    /// Every n-th day/week/month/year, MUST be >0
+    pub interval: u32,
+    /// Empty → weekday/day of the first occurrence is used
+    pub by_day: Vec<ByDay>,
+    /// Max amount of occurrences (including the first one and excluded ones)
+    pub count: Option<u32>,
+    /// Last possible start of occurrence (inclusive)
+    pub until: Option<DateTimeType>,
+    /// Starts of occurrences that are excluded
+    pub exdates: Vec<DateTimeType>,
+}
+
+impl Recurrence {
+    pub fn new(frequency: Frequency)->Self {
+        Self {
+            frequency,
+            interval: 1,
+            by_day: Vec::new(),
+            count: None,
+            until: None,
+            exdates: Vec::new(),
+        }
+    }
+
+    pub fn check(&self
+    )->Result<(), String> {
+        if self.interval == 0 {
+            return Err("Recurrence interval must be >0".into());
+        }
+        if self.count == Some(0) {
+            return Err("Recurrence count must be >0".into());
+        }
+        for by_day in &self.by_day {
+            if by_day.ordinal.is_some() && matches!(self.frequency, Frequency::Daily | Frequency::Weekly) {
+                return Err(format!("BYDAY ordinal {:?} is allowed only for monthly and yearly recurrence", by_day));
+            }
+            if matches!(by_day.ordinal, Some(o) if o == 0 || !(-53..=53).contains(&o)) {
+                return Err(format!("Wrong BYDAY ordinal {:?}", by_day));
+            }
+        }
+        Ok(())
+    }
+
+    /// Starts of all occurrences of Event [first_start, first_start+duration) that overlap [window_start, window_end), ordered.
+    /// First occurrence (Event itself) is included too. Occurrences keep wall-clock time of first_start in zone
+    pub fn occurrences(&self,
+        first_start: DateTimeType,
+        duration: Duration,
+        zone: EventZone,
+        window_start: DateTimeType,
+        window_end: DateTimeType
+    )->Vec<DateTimeType> {
+        let mut found = Vec::new();
+        if first_start >= window_end {
+            return found;
+        }
+        if first_start + duration > window_start && !self.exdates.contains(&first_start) {
+            found.push(first_start);
+        }
+        let mut generated = 1;
+        let mut empty_periods = 0;
+
+        let local_first_start = zone.to_local(first_start);
+        for period in 0.. {
+            let Some(local_candidates) = self.period_starts(local_first_start, period) else { break };
+            let candidates: Vec<DateTimeType> = local_candidates.into_iter().map(|t| zone.resolve_local(t)).collect();
+            if candidates.is_empty() {
+                empty_periods += 1;
+                if empty_periods > MAX_EMPTY_PERIODS {
+                    break;
+                }
+                continue;
+            }
+            empty_periods = 0;
+
+            for start in candidates {
+                if start <= first_start {
+                    continue;
+                }
+                if self.count.is_some_and(|count| generated >= count)
+                || self.until.is_some_and(|until| start > until)
+                || start >= window_end {
+                    return found;
+                }
+                generated += 1;
+
+                if start + duration > window_start && !self.exdates.contains(&start) {
+                    found.push(start);
+                }
+            }
+        }
+        found
+    }
+
+    /// Wall-clock candidate starts in n-th period (day/week/month/year after the first occurrence), ordered.
+    /// None if period is out of supported time range
+    fn period_starts(&self,
+        first_start: NaiveDateTime,
+        period: u32
+    )->Option<Vec<NaiveDateTime>> {
+        let first_day = first_start.date();
+        let time = first_start.time();
+        let step = period.checked_mul(self.interval)?;
+
+        let days: Vec<NaiveDate> = match self.frequency {
+            Frequency::Daily => {
+                let day = first_day.checked_add_days(Days::new(step.into()))?;
+                if self.by_day.is_empty() || self.by_day.iter().any(|b| b.weekday == day.weekday()) {
+                    vec![day]
+                } else {
+                    vec![]
+                }
+            }
+            Frequency::Weekly => {
+                let monday = first_day.checked_sub_days(Days::new(first_day.weekday().num_days_from_monday().into()))?
+                    .checked_add_days(Days::new(u64::from(step) * 7))?;
+                let mut weekdays: Vec<Weekday> = if self.by_day.is_empty() {
+                    vec![first_day.weekday()]
+                } else {
+                    self.by_day.iter().map(|b| b.weekday).collect()
+                };
+                weekdays.sort_by_key(|w| w.num_days_from_monday());
+                weekdays.dedup();
+                weekdays.into_iter()
+                    .filter_map(|w| monday.checked_add_days(Days::new(w.num_days_from_monday().into())))
+                    .collect()
+            }
+            Frequency::Monthly => {
+                let month_start = first_day.with_day(1)?.checked_add_months(Months::new(step))?;
+                if self.by_day.is_empty() {
+                    // months without this day (e.g. 31st) are skipped, as in RFC 5545
+                    month_start.with_day(first_day.day()).into_iter().collect()
+                } else {
+                    let next_month = month_start.checked_add_months(Months::new(1))?;
+                    days_matching(month_start, next_month, &self.by_day)
+                }
+            }
+            Frequency::Yearly => {
+                let year = first_day.year().checked_add(i32::try_from(step).ok()?)?;
+                if self.by_day.is_empty() {
+                    // 29th of February is skipped in non-leap years
+                    NaiveDate::from_ymd_opt(year, first_day.month(), first_day.day()).into_iter().collect()
+                } else {
+                    days_matching(NaiveDate::from_ymd_opt(year, 1, 1)?, NaiveDate::from_ymd_opt(year + 1, 1, 1)?, &self.by_day)
+                }
+            }
+        };
+
+        Some(days.into_iter().map(|day| day.and_time(time)).collect())
+    }
+}
+
+/// Days in [from, to) that match any of by_day, ordered. Ordinals count inside [from, to)
+fn days_matching(from: NaiveDate, to: NaiveDate, by_day: &[ByDay])->Vec<NaiveDate> {
+    let days: Vec<NaiveDate> = from.iter_days().take_while(|d| *d < to).collect();
+    let mut found: Vec<NaiveDate> = Vec::new();
+    for rule in by_day {
+        let same_weekday: Vec<NaiveDate> = days.iter().copied().filter(|d| d.weekday() == rule.weekday).collect();
+        match rule.ordinal {
+            None => found.extend(same_weekday),
+            Some(o) if o > 0 => found.extend(same_weekday.get(o as usize - 1)),
+            Some(o) => found.extend(same_weekday.len().checked_sub(o.unsigned_abs() as usize).map(|i| same_weekday[i])),
+        }
+    }
+    found.sort();
+    found.dedup();
+    found
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+    use chrono_tz::Europe::Berlin;
+
+    fn at(y: i32, mo: u32, d: u32, h: u32)->DateTimeType {
+        Utc.with_ymd_and_hms(y, mo, d, h, 0, 0).unwrap()
+    }
+
+    fn by(ordinal: Option<i8>, weekday: Weekday)->ByDay {
+        ByDay { ordinal, weekday }
+    }
+
+    /// Starts of all occurrences in 2026-2027 of 1-hour Event starting at first
+    fn starts(recurrence: &Recurrence, first: DateTimeType)->Vec<DateTimeType> {
+        recurrence.occurrences(first, Duration::hours(1), EventZone::Utc, at(2026, 1, 1, 0), at(2028, 1, 1, 0))
+    }
+
+    #[test]
+    fn weekly_by_day_with_count() {
+        let mut r = Recurrence::new(Frequency::Weekly);
+        r.by_day = vec![by(None, Weekday::Fri), by(None, Weekday::Mon), by(None, Weekday::Wed)];
+        r.count = Some(5);
+        // 2026-05-06 is Wednesday
+        assert_eq!(starts(&r, at(2026, 5, 6, 9)), vec![at(2026, 5, 6, 9), at(2026, 5, 8, 9), at(2026, 5, 11, 9), at(2026, 5, 13, 9), at(2026, 5, 15, 9)]);
+
+        r.interval = 2;
+        r.count = Some(3);
+        assert_eq!(starts(&r, at(2026, 5, 6, 9)), vec![at(2026, 5, 6, 9), at(2026, 5, 8, 9), at(2026, 5, 18, 9)]);
+    }
+
+    #[test]
+    fn until_is_inclusive_and_exdates_count() {
+        let mut r = Recurrence::new(Frequency::Daily);
+        r.until = Some(at(2026, 5, 7, 9));
+        assert_eq!(starts(&r, at(2026, 5, 4, 9)), vec![at(2026, 5, 4, 9), at(2026, 5, 5, 9), at(2026, 5, 6, 9), at(2026, 5, 7, 9)]);
+
+        // excluded occurrences are still counted by COUNT
+        let mut r = Recurrence::new(Frequency::Daily);
+        r.count = Some(3);
+        r.exdates = vec![at(2026, 5, 4, 9), at(2026, 5, 5, 9)];
+        assert_eq!(starts(&r, at(2026, 5, 4, 9)), vec![at(2026, 5, 6, 9)]);
+    }
+
+    #[test]
+    fn monthly_and_yearly_rules() {
+        // last Friday of month
+        let mut r = Recurrence::new(Frequency::Monthly);
+        r.by_day = vec![by(Some(-1), Weekday::Fri)];
+        r.count = Some(3);
+        assert_eq!(starts(&r, at(2026, 5, 29, 9)), vec![at(2026, 5, 29, 9), at(2026, 6, 26, 9), at(2026, 7, 31, 9)]);
+
+        // months without 31st are skipped
+        let mut r = Recurrence::new(Frequency::Monthly);
+        r.count = Some(3);
+        assert_eq!(starts(&r, at(2026, 5, 31, 9)), vec![at(2026, 5, 31, 9), at(2026, 7, 31, 9), at(2026, 8, 31, 9)]);
+
+        // 29th of February only in leap years, first Monday of year
+        let r = Recurrence::new(Frequency::Yearly);
+        let leap = r.occurrences(at(2024, 2, 29, 9), Duration::hours(1), EventZone::Utc, at(2024, 1, 1, 0), at(2033, 1, 1, 0));
+        assert_eq!(leap, vec![at(2024, 2, 29, 9), at(2028, 2, 29, 9), at(2032, 2, 29, 9)]);
+        let mut r = Recurrence::new(Frequency::Yearly);
+        r.by_day = vec![by(Some(1), Weekday::Mon)];
+        r.count = Some(2);
+        assert_eq!(starts(&r, at(2026, 1, 5, 9)), vec![at(2026, 1, 5, 9), at(2027, 1, 4, 9)]);
+    }
+
+    #[test]
+    fn window_limits_occurrences() {
+        let r = Recurrence::new(Frequency::Daily);
+        // occurrence that started before window but is still running is included
+        let found = r.occurrences(at(2026, 5, 4, 9), Duration::hours(2), EventZone::Utc, at(2026, 5, 6, 10), at(2026, 5, 8, 9));
+        assert_eq!(found, vec![at(2026, 5, 6, 9), at(2026, 5, 7, 9)]);
+        assert!(r.occurrences(at(2026, 5, 4, 9), Duration::hours(1), EventZone::Utc, at(2026, 5, 1, 0), at(2026, 5, 4, 9)).is_empty());
+    }
+
+    #[test]
+    fn wall_clock_time_is_kept_over_dst() {
+        let r = Recurrence::new(Frequency::Weekly);
+        let zone = EventZone::Tz(Berlin);
+        // 9:00 CET = 8:00 UTC, after 2026-03-29 9:00 CEST = 7:00 UTC
+        let first = Berlin.with_ymd_and_hms(2026, 3, 23, 9, 0, 0).unwrap().to_utc();
+        let found = r.occurrences(first, Duration::hours(1), zone, first, first + Duration::days(14));
+        assert_eq!(found, vec![at(2026, 3, 23, 8), at(2026, 3, 30, 7), at(2026, 4, 6, 7)]);
+    }
+
+    #[test]
+    fn rule_that_never_matches_ends() {
+        // there is no 6th Monday in any month: expansion stops after MAX_EMPTY_PERIODS instead of running forever
+        let mut r = Recurrence::new(Frequency::Monthly);
+        r.by_day = vec![by(Some(6), Weekday::Mon)];
+        assert!(r.check().is_ok());
+        let found = r.occurrences(at(2026, 5, 4, 9), Duration::hours(1), EventZone::Utc, at(2026, 1, 1, 0), DateTimeType::MAX_UTC);
+        assert_eq!(found, vec![at(2026, 5, 4, 9)]);
+    }
+
+    #[test]
+    fn invalid_rules_are_rejected() {
+        let mut r = Recurrence::new(Frequency::Weekly);
+        r.interval = 0;
+        assert!(r.check().is_err());
+        let mut r = Recurrence::new(Frequency::Daily);
+        r.count = Some(0);
+        assert!(r.check().is_err());
+        let mut r = Recurrence::new(Frequency::Weekly);
+        r.by_day = vec![by(Some(1), Weekday::Mon)];
+        assert!(r.check().is_err());
+        let mut r = Recurrence::new(Frequency::Monthly);
+        r.by_day = vec![by(Some(0), Weekday::Mon)];
+        assert!(r.check().is_err());
+    }
+}
@@ -1,9 +1,61 @@
-// struct WrapperApp;
-// impl core::App for WrapperApp {
-//     fn get_name()->&'static str{"Wrapper"}
-//     fn get_inputs()->&'static core::InputList -> {HList![
-//             TypeId::of::<core::EventTable>(),
-//             TypeId::of::<Vec::<core::UUID_TYPE>>()
-//         ]
-//     }
-// }
\ No newline at end of file
+//Standard library of R/E/As that have a vast usage potential and are preprogrammed by developers
+
+pub mod adder;
+pub mod calendar;
+pub mod compact;
+pub mod csv_file;
+pub mod et_file;
+pub mod filter;
+pub mod holidays;
+pub mod ical;
+pub mod materialise;
+pub mod pomodoro;
+pub mod reschedule;
+pub mod scheduler;
+pub mod sql;
+pub mod sqlite;
+pub mod travel;
+pub mod working_hours;
+pub mod wrapper;
+
+use crate::core::{Registry, R_E_A};
+
+/// Registers all std R/E/As and their IO types that can be set directly. sql::SqlQuery is registered in its untyped form (see SqlQuery::from_inputs())
+pub fn register_all(registry: &mut Registry) {
+    registry.register("Adder", || R_E_A::App(Box::new(adder::Adder)));
+    registry.register("Filter", || R_E_A::App(Box::new(filter::Filter)));
+    registry.register("Materialise", || R_E_A::App(Box::new(materialise::Materialise)));
+    registry.register("Pomodoro", || R_E_A::App(Box::new(pomodoro::Pomodoro)));
+    registry.register("WorkingHours", || R_E_A::App(Box::new(working_hours::WorkingHours)));
+    registry.register("Scheduler", || R_E_A::App(Box::new(scheduler::Scheduler)));
+    registry.register("Travel", || R_E_A::App(Box::new(travel::Travel)));
+    registry.register("Wrapper", || R_E_A::App(Box::new(wrapper::Wrapper)));
+    registry.register("Compact", || R_E_A::App(Box::new(compact::Compact)));
+    registry.register("Reschedule", || R_E_A::App(Box::new(reschedule::Reschedule)));
+    registry.register("ManualEvents", || R_E_A::Extension(Box::new(adder::ManualEvents)));
+    registry.register("IcsImport", || R_E_A::Extension(Box::new(ical::IcsImport)));
+    registry.register("CsvImport", || R_E_A::Extension(Box::new(csv_file::CsvImport)));
+    registry.register("EtImport", || R_E_A::Extension(Box::new(et_file::EtImport)));
+    registry.register("Holidays", || R_E_A::Extension(Box::new(holidays::Holidays)));
+    registry.register("WorkingCalendar", || R_E_A::Extension(Box::new(working_hours::WorkingCalendar)));
+    registry.register("SqliteLoad", || R_E_A::Extension(Box::new(sqlite::SqliteLoad)));
+    registry.register("SqlQuery", || R_E_A::Extension(Box::new(sql::SqlQuery::from_inputs())));
+    registry.register("TaskQuery", || R_E_A::Extension(Box::new(scheduler::TaskQuery)));
+    registry.register("TravelQuery", || R_E_A::Extension(Box::new(travel::TravelQuery)));
+    registry.register("IcsExport", || R_E_A::Render(Box::new(ical::IcsExport)));
+    registry.register("CsvExport", || R_E_A::Render(Box::new(csv_file::CsvExport)));
+    registry.register("EtExport", || R_E_A::Render(Box::new(et_file::EtExport)));
+    registry.register("SqliteSave", || R_E_A::Render(Box::new(sqlite::SqliteSave)));
+    registry.register("CalendarRender", || R_E_A::Render(Box::new(calendar::CalendarRender)));
+
+    registry.register_value::<csv_file::CsvMapping>();
+    registry.register_value::<working_hours::WorkingTime>();
+    registry.register_value::<Vec<scheduler::Task>>();
+    registry.register_value::<travel::TravelTimes>();
+    registry.register_value::<wrapper::WrapPolicy>();
+    registry.register_value::<Vec<(String, sql::SqlType)>>();
+    registry.register_debug::<Vec<scheduler::Unplaced>>();
+    registry.register_debug::<Vec<travel::InfeasibleDay>>();
+    registry.register_debug::<Vec<reschedule::Late>>();
+    registry.register_debug::<Vec<sql::SqlRow>>();
+}
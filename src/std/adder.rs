@@ -0,0 +1,145 @@
+//Responsible for adding Events to ET: Adder App and ManualEvents Extension for Events entered by hand in GUI
+
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+use crate::core::{get_input, App, EventSpec, EventTable, Extension, IOListOfTypes, IOOutputType, IOType, R_E_AClass, R_E_AGeneric};
+
+// ==========ADDER APP==========
+
+/// Adds Events to ET. Usually first App of pipeline
+/// Input: "events": Vec<EventSpec> (Events with UUID that already exists in ET are skipped, so same Events can be added twice safely)
+/// Output: ET with added Events
+pub struct Adder;
+
+impl R_E_AGeneric for Adder {
+    fn get_name(&self)->&'static str {
+        "Adder"
+    }
+    fn get_class(&self)->&'static R_E_AClass {
+        &R_E_AClass::App
+    }
+    fn get_inputs(&self)->&'static IOListOfTypes {
+        static INPUTS: LazyLock<IOListOfTypes> = LazyLock::new(|| HashMap::from([
+            ("events".to_string(), TypeId::of::<Vec<EventSpec>>()),
+        ]));
+        &INPUTS
+    }
+}
+
+impl App for Adder {
+    fn run(&self,
+        mut et: EventTable,
+        inputs: IOType
+    )->Result<EventTable, String> {
+        let events = get_input::<Vec<EventSpec>>(&inputs, "events")?;
+        for spec in events {
+            if spec.uuid.is_some_and(|uuid| et.get(uuid).is_some()) {
+                continue;
+            }
+            et.add_spec(spec.clone()).map_err(|e| format!("Adder: Event {}: {}", spec.name, e))?;
+        }
+        Ok(et)
+    }
+}
+
+
+
+// ==========MANUAL EVENTS EXTENSION==========
+
+/// Events entered by hand: input is set in GUI (or "Inputs" of pipeline file) and passed to output unchanged
+/// Input: "events": Vec<EventSpec>
+/// Output: "events": Vec<EventSpec>
+pub struct ManualEvents;
+
+impl R_E_AGeneric for ManualEvents {
+    fn get_name(&self)->&'static str {
+        "ManualEvents"
+    }
+    fn get_class(&self)->&'static R_E_AClass {
+        &R_E_AClass::Extension
+    }
+    fn get_inputs(&self)->&'static IOListOfTypes {
+        static INPUTS: LazyLock<IOListOfTypes> = LazyLock::new(|| HashMap::from([
+            ("events".to_string(), TypeId::of::<Vec<EventSpec>>()),
+        ]));
+        &INPUTS
+    }
+}
+
+impl Extension for ManualEvents {
+    fn get_outputs(&self)->&'static IOListOfTypes {
+        static OUTPUTS: LazyLock<IOListOfTypes> = LazyLock::new(|| HashMap::from([
+            ("events".to_string(), TypeId::of::<Vec<EventSpec>>()),
+        ]));
+        &OUTPUTS
+    }
+
+    fn run(&self,
+        inputs: IOType
+    )->Result<IOOutputType, String> {
+        let events = get_input::<Vec<EventSpec>>(&inputs, "events")?;
+        Ok(HashMap::from([
+            ("events".to_string(), Box::new(events.clone()) as Box<dyn Any+Send+Sync>),
+        ]))
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use uuid::Uuid;
+    use crate::core::EventZone;
+    use crate::core::testing::{at, times, TestInputs};
+
+    fn specs()->Vec<EventSpec> {
+        let mut meeting = EventSpec::new("Meeting".to_string(), at(4, 9, 0), at(4, 10, 0));
+        meeting.uuid = Some(Uuid::from_u128(1));
+        meeting.zone = EventZone::Tz(chrono_tz::Europe::Berlin);
+        meeting.fields.insert("room".to_string(), "A".to_string());
+        let day = NaiveDate::from_ymd_opt(2026, 5, 5).unwrap();
+        vec![meeting, EventSpec::new_all_day("Holiday".to_string(), day, day.succ_opt().unwrap())]
+    }
+
+    #[test]
+    fn specs_are_added_as_events() {
+        let et = Adder.run(EventTable::new(), TestInputs::new().with("events", specs()).get()).unwrap();
+        assert_eq!(et.get_events().count(), 2);
+        let meeting = et.get(Uuid::from_u128(1)).unwrap();
+        assert_eq!((meeting.get_name(), meeting.start, meeting.end), ("Meeting", at(4, 9, 0), at(4, 10, 0)));
+        assert_eq!((meeting.zone, meeting.fields["room"].as_str()), (EventZone::Tz(chrono_tz::Europe::Berlin), "A"));
+        let holiday = et.get_events().find(|e| e.get_name() == "Holiday").unwrap();
+        assert_eq!((holiday.start, holiday.end, holiday.all_day.is_some()), (at(5, 0, 0), at(6, 0, 0), true));
+        assert!(et.check_self().is_ok());
+    }
+
+    #[test]
+    fn existing_uuids_are_skipped() {
+        let et = Adder.run(EventTable::new(), TestInputs::new().with("events", specs()).get()).unwrap();
+        let mut moved = et;
+        moved.set_times(Uuid::from_u128(1), at(4, 11, 0), at(4, 12, 0)).unwrap();
+
+        // Event with known UUID stays as it is, Event without UUID is added again
+        let et = Adder.run(moved, TestInputs::new().with("events", specs()).get()).unwrap();
+        assert_eq!(et.get_events().count(), 3);
+        assert_eq!(times(&et, Uuid::from_u128(1)), (at(4, 11, 0), at(4, 12, 0)));
+        assert_eq!(et.get_events().filter(|e| e.get_name() == "Holiday").count(), 2);
+    }
+
+    #[test]
+    fn invalid_specs_are_rejected() {
+        let reversed = vec![EventSpec::new("Reversed".to_string(), at(4, 10, 0), at(4, 9, 0))];
+        let error = Adder.run(EventTable::new(), TestInputs::new().with("events", reversed).get()).unwrap_err();
+        assert!(error.starts_with("Adder: Event Reversed:"), "{}", error);
+    }
+
+    #[test]
+    fn manual_events_are_passed_through() {
+        let mut outputs = ManualEvents.run(TestInputs::new().with("events", specs()).get()).unwrap();
+        assert_eq!(*outputs.remove("events").unwrap().downcast::<Vec<EventSpec>>().unwrap(), specs());
+    }
+}
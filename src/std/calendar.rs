@@ -0,0 +1,605 @@
+//Responsible for calendar GUI: month/week/day views of ET as Render and as reusable egui view
+
+use std::any::TypeId;
+use std::collections::{HashMap, HashSet};
+use std::sync::LazyLock;
+
+use chrono::{Datelike, Days, Duration, Months, NaiveDate, NaiveDateTime, NaiveTime, Utc, Weekday};
+use chrono_tz::Tz;
+use eframe::egui;
+use egui::{Align2, Color32, FontId, Pos2, Rect, RichText, Sense, Stroke, Vec2};
+use uuid::Uuid;
+
+use crate::core::{get_input, DateTimeType, Event, EventTable, EventZone, IOListOfTypes, IOType, R_E_AClass, R_E_AGeneric, Render};
+use crate::gui::claim_event_loop;
+use crate::std::filter::is_hidden;
+
+const HOUR_HEIGHT: f32 = 40.0;
+const GUTTER_WIDTH: f32 = 45.0;
+const HEADER_HEIGHT: f32 = 20.0;
+const ALL_DAY_HEIGHT: f32 = 18.0;
+/// Zero-length and very short Events are drawn as if they last this long
+const MIN_DRAWN_MINUTES: i64 = 20;
+/// Events listed in one day of month view, others are shown as "+n more"
+const MONTH_CELL_ITEMS: usize = 3;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CalendarMode {
+    Month,
+    Week,
+    Day,
+}
+
+/// Event or occurrence of recurring Event as viewer sees it
+#[derive(Clone, Debug)]
+struct CalendarItem {
+    uuid: Uuid,
+    name: String,
+    /// Wall-clock times in zone of viewer
+    start: NaiveDateTime,
+    end: NaiveDateTime,
+    all_day: bool,
+    /// Not the Event itself but its (not materialised) repetition
+    occurrence: bool,
+}
+
+impl CalendarItem {
+    fn on_date(&self, date: NaiveDate)->bool {
+        let day_start = date.and_time(NaiveTime::MIN);
+        self.start < day_start + Duration::days(1) && (self.end > day_start || self.start >= day_start)
+    }
+
+    fn color(&self)->Color32 {
+        let b = self.uuid.as_bytes();
+        Color32::from_rgb(50 + b[0] / 2, 50 + b[1] / 2, 50 + b[2] / 2)
+    }
+}
+
+/// Calendar of one ET, can be shown as own window (see CalendarRender) or inside other GUI with ui()
+pub struct CalendarView {
+    et: EventTable,
+    /// Zone of viewer
+    zone: Tz,
+    pub mode: CalendarMode,
+    /// Any date of shown month/week/day
+    pub date: NaiveDate,
+    /// (Uuid, wall-clock start) of selected item
+    selected: Option<(Uuid, NaiveDateTime)>,
+    /// Items of shown dates [from, to), rebuilt after navigation
+    cache: Option<(NaiveDate, NaiveDate, Vec<CalendarItem>)>,
+}
+
+impl CalendarView {
+    pub fn new(et: EventTable, zone: Tz)->Self {
+        Self {
+            et,
+            zone,
+            mode: CalendarMode::Month,
+            date: Utc::now().with_timezone(&zone).date_naive(),
+            selected: None,
+            cache: None,
+        }
+    }
+
+    pub fn set_et(&mut self, et: EventTable) {
+        self.et = et;
+        self.selected = None;
+        self.cache = None;
+    }
+
+    /// Shown dates [from, to) of current mode
+    fn shown_dates(&self)->(NaiveDate, NaiveDate) {
+        match self.mode {
+            CalendarMode::Month => {
+                let first = self.date.with_day(1).unwrap_or(self.date);
+                let grid_start = first.week(Weekday::Mon).first_day();
+                let after_last = first + Months::new(1);
+                let weeks = ((after_last - grid_start).num_days() + 6) / 7;
+                (grid_start, grid_start + Days::new(weeks as u64 * 7))
+            }
+            CalendarMode::Week => {
+                let monday = self.date.week(Weekday::Mon).first_day();
+                (monday, monday + Days::new(7))
+            }
+            CalendarMode::Day => (self.date, self.date + Days::new(1)),
+        }
+    }
+
+    fn items(&mut self, from: NaiveDate, to: NaiveDate)->&[CalendarItem] {
+        if !matches!(&self.cache, Some((f, t, _)) if *f == from && *t == to) {
+            self.cache = Some((from, to, collect_items(&self.et, self.zone, from, to)));
+        }
+        self.cache.as_ref().map(|c| c.2.as_slice()).unwrap_or_default()
+    }
+
+    fn step(&mut self, forward: bool) {
+        let date = match (self.mode, forward) {
+            (CalendarMode::Month, true) => self.date.checked_add_months(Months::new(1)),
+            (CalendarMode::Month, false) => self.date.checked_sub_months(Months::new(1)),
+            (CalendarMode::Week, true) => self.date.checked_add_days(Days::new(7)),
+            (CalendarMode::Week, false) => self.date.checked_sub_days(Days::new(7)),
+            (CalendarMode::Day, true) => self.date.succ_opt(),
+            (CalendarMode::Day, false) => self.date.pred_opt(),
+        };
+        self.date = date.unwrap_or(self.date);
+    }
+
+    pub fn ui(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.selectable_value(&mut self.mode, CalendarMode::Month, "Month");
+            ui.selectable_value(&mut self.mode, CalendarMode::Week, "Week");
+            ui.selectable_value(&mut self.mode, CalendarMode::Day, "Day");
+            ui.separator();
+            if ui.button("<").clicked() {
+                self.step(false);
+            }
+            if ui.button("Today").clicked() {
+                self.date = Utc::now().with_timezone(&self.zone).date_naive();
+            }
+            if ui.button(">").clicked() {
+                self.step(true);
+            }
+            let title = match self.mode {
+                CalendarMode::Month => self.date.format("%B %Y").to_string(),
+                CalendarMode::Week => format!("Week {} of {}", self.date.iso_week().week(), self.date.iso_week().year()),
+                CalendarMode::Day => self.date.format("%A, %Y-%m-%d").to_string(),
+            };
+            ui.heading(title);
+            ui.label(RichText::new(self.zone.name()).weak());
+        });
+        ui.separator();
+
+        self.details_ui(ui);
+
+        let (from, to) = self.shown_dates();
+        let days: Vec<NaiveDate> = from.iter_days().take_while(|d| *d < to).collect();
+        let month = self.date.month();
+        let now = Utc::now().with_timezone(&self.zone).naive_local();
+        let items = self.items(from, to).to_vec();
+        let action = match self.mode {
+            CalendarMode::Month => month_ui(ui, &items, &days, month, now.date()),
+            CalendarMode::Week | CalendarMode::Day => time_grid_ui(ui, &items, &days, now),
+        };
+        match action {
+            Some(Action::Select(i)) => self.selected = Some((items[i].uuid, items[i].start)),
+            Some(Action::OpenDay(date)) => {
+                self.mode = CalendarMode::Day;
+                self.date = date;
+            }
+            None => {}
+        }
+    }
+
+    fn details_ui(&mut self, ui: &mut egui::Ui) {
+        let Some((uuid, start)) = self.selected else { return };
+        let Some(event) = self.et.get(uuid) else {
+            self.selected = None;
+            return;
+        };
+        let mut close = false;
+        egui::Frame::group(ui.style()).show(ui, |ui| {
+            ui.horizontal(|ui| {
+                ui.strong(event.get_name());
+                if ui.small_button("x").clicked() {
+                    close = true;
+                }
+            });
+            let end = start + (event.end_in(self.zone) - event.start_in(self.zone));
+            match event.all_day {
+                Some(_) => ui.label(format!("{} – {} (all day)", start.date(), (end - Duration::days(1)).date())),
+                None => ui.label(format!("{} – {}", start.format("%Y-%m-%d %H:%M"), end.format("%Y-%m-%d %H:%M"))),
+            };
+            match event.zone {
+                EventZone::Utc => ui.label("Zone: UTC"),
+                EventZone::Tz(tz) => ui.label(format!("Zone: {}", tz.name())),
+                EventZone::Floating => ui.label("Zone: floating (same wall-clock time everywhere)"),
+            };
+            if event.recurrence.is_some() {
+                ui.label(if start == event.start_in(self.zone) { "Recurring Event" } else { "Occurrence of recurring Event" });
+            }
+            if let Some(parent) = event.parent {
+                ui.label(format!("Part of {}", parent));
+            }
+            if !event.fields.is_empty() {
+                egui::Grid::new("calendar_details_fields").show(ui, |ui| {
+                    for (key, value) in &event.fields {
+                        ui.label(RichText::new(key).weak());
+                        ui.label(value);
+                        ui.end_row();
+                    }
+                });
+            }
+            ui.label(RichText::new(uuid.to_string()).weak().small());
+        });
+        if close {
+            self.selected = None;
+        }
+    }
+}
+
+impl eframe::App for CalendarView {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        egui::CentralPanel::default().show(ctx, |ui| self.ui(ui));
+    }
+}
+
+/// What user clicked
+enum Action {
+    /// Index of item
+    Select(usize),
+    OpenDay(NaiveDate),
+}
+
+/// Events and occurrences overlapping wall-clock dates [from, to) of viewer, ordered by start. Events hidden by Filter are skipped
+fn collect_items(et: &EventTable, zone: Tz, from: NaiveDate, to: NaiveDate)->Vec<CalendarItem> {
+    let (local_from, local_to) = (from.and_time(NaiveTime::MIN), to.and_time(NaiveTime::MIN));
+    // Floating Events are stored as wall-clock time in UTC, so window is widened by a day
+    let viewer = EventZone::Tz(zone);
+    let (abs_from, abs_to) = (viewer.resolve_local(local_from) - Duration::days(1), viewer.resolve_local(local_to) + Duration::days(1));
+
+    let materialised: HashSet<(Uuid, DateTimeType)> = et.get_events()
+        .filter_map(|e| Some((e.parent?, e.recurrence_id?)))
+        .collect();
+    let single = et.overlapping(abs_from, abs_to).into_iter().filter(|e| e.recurrence.is_none());
+    let recurring = et.get_events().filter(|e| e.recurrence.is_some());
+
+    let mut items = Vec::new();
+    for event in single.chain(recurring).filter(|e| !is_hidden(e)) {
+        for (start, end) in et.occurrences(event.uuid, abs_from, abs_to).unwrap_or_default() {
+            let occurrence = start != event.start;
+            if occurrence && materialised.contains(&(event.uuid, start)) {
+                continue;
+            }
+            let item = CalendarItem {
+                uuid: event.uuid,
+                name: event.get_name().to_string(),
+                start: Event::time_in(event.zone, start, zone),
+                end: Event::time_in(event.zone, end, zone),
+                all_day: event.all_day.is_some(),
+                occurrence,
+            };
+            if item.start < local_to && (item.end > local_from || item.start >= local_from) {
+                items.push(item);
+            }
+        }
+    }
+    items.sort_by_key(|i| (i.start, i.end));
+    items
+}
+
+/// (column, amount of columns in its group of overlapping intervals) of each interval, intervals MUST be ordered by start.
+/// Intervals shorter than min_length take min_length
+fn layout_columns(intervals: &[(NaiveDateTime, NaiveDateTime)], min_length: Duration)->Vec<(usize, usize)> {
+    let mut result = vec![(0, 1); intervals.len()];
+    let mut column_ends: Vec<NaiveDateTime> = Vec::new();
+    let mut group_start = 0;
+    let mut group_end = NaiveDateTime::MIN;
+    for (i, &(start, end)) in intervals.iter().enumerate() {
+        let end = end.max(start + min_length);
+        if start >= group_end && i > group_start {
+            for r in &mut result[group_start..i] {
+                r.1 = column_ends.len();
+            }
+            column_ends.clear();
+            group_start = i;
+        }
+        let column = match column_ends.iter().position(|e| *e <= start) {
+            Some(column) => {
+                column_ends[column] = end;
+                column
+            }
+            None => {
+                column_ends.push(end);
+                column_ends.len() - 1
+            }
+        };
+        result[i].0 = column;
+        group_end = if i == group_start { end } else { group_end.max(end) };
+    }
+    for r in &mut result[group_start..] {
+        r.1 = column_ends.len();
+    }
+    result
+}
+
+/// Colored block with name of item; true if clicked
+fn item_block(ui: &mut egui::Ui, rect: Rect, item: &CalendarItem, id: egui::Id)->bool {
+    let response = ui.interact(rect, id, Sense::click());
+    let fill = if response.hovered() { item.color().gamma_multiply(1.3) } else { item.color() };
+    ui.painter().rect_filled(rect.shrink(1.0), 3.0, fill);
+    ui.painter().with_clip_rect(rect.shrink(2.0)).text(
+        rect.left_top() + Vec2::new(4.0, 2.0),
+        Align2::LEFT_TOP,
+        if item.occurrence { format!("↻ {}", item.name) } else { item.name.clone() },
+        FontId::proportional(12.0),
+        Color32::WHITE,
+    );
+    response.on_hover_text(format!("{}\n{} – {}", item.name, item.start.format("%Y-%m-%d %H:%M"), item.end.format("%Y-%m-%d %H:%M"))).clicked()
+}
+
+/// text cut to max_chars characters with "…"
+fn truncated(text: &str, max_chars: usize)->String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let mut cut: String = text.chars().take(max_chars.saturating_sub(1)).collect();
+    cut.push('…');
+    cut
+}
+
+fn month_ui(ui: &mut egui::Ui, items: &[CalendarItem], days: &[NaiveDate], month: u32, today: NaiveDate)->Option<Action> {
+    let mut action = None;
+    let column_width = (ui.available_width() / 7.0 - ui.spacing().item_spacing.x).max(40.0);
+    // approximate width of small font character
+    let max_chars = (column_width / 6.0) as usize;
+    egui::ScrollArea::vertical().show(ui, |ui| {
+        egui::Grid::new("calendar_month")
+            .num_columns(7)
+            .min_col_width(column_width)
+            .max_col_width(column_width)
+            .show(ui, |ui| {
+                for weekday in ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"] {
+                    ui.strong(weekday);
+                }
+                ui.end_row();
+
+                for week in days.chunks(7) {
+                    for &day in week {
+                        ui.vertical(|ui| {
+                            ui.set_min_height(20.0 + 16.0 * MONTH_CELL_ITEMS as f32);
+                            let mut number = RichText::new(day.day().to_string());
+                            if day.month() != month {
+                                number = number.weak();
+                            }
+                            if day == today {
+                                number = number.strong().underline();
+                            }
+                            if ui.add(egui::Button::new(number).frame(false)).clicked() {
+                                action = Some(Action::OpenDay(day));
+                            }
+
+                            let on_day: Vec<usize> = (0..items.len()).filter(|&i| items[i].on_date(day)).collect();
+                            for &i in on_day.iter().take(MONTH_CELL_ITEMS) {
+                                let item = &items[i];
+                                let text = match item.all_day {
+                                    true => item.name.clone(),
+                                    false => format!("{} {}", item.start.format("%H:%M"), item.name),
+                                };
+                                let text = truncated(&text, max_chars);
+                                let label = egui::Label::new(RichText::new(text).color(item.color()).small()).wrap(false).sense(Sense::click());
+                                if ui.add(label).clicked() {
+                                    action = Some(Action::Select(i));
+                                }
+                            }
+                            if on_day.len() > MONTH_CELL_ITEMS && ui.small_button(format!("+{} more", on_day.len() - MONTH_CELL_ITEMS)).clicked() {
+                                action = Some(Action::OpenDay(day));
+                            }
+                        });
+                    }
+                    ui.end_row();
+                }
+            });
+    });
+    action
+}
+
+/// Week/day view: all-day Events on top, timed Events on time axis, overlapping Events side by side. now is wall-clock time of viewer
+fn time_grid_ui(ui: &mut egui::Ui, items: &[CalendarItem], days: &[NaiveDate], now: NaiveDateTime)->Option<Action> {
+    let mut action = None;
+    let today = now.date();
+    let column_width = ((ui.available_width() - GUTTER_WIDTH) / days.len() as f32).max(30.0);
+    let column_x = |left: f32, day: usize| left + GUTTER_WIDTH + day as f32 * column_width;
+    let stroke = ui.visuals().widgets.noninteractive.bg_stroke;
+    let text_color = ui.visuals().text_color();
+    let (first_day, after_last_day) = (days[0].and_time(NaiveTime::MIN), (days[days.len() - 1] + Days::new(1)).and_time(NaiveTime::MIN));
+
+    // header: dates and all-day Events as bars (each bar in own row)
+    let all_day: Vec<usize> = (0..items.len()).filter(|&i| items[i].all_day).collect();
+    let bars: Vec<(NaiveDateTime, NaiveDateTime)> = all_day.iter().map(|&i| (items[i].start, items[i].end)).collect();
+    let rows = layout_columns(&bars, Duration::zero());
+    let amount_of_rows = rows.iter().map(|r| r.0 + 1).max().unwrap_or(0);
+    let (header, _) = ui.allocate_exact_size(
+        Vec2::new(GUTTER_WIDTH + column_width * days.len() as f32, HEADER_HEIGHT + ALL_DAY_HEIGHT * amount_of_rows as f32),
+        Sense::hover(),
+    );
+    for (d, day) in days.iter().enumerate() {
+        let rect = Rect::from_min_size(Pos2::new(column_x(header.left(), d), header.top()), Vec2::new(column_width, HEADER_HEIGHT));
+        let response = ui.interact(rect, ui.id().with(("calendar_day", *day)), Sense::click());
+        let color = if *day == today { ui.visuals().strong_text_color() } else { text_color };
+        ui.painter().text(rect.center(), Align2::CENTER_CENTER, day.format("%a %d.%m").to_string(), FontId::proportional(13.0), color);
+        if response.clicked() {
+            action = Some(Action::OpenDay(*day));
+        }
+    }
+    for (bar, &i) in all_day.iter().enumerate() {
+        let item = &items[i];
+        let from_day = (item.start.max(first_day) - first_day).num_days() as f32;
+        let to_day = (item.end.min(after_last_day) - first_day).num_days().max(from_day as i64 + 1) as f32;
+        let top = header.top() + HEADER_HEIGHT + ALL_DAY_HEIGHT * rows[bar].0 as f32;
+        let rect = Rect::from_min_max(
+            Pos2::new(column_x(header.left(), 0) + from_day * column_width, top),
+            Pos2::new(column_x(header.left(), 0) + to_day * column_width, top + ALL_DAY_HEIGHT),
+        );
+        if item_block(ui, rect, item, ui.id().with(("calendar_all_day", i))) {
+            action = Some(Action::Select(i));
+        }
+    }
+
+    egui::ScrollArea::vertical().show(ui, |ui| {
+        let (grid, _) = ui.allocate_exact_size(Vec2::new(header.width(), 24.0 * HOUR_HEIGHT), Sense::hover());
+        for hour in 0..24 {
+            let y = grid.top() + hour as f32 * HOUR_HEIGHT;
+            ui.painter().hline(grid.left() + GUTTER_WIDTH..=grid.right(), y, stroke);
+            ui.painter().text(Pos2::new(grid.left() + 4.0, y + 2.0), Align2::LEFT_TOP, format!("{:02}:00", hour), FontId::proportional(11.0), text_color);
+        }
+        for d in 0..=days.len() {
+            let x = column_x(grid.left(), d);
+            ui.painter().vline(x, grid.y_range(), stroke);
+        }
+
+        for (d, day) in days.iter().enumerate() {
+            let day_start = day.and_time(NaiveTime::MIN);
+            let day_end = day_start + Duration::days(1);
+            // parts of timed Events inside this day
+            let timed: Vec<usize> = (0..items.len()).filter(|&i| !items[i].all_day && items[i].on_date(*day)).collect();
+            let parts: Vec<(NaiveDateTime, NaiveDateTime)> = timed.iter()
+                .map(|&i| (items[i].start.max(day_start), items[i].end.min(day_end)))
+                .collect();
+            let columns = layout_columns(&parts, Duration::minutes(MIN_DRAWN_MINUTES));
+
+            let y_of = |t: NaiveDateTime| grid.top() + (t - day_start).num_minutes() as f32 / 60.0 * HOUR_HEIGHT;
+            for (p, &i) in timed.iter().enumerate() {
+                let (start, end) = parts[p];
+                let (column, amount_of_columns) = columns[p];
+                let width = column_width / amount_of_columns as f32;
+                let left = column_x(grid.left(), d) + column as f32 * width;
+                let top = y_of(start);
+                let bottom = y_of(end).max(top + MIN_DRAWN_MINUTES as f32 / 60.0 * HOUR_HEIGHT).min(grid.bottom());
+                let rect = Rect::from_min_max(Pos2::new(left, top), Pos2::new(left + width, bottom));
+                if item_block(ui, rect, &items[i], ui.id().with(("calendar_timed", i, *day))) {
+                    action = Some(Action::Select(i));
+                }
+            }
+            if *day == today {
+                let x = column_x(grid.left(), d);
+                ui.painter().hline(x..=x + column_width, y_of(now), Stroke::new(2.0, Color32::RED));
+            }
+        }
+    });
+    action
+}
+
+
+
+// ==========RENDER==========
+
+/// Shows ET in calendar window (month/week/day views). Blocks until window is closed.
+/// Only one window can be opened per process, so in REA GUI this Render fails (Data panel shows ET pipes as calendar instead)
+/// Input: "zone": Tz (zone of viewer)
+pub struct CalendarRender;
+
+impl R_E_AGeneric for CalendarRender {
+    fn get_name(&self)->&'static str {
+        "CalendarRender"
+    }
+    fn get_class(&self)->&'static R_E_AClass {
+        &R_E_AClass::Render
+    }
+    fn get_inputs(&self)->&'static IOListOfTypes {
+        static INPUTS: LazyLock<IOListOfTypes> = LazyLock::new(|| HashMap::from([
+            ("zone".to_string(), TypeId::of::<Tz>()),
+        ]));
+        &INPUTS
+    }
+}
+
+impl Render for CalendarRender {
+    fn run(&self,
+        et: EventTable,
+        inputs: IOType
+    )->Result<(), String> {
+        let zone = *get_input::<Tz>(&inputs, "zone")?;
+        if !claim_event_loop() {
+            return Err("CalendarRender: calendar window can not be opened from running GUI or twice, use \"Calendar\" in Data panel instead".into());
+        }
+        eframe::run_native(
+            "REA calendar",
+            eframe::NativeOptions::default(),
+            Box::new(move |_cc| Box::new(CalendarView::new(et, zone))),
+        ).map_err(|e| format!("CalendarRender: {}", e))
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use crate::core::EventSpec;
+    use crate::core::testing::at;
+    use crate::recurrence::{Frequency, Recurrence};
+    use crate::std::filter::HIDDEN_FIELD;
+
+    fn local(day: u32, h: u32, m: u32)->NaiveDateTime {
+        NaiveDate::from_ymd_opt(2026, 5, day).unwrap().and_hms_opt(h, m, 0).unwrap()
+    }
+
+    fn date(month: u32, day: u32)->NaiveDate {
+        NaiveDate::from_ymd_opt(2026, month, day).unwrap()
+    }
+
+    /// Layout of intervals of one day given as ("09:00", "10:00")
+    fn columns(intervals: &[(&str, &str)])->Vec<(usize, usize)> {
+        let time = |text: &str| date(5, 4).and_time(NaiveTime::parse_from_str(text, "%H:%M").unwrap());
+        let intervals: Vec<_> = intervals.iter().map(|(start, end)| (time(start), time(end))).collect();
+        layout_columns(&intervals, Duration::minutes(MIN_DRAWN_MINUTES))
+    }
+
+    #[test]
+    fn chained_overlaps_share_columns() {
+        // third starts when first ends, so it takes first column; last is alone
+        assert_eq!(columns(&[("09:00", "10:00"), ("09:30", "10:30"), ("10:00", "11:00"), ("12:00", "13:00")]),
+            vec![(0, 2), (1, 2), (0, 2), (0, 1)]);
+    }
+
+    #[test]
+    fn nested_overlaps_get_more_columns() {
+        assert_eq!(columns(&[("09:00", "12:00"), ("09:30", "10:00"), ("10:30", "11:00"), ("10:45", "11:30")]),
+            vec![(0, 3), (1, 3), (1, 3), (2, 3)]);
+    }
+
+    #[test]
+    fn zero_length_intervals_take_minimal_length() {
+        // 14:00 is drawn as 14:00-14:20, so it collides with 14:10; 15:00 does not collide with 15:20
+        assert_eq!(columns(&[("14:00", "14:00"), ("14:10", "14:30"), ("15:00", "15:00"), ("15:20", "15:20")]),
+            vec![(0, 2), (1, 2), (0, 1), (0, 1)]);
+        assert_eq!(columns(&[]), vec![]);
+    }
+
+    #[test]
+    fn hidden_events_and_materialised_occurrences_are_skipped() {
+        let mut et = EventTable::new();
+        let visible = et.add_spec(EventSpec::new("Visible".to_string(), at(4, 9, 0), at(4, 10, 0))).unwrap();
+        let mut hidden = EventSpec::new("Hidden".to_string(), at(4, 11, 0), at(4, 12, 0));
+        hidden.fields.insert(HIDDEN_FIELD.to_string(), "rest".to_string());
+        et.add_spec(hidden).unwrap();
+        let mut daily = EventSpec::new("Daily".to_string(), at(4, 7, 0), at(4, 8, 0));
+        daily.recurrence = Some(Recurrence::new(Frequency::Daily));
+        let daily = et.add_spec(daily).unwrap();
+        // occurrence of Tuesday is materialised and moved, it is shown only once at its new time
+        let tuesday = et.materialise(daily, at(5, 0, 0), at(6, 0, 0)).unwrap()[0];
+        et.set_times(tuesday, at(5, 15, 0), at(5, 16, 0)).unwrap();
+
+        let items = collect_items(&et, chrono_tz::UTC, date(5, 4), date(5, 7));
+        let seen: Vec<_> =items.iter().map(|i| (i.uuid, i.start, i.occurrence)).collect();
+        assert_eq!(seen, vec![
+            (daily, local(4, 7, 0), false),
+            (visible, local(4, 9, 0), false),
+            (tuesday, local(5, 15, 0), false),
+            (daily, local(6, 7, 0), true),
+        ]);
+    }
+
+    #[test]
+    fn times_are_wall_clock_times_of_viewer() {
+        let mut et = EventTable::new();
+        // Floating 23:30 is stored as 23:30 UTC, which is next day in Tokyo: found only because window is widened
+        let mut floating = EventSpec::new("Floating".to_string(), at(4, 23, 30), at(4, 23, 45));
+        floating.zone = EventZone::Floating;
+        let floating = et.add_spec(floating).unwrap();
+        let items = collect_items(&et, chrono_tz::Asia::Tokyo, date(5, 4), date(5, 5));
+        assert_eq!(items.iter().map(|i| (i.uuid, i.start, i.end)).collect::<Vec<_>>(), vec![(floating, local(4, 23, 30), local(4, 23, 45))]);
+
+        // 12:00 UTC is 13:00 in Berlin before DST start and 14:00 after it
+        let mut et = EventTable::new();
+        et.add_spec(EventSpec::new("Before".to_string(), Utc.with_ymd_and_hms(2026, 3, 28, 12, 0, 0).unwrap(), Utc.with_ymd_and_hms(2026, 3, 28, 13, 0, 0).unwrap())).unwrap();
+        et.add_spec(EventSpec::new("After".to_string(), Utc.with_ymd_and_hms(2026, 3, 29, 12, 0, 0).unwrap(), Utc.with_ymd_and_hms(2026, 3, 29, 13, 0, 0).unwrap())).unwrap();
+        let items = collect_items(&et, chrono_tz::Europe::Berlin, date(3, 28), date(3, 30));
+        assert_eq!(items.iter().map(|i| (i.name.as_str(), i.start.time())).collect::<Vec<_>>(), vec![
+            ("Before", NaiveTime::from_hms_opt(13, 0, 0).unwrap()),
+            ("After", NaiveTime::from_hms_opt(14, 0, 0).unwrap()),
+        ]);
+        assert!(collect_items(&et, chrono_tz::Europe::Berlin, date(3, 30), date(3, 31)).is_empty());
+    }
+}
@@ -0,0 +1,174 @@
+//Responsible for Compact App: overlaps are resolved by pushing Events later, since Events can not be deleted
+
+use std::any::TypeId;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::LazyLock;
+
+use chrono_tz::Tz;
+use uuid::Uuid;
+
+use crate::core::{get_input, App, BusyTime, DateTimeType, EventTable, EventZone, IOListOfTypes, IOType, R_E_AClass, R_E_AGeneric};
+use crate::std::filter::is_hidden;
+
+/// Pushes overlapping Events later, walking Events of each assignee in time order (Events without assignee field are treated as Events of one person "",
+/// empty assignee_field → all Events are one person). Hidden, all-day, recurring and zero-length Events are not considered.
+/// Pinned Events (having pinned_field) are never moved, other Events are placed around them.
+/// Event that would be pushed past horizon is moved to the end of ET instead (after last Event of its assignee), so it does not push everything after it
+/// Inputs: "assignee_field": String, "pinned_field": String (e.g. "pinned", empty → nothing is pinned), "horizon": Option<DateTimeType>, "zone": Tz (zone of Floating Events)
+/// Output: ET without overlaps between Events of same assignee (except overlaps between pinned Events)
+pub struct Compact;
+
+impl R_E_AGeneric for Compact {
+    fn get_name(&self)->&'static str {
+        "Compact"
+    }
+    fn get_class(&self)->&'static R_E_AClass {
+        &R_E_AClass::App
+    }
+    fn get_inputs(&self)->&'static IOListOfTypes {
+        static INPUTS: LazyLock<IOListOfTypes> = LazyLock::new(|| HashMap::from([
+            ("assignee_field".to_string(), TypeId::of::<String>()),
+            ("pinned_field".to_string(), TypeId::of::<String>()),
+            ("horizon".to_string(), TypeId::of::<Option<DateTimeType>>()),
+            ("zone".to_string(), TypeId::of::<Tz>()),
+        ]));
+        &INPUTS
+    }
+}
+
+/// Event of one assignee: (start, uuid, end, zone), absolute times
+type Item = (DateTimeType, Uuid, DateTimeType, EventZone);
+
+impl App for Compact {
+    fn run(&self,
+        mut et: EventTable,
+        inputs: IOType
+    )->Result<EventTable, String> {
+        let assignee_field = get_input::<String>(&inputs, "assignee_field")?;
+        let pinned_field = get_input::<String>(&inputs, "pinned_field")?;
+        let horizon = *get_input::<Option<DateTimeType>>(&inputs, "horizon")?;
+        let zone = *get_input::<Tz>(&inputs, "zone")?;
+
+        // assignee → (pinned, movable)
+        let mut groups: BTreeMap<String, (Vec<Item>, Vec<Item>)> = BTreeMap::new();
+        for event in et.get_events() {
+            if is_hidden(event) || event.all_day.is_some() || event.recurrence.is_some() || event.start == event.end {
+                continue;
+            }
+            let assignee = event.fields.get(assignee_field).cloned().unwrap_or_default();
+            let (start, end) = event.timed_in(zone);
+            let (pinned, movable) = groups.entry(assignee).or_default();
+            if !pinned_field.is_empty() && event.fields.contains_key(pinned_field) {
+                pinned.push((start, event.uuid, end, event.zone));
+            } else {
+                movable.push((start, event.uuid, end, event.zone));
+            }
+        }
+
+        for (pinned, mut movable) in groups.into_values() {
+            movable.sort_by_key(|(start, uuid, _, _)| (*start, *uuid));
+            let busy = BusyTime::new(pinned.iter().map(|(start, _, end, _)| (*start, *end)));
+            let mut moves = Vec::new();
+            let mut deferred = Vec::new();
+            let mut cursor = DateTimeType::MIN_UTC; // end of last placed Event
+            for item in movable {
+                let (start, _, end, _) = item;
+                let new_start = busy.free_slot(start.max(cursor), end - start);
+                if new_start > start && horizon.is_some_and(|horizon| new_start + (end - start) > horizon) {
+                    deferred.push(item);
+                    continue;
+                }
+                cursor = new_start + (end - start);
+                moves.push((item, new_start));
+            }
+
+            // end of ET: after everything of this assignee
+            let mut cursor = busy.end().into_iter().fold(cursor, DateTimeType::max);
+            for item in deferred {
+                let (start, _, end, _) = item;
+                let new_start = busy.free_slot(cursor, end - start);
+                cursor = new_start + (end - start);
+                moves.push((item, new_start));
+            }
+
+            for ((start, uuid, end, event_zone), new_start) in moves {
+                if new_start == start {
+                    continue;
+                }
+                let stored = |t: DateTimeType| match event_zone {
+                    EventZone::Floating => EventZone::Tz(zone).to_local(t).and_utc(),
+                    EventZone::Utc | EventZone::Tz(_) => t,
+                };
+                et.set_times(uuid, stored(new_start), stored(new_start + (end - start))).map_err(|e| format!("Compact: {}", e))?;
+            }
+        }
+        Ok(et)
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::EventSpec;
+    use crate::core::testing::{at, times, TestInputs};
+
+    fn add(et: &mut EventTable, assignee: &str, start: DateTimeType, end: DateTimeType, pinned: bool)->Uuid {
+        let mut spec = EventSpec::new("Event".to_string(), start, end);
+        spec.fields.insert("assignee".to_string(), assignee.to_string());
+        if pinned {
+            spec.fields.insert("pinned".to_string(), String::new());
+        }
+        et.add_spec(spec).unwrap()
+    }
+
+    fn run(et: EventTable, horizon: Option<DateTimeType>, zone: Tz)->EventTable {
+        let inputs = TestInputs::new()
+            .with("assignee_field", "assignee".to_string())
+            .with("pinned_field", "pinned".to_string())
+            .with("horizon", horizon)
+            .with("zone", zone);
+        Compact.run(et, inputs.get()).unwrap()
+    }
+
+    #[test]
+    fn overlaps_are_pushed_around_pinned_events() {
+        let mut et = EventTable::new();
+        let first = add(&mut et, "alice", at(4, 9, 0), at(4, 10, 0), false);
+        let second = add(&mut et, "alice", at(4, 9, 30), at(4, 10, 30), false);
+        let third = add(&mut et, "alice", at(4, 10, 0), at(4, 11, 0), false);
+        let pinned = add(&mut et, "alice", at(4, 10, 45), at(4, 11, 15), true);
+        let last = add(&mut et, "alice", at(4, 16, 0), at(4, 17, 0), false);
+        let other = add(&mut et, "bob", at(4, 9, 30), at(4, 10, 30), false);
+
+        let compacted = run(et.clone(), None, chrono_tz::UTC);
+        assert_eq!(times(&compacted, first), (at(4, 9, 0), at(4, 10, 0)));
+        assert_eq!(times(&compacted, second), (at(4, 11, 15), at(4, 12, 15)));
+        assert_eq!(times(&compacted, third), (at(4, 12, 15), at(4, 13, 15)));
+        assert_eq!(times(&compacted, pinned), (at(4, 10, 45), at(4, 11, 15)));
+        assert_eq!(times(&compacted, last), (at(4, 16, 0), at(4, 17, 0)));
+        assert_eq!(times(&compacted, other), (at(4, 9, 30), at(4, 10, 30)));
+
+        // third would end after horizon, so it goes after last Event of alice instead of pushing it
+        let deferred = run(et, Some(at(4, 13, 0)), chrono_tz::UTC);
+        assert_eq!(times(&deferred, second), (at(4, 11, 15), at(4, 12, 15)));
+        assert_eq!(times(&deferred, third), (at(4, 17, 0), at(4, 18, 0)));
+        assert_eq!(times(&deferred, last), (at(4, 16, 0), at(4, 17, 0)));
+    }
+
+    #[test]
+    fn floating_events_use_zone() {
+        // 07:00-08:00 UTC is 09:00-10:00 in Berlin, Floating Event is at 09:30-10:30 wall-clock time
+        let mut et = EventTable::new();
+        let fixed = add(&mut et, "alice", at(4, 7, 0), at(4, 8, 0), true);
+        let mut spec = EventSpec::new("Floating".to_string(), at(4, 9, 30), at(4, 10, 30));
+        spec.zone = EventZone::Floating;
+        spec.fields.insert("assignee".to_string(), "alice".to_string());
+        let floating = et.add_spec(spec).unwrap();
+
+        let compacted = run(et, None, chrono_tz::Europe::Berlin);
+        assert_eq!(times(&compacted, fixed), (at(4, 7, 0), at(4, 8, 0)));
+        assert_eq!(times(&compacted, floating), (at(4, 10, 0), at(4, 11, 0)));
+    }
+}
@@ -0,0 +1,365 @@
+//Responsible for CSV (spreadsheet) files: import Extension and export Render with user-defined column layout
+
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::sync::LazyLock;
+
+use chrono::{Duration, NaiveDate, NaiveDateTime, NaiveTime};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::core::{get_input, DateTimeType, Event, EventSpec, EventTable, EventZone, Extension, IOListOfTypes, IOOutputType, IOType, R_E_AClass, R_E_AGeneric, Render};
+
+/// What CSV column contains
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CsvColumn {
+    Name,
+    Start,
+    End,
+    Uuid,
+    /// User-defined field of Event (see Event.fields)
+    Field(String),
+}
+
+/// Layout of CSV file, same for import and export
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CsvMapping {
+    /// (header of column, what column contains). Columns of file that are not listed are ignored on import
+    pub columns: Vec<(String, CsvColumn)>,
+    /// chrono format of times, e.g. "%Y-%m-%d %H:%M". If it has no time part, Events are all-day
+    pub date_format: String,
+    /// chrono format of dates of all-day Events, e.g. "%Y-%m-%d". On import cells in this format are dates too
+    pub all_day_format: String,
+    /// Zone of times in file
+    pub zone: EventZone,
+    /// If true: end date of all-day Event is its last day (as people usually write it), not day after it
+    pub end_date_inclusive: bool,
+    pub delimiter: u8,
+}
+
+impl Default for CsvMapping {
+    fn default()->Self {
+        Self {
+            columns: vec![
+                ("name".into(), CsvColumn::Name),
+                ("start".into(), CsvColumn::Start),
+                ("end".into(), CsvColumn::End),
+            ],
+            date_format: "%Y-%m-%d %H:%M".into(),
+            all_day_format: "%Y-%m-%d".into(),
+            zone: EventZone::Utc,
+            end_date_inclusive: false,
+            delimiter: b',',
+        }
+    }
+}
+
+/// Time read from CSV cell
+enum CsvTime {
+    Date(NaiveDate),
+    DateTime(NaiveDateTime),
+}
+
+impl CsvMapping {
+    fn parse_time(&self, value: &str)->Result<CsvTime, String> {
+        let value = value.trim();
+        NaiveDateTime::parse_from_str(value, &self.date_format).map(CsvTime::DateTime)
+            .or_else(|_| NaiveDate::parse_from_str(value, &self.date_format).map(CsvTime::Date))
+            .or_else(|e| NaiveDate::parse_from_str(value, &self.all_day_format).map(CsvTime::Date).map_err(|_| e))
+            .map_err(|e| format!("Wrong time {} for format {}: {}", value, self.date_format, e))
+    }
+
+    /// Event time t formatted as wall-clock time in zone of file (Floating Events keep their wall-clock time)
+    fn format_time(&self, event: &Event, t: DateTimeType)->Result<String, String> {
+        let local = match (event.zone, self.zone) {
+            (EventZone::Floating, _) => event.zone.to_local(t),
+            (_, zone) => zone.to_local(t),
+        };
+        // wrong format is an error, not a panic as with to_string()
+        let mut formatted = String::new();
+        write!(formatted, "{}", local.format(&self.date_format))
+            .map_err(|_| format!("Wrong date format {}", self.date_format))?;
+        Ok(formatted)
+    }
+
+    /// Date of all-day Event formatted with all_day_format
+    fn format_date(&self, date: NaiveDate)->Result<String, String> {
+        let mut formatted = String::new();
+        write!(formatted, "{}", date.format(&self.all_day_format))
+            .map_err(|_| format!("Wrong all-day date format {}", self.all_day_format))?;
+        Ok(formatted)
+    }
+}
+
+
+
+// ==========IMPORT EXTENSION==========
+
+/// Reads Events from CSV file with header row
+/// Inputs: "path": String, "mapping": CsvMapping
+/// Output: "events": Vec<EventSpec>
+pub struct CsvImport;
+
+impl R_E_AGeneric for CsvImport {
+    fn get_name(&self)->&'static str {
+        "CsvImport"
+    }
+    fn get_class(&self)->&'static R_E_AClass {
+        &R_E_AClass::Extension
+    }
+    fn get_inputs(&self)->&'static IOListOfTypes {
+        static INPUTS: LazyLock<IOListOfTypes> = LazyLock::new(|| HashMap::from([
+            ("path".to_string(), TypeId::of::<String>()),
+            ("mapping".to_string(), TypeId::of::<CsvMapping>()),
+        ]));
+        &INPUTS
+    }
+}
+
+impl Extension for CsvImport {
+    fn get_outputs(&self)->&'static IOListOfTypes {
+        static OUTPUTS: LazyLock<IOListOfTypes> = LazyLock::new(|| HashMap::from([
+            ("events".to_string(), TypeId::of::<Vec<EventSpec>>()),
+        ]));
+        &OUTPUTS
+    }
+
+    fn run(&self,
+        inputs: IOType
+    )->Result<IOOutputType, String> {
+        let path = get_input::<String>(&inputs, "path")?;
+        let mapping = get_input::<CsvMapping>(&inputs, "mapping")?;
+        let text = std::fs::read_to_string(path).map_err(|e| format!("CsvImport: can not read {}: {}", path, e))?;
+        let events = parse_csv(&text, mapping).map_err(|e| format!("CsvImport: {}: {}", path, e))?;
+        Ok(HashMap::from([
+            ("events".to_string(), Box::new(events) as Box<dyn Any+Send+Sync>),
+        ]))
+    }
+}
+
+/// Rows of CSV text (with header row) as EventSpecs
+pub fn parse_csv(text: &str,
+    mapping: &CsvMapping
+)->Result<Vec<EventSpec>, String> {
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(mapping.delimiter)
+        .flexible(true)
+        .from_reader(text.as_bytes());
+    let headers = reader.headers().map_err(|e| format!("Wrong header row: {}", e))?.clone();
+
+    // (position in row, what it contains)
+    let mut columns: Vec<(usize, &CsvColumn)> = Vec::new();
+    for (header, column) in &mapping.columns {
+        let position = headers.iter().position(|h| h.trim() == header)
+            .ok_or(format!("Column {} not found in header row", header))?;
+        columns.push((position, column));
+    }
+    for required in [CsvColumn::Start, CsvColumn::End] {
+        if !columns.iter().any(|(_, c)| **c == required) {
+            return Err(format!("Mapping has no column for {:?}", required));
+        }
+    }
+
+    let mut events = Vec::new();
+    for (row, record) in reader.records().enumerate() {
+        // row 1 is header
+        let row = row + 2;
+        let record = record.map_err(|e| format!("Row {}: {}", row, e))?;
+        let cell = |position: usize| record.get(position).unwrap_or_default();
+
+        let mut name = String::new();
+        let (mut start, mut end) = (None, None);
+        let mut uuid = None;
+        let mut fields = Vec::new();
+        for (position, column) in &columns {
+            let value = cell(*position);
+            match column {
+                CsvColumn::Name => name = value.to_string(),
+                CsvColumn::Start => start = Some(mapping.parse_time(value).map_err(|e| format!("Row {}: {}", row, e))?),
+                CsvColumn::End => end = Some(mapping.parse_time(value).map_err(|e| format!("Row {}: {}", row, e))?),
+                CsvColumn::Uuid => uuid = Some(Uuid::parse_str(value.trim()).map_err(|e| format!("Row {}: wrong UUID {}: {}", row, value, e))?),
+                CsvColumn::Field(key) if !value.is_empty() => fields.push((key.clone(), value.to_string())),
+                CsvColumn::Field(_) => {}
+            }
+        }
+
+        let mut spec = match (start, end) {
+            (Some(CsvTime::Date(first)), Some(CsvTime::Date(last))) => {
+                let after_last = if mapping.end_date_inclusive { last + Duration::days(1) } else { last };
+                if after_last <= first {
+                    return Err(format!("Row {}: all-day Event ends before it starts", row));
+                }
+                EventSpec::new_all_day(name, first, after_last)
+            }
+            (Some(start), Some(end)) => {
+                let start = mapping.zone.resolve_local(datetime_of(start));
+                let end = mapping.zone.resolve_local(datetime_of(end));
+                if start > end {
+                    return Err(format!("Row {}: Event ends before it starts", row));
+                }
+                let mut spec = EventSpec::new(name, start, end);
+                spec.zone = mapping.zone;
+                spec
+            }
+            _ => return Err(format!("Row {}: start or end is missing", row)),
+        };
+        spec.uuid = uuid;
+        spec.fields.extend(fields);
+        events.push(spec);
+    }
+    Ok(events)
+}
+
+/// Date is midnight
+fn datetime_of(time: CsvTime)->NaiveDateTime {
+    match time {
+        CsvTime::Date(date) => date.and_time(NaiveTime::MIN),
+        CsvTime::DateTime(datetime) => datetime,
+    }
+}
+
+
+
+// ==========EXPORT RENDER==========
+
+/// Writes ET to CSV file with header row, Events are ordered by start
+/// Inputs: "path": String, "mapping": CsvMapping
+pub struct CsvExport;
+
+impl R_E_AGeneric for CsvExport {
+    fn get_name(&self)->&'static str {
+        "CsvExport"
+    }
+    fn get_class(&self)->&'static R_E_AClass {
+        &R_E_AClass::Render
+    }
+    fn get_inputs(&self)->&'static IOListOfTypes {
+        static INPUTS: LazyLock<IOListOfTypes> = LazyLock::new(|| HashMap::from([
+            ("path".to_string(), TypeId::of::<String>()),
+            ("mapping".to_string(), TypeId::of::<CsvMapping>()),
+        ]));
+        &INPUTS
+    }
+}
+
+impl Render for CsvExport {
+    fn run(&self,
+        et: EventTable,
+        inputs: IOType
+    )->Result<(), String> {
+        let path = get_input::<String>(&inputs, "path")?;
+        let mapping = get_input::<CsvMapping>(&inputs, "mapping")?;
+        let text = to_csv(&et, mapping)?;
+        std::fs::write(path, text).map_err(|e| format!("CsvExport: can not write {}: {}", path, e))
+    }
+}
+
+/// Whole ET as CSV text with header row
+pub fn to_csv(et: &EventTable,
+    mapping: &CsvMapping
+)->Result<String, String> {
+    let mut writer = csv::WriterBuilder::new()
+        .delimiter(mapping.delimiter)
+        .from_writer(Vec::new());
+    writer.write_record(mapping.columns.iter().map(|(header, _)| header))
+        .map_err(|e| format!("CSV header: {}", e))?;
+
+    let mut events: Vec<&Event> = et.get_events().collect();
+    events.sort_by_key(|e| (e.start, e.end));
+    for event in events {
+        // all-day Events are written as dates, so they are read back as all-day
+        let (start, end) = match event.all_day {
+            Some((first, after_last)) => {
+                let last = if mapping.end_date_inclusive { after_last - Duration::days(1) } else { after_last };
+                (mapping.format_date(first)?, mapping.format_date(last)?)
+            }
+            None => (mapping.format_time(event, event.start)?, mapping.format_time(event, event.end)?),
+        };
+        let row = mapping.columns.iter()
+            .map(|(_, column)| Ok(match column {
+                CsvColumn::Name => event.get_name().to_string(),
+                CsvColumn::Start => start.clone(),
+                CsvColumn::End => end.clone(),
+                CsvColumn::Uuid => event.uuid.to_string(),
+                CsvColumn::Field(key) => event.fields.get(key).cloned().unwrap_or_default(),
+            }))
+            .collect::<Result<Vec<String>, String>>()?;
+        writer.write_record(&row).map_err(|e| format!("CSV row of Event {}: {}", event.uuid, e))?;
+    }
+
+    let bytes = writer.into_inner().map_err(|e| format!("CSV: {}", e))?;
+    String::from_utf8(bytes).map_err(|e| format!("CSV: {}", e))
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+    use chrono_tz::Europe::Berlin;
+
+    fn date(d: u32)->NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 5, d).unwrap()
+    }
+
+    fn mapping()->CsvMapping {
+        CsvMapping {
+            columns: vec![
+                ("Title".into(), CsvColumn::Name),
+                ("From".into(), CsvColumn::Start),
+                ("To".into(), CsvColumn::End),
+                ("Id".into(), CsvColumn::Uuid),
+                ("Who".into(), CsvColumn::Field("assignee".into())),
+            ],
+            date_format: "%d.%m.%Y %H:%M".into(),
+            all_day_format: "%d.%m.%Y".into(),
+            zone: EventZone::Tz(Berlin),
+            end_date_inclusive: true,
+            delimiter: b';',
+        }
+    }
+
+    #[test]
+    fn columns_are_mapped_by_header() {
+        let text = "Ignored;Who;To;From;Title\n\
+                    x;Anna;04.05.2026 10:30;04.05.2026 09:00;Meeting\n\
+                    x;;05.05.2026;04.05.2026;Trip\n";
+        let mut mapping = mapping();
+        mapping.columns.retain(|(_, c)| *c != CsvColumn::Uuid);
+        let events = parse_csv(text, &mapping).unwrap();
+        assert_eq!((events[0].name.as_str(), events[0].zone), ("Meeting", EventZone::Tz(Berlin)));
+        assert_eq!((events[0].start, events[0].end), (Utc.with_ymd_and_hms(2026, 5, 4, 7, 0, 0).unwrap(), Utc.with_ymd_and_hms(2026, 5, 4, 8, 30, 0).unwrap()));
+        assert_eq!(events[0].fields["assignee"], "Anna");
+        // inclusive end date, empty field is not set
+        assert_eq!((events[1].all_day, events[1].fields.get("assignee")), (Some((date(4), date(6))), None));
+
+        assert!(parse_csv("Title;From\nA;04.05.2026 09:00\n", &mapping).unwrap_err().contains("Column To"));
+        assert!(parse_csv(text.replace("10:30", "08:30").as_str(), &mapping).unwrap_err().contains("Row 2"));
+        assert!(parse_csv("Who;To;From;Title\n;04.05.2026;xx;A\n", &mapping).unwrap_err().contains("Row 2"));
+    }
+
+    #[test]
+    fn export_and_import_roundtrip() {
+        let mut et = EventTable::new();
+        let mut spec = EventSpec::new("Meeting, \"big\"".to_string(), Utc.with_ymd_and_hms(2026, 5, 4, 7, 0, 0).unwrap(), Utc.with_ymd_and_hms(2026, 5, 4, 8, 30, 0).unwrap());
+        spec.zone = EventZone::Tz(Berlin);
+        spec.fields.insert("assignee".into(), "Anna".into());
+        et.add_spec(spec).unwrap();
+        et.add_spec(EventSpec::new_all_day("Trip".to_string(), date(5), date(8))).unwrap();
+
+        let mapping = mapping();
+        let text = to_csv(&et, &mapping).unwrap();
+        // all-day Event has dates only, last day inclusive
+        assert!(text.contains("05.05.2026;07.05.2026"));
+        let imported = parse_csv(&text, &mapping).unwrap();
+        assert_eq!(imported.len(), 2);
+        for spec in imported {
+            let event = et.get(spec.uuid.unwrap()).unwrap();
+            assert_eq!((spec.name.as_str(), spec.start, spec.end, spec.all_day), (event.get_name(), event.start, event.end, event.all_day));
+            assert_eq!(spec.fields, event.fields);
+        }
+    }
+}
@@ -0,0 +1,161 @@
+//Responsible for ET snapshot files: whole ET is saved as JSON or compact binary and loaded back with same UUIDs (e.g. as cache of slow pipeline part)
+
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+use crate::core::{get_input, EventSpec, EventTable, Extension, IOListOfTypes, IOOutputType, IOType, R_E_AClass, R_E_AGeneric, Render};
+
+/// Format of snapshot by extension of path: ".json" → JSON (readable), ".bin" → binary (compact)
+enum Format {
+    Json,
+    Binary,
+}
+
+fn format_of(path: &str)->Result<Format, String> {
+    match std::path::Path::new(path).extension().and_then(|e| e.to_str()) {
+        Some("json") => Ok(Format::Json),
+        Some("bin") => Ok(Format::Binary),
+        _ => Err(format!("{}: unknown snapshot format (expected .json or .bin)", path)),
+    }
+}
+
+
+
+// ==========EXPORT RENDER==========
+
+/// Writes whole ET to snapshot file
+/// Input: "path": String (.json or .bin)
+pub struct EtExport;
+
+impl R_E_AGeneric for EtExport {
+    fn get_name(&self)->&'static str {
+        "EtExport"
+    }
+    fn get_class(&self)->&'static R_E_AClass {
+        &R_E_AClass::Render
+    }
+    fn get_inputs(&self)->&'static IOListOfTypes {
+        static INPUTS: LazyLock<IOListOfTypes> = LazyLock::new(|| HashMap::from([
+            ("path".to_string(), TypeId::of::<String>()),
+        ]));
+        &INPUTS
+    }
+}
+
+impl Render for EtExport {
+    fn run(&self,
+        et: EventTable,
+        inputs: IOType
+    )->Result<(), String> {
+        let path = get_input::<String>(&inputs, "path")?;
+        let bytes = match format_of(path).map_err(|e| format!("EtExport: {}", e))? {
+            Format::Json => et.to_json()?.into_bytes(),
+            Format::Binary => et.to_binary()?,
+        };
+        std::fs::write(path, bytes).map_err(|e| format!("EtExport: can not write {}: {}", path, e))
+    }
+}
+
+
+
+// ==========IMPORT EXTENSION==========
+
+/// Reads snapshot file written by EtExport. Snapshot is checked as whole ET (no duplicated UUIDs, no end < start)
+/// Input: "path": String (.json or .bin)
+/// Output: "events": Vec<EventSpec> (with UUIDs of snapshot, so Adder skips Events that are already in ET)
+pub struct EtImport;
+
+impl R_E_AGeneric for EtImport {
+    fn get_name(&self)->&'static str {
+        "EtImport"
+    }
+    fn get_class(&self)->&'static R_E_AClass {
+        &R_E_AClass::Extension
+    }
+    fn get_inputs(&self)->&'static IOListOfTypes {
+        static INPUTS: LazyLock<IOListOfTypes> = LazyLock::new(|| HashMap::from([
+            ("path".to_string(), TypeId::of::<String>()),
+        ]));
+        &INPUTS
+    }
+}
+
+impl Extension for EtImport {
+    fn get_outputs(&self)->&'static IOListOfTypes {
+        static OUTPUTS: LazyLock<IOListOfTypes> = LazyLock::new(|| HashMap::from([
+            ("events".to_string(), TypeId::of::<Vec<EventSpec>>()),
+        ]));
+        &OUTPUTS
+    }
+
+    fn run(&self,
+        inputs: IOType
+    )->Result<IOOutputType, String> {
+        let path = get_input::<String>(&inputs, "path")?;
+        let format = format_of(path).map_err(|e| format!("EtImport: {}", e))?;
+        let bytes = std::fs::read(path).map_err(|e| format!("EtImport: can not read {}: {}", path, e))?;
+        let et = match format {
+            Format::Json => EventTable::from_json(&String::from_utf8_lossy(&bytes)),
+            Format::Binary => EventTable::from_binary(&bytes),
+        }.map_err(|e| format!("EtImport: {}: {}", path, e))?;
+        let events: Vec<EventSpec> = et.get_events().map(EventSpec::from).collect();
+        Ok(HashMap::from([
+            ("events".to_string(), Box::new(events) as Box<dyn Any+Send+Sync>),
+        ]))
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{NaiveDate, TimeZone, Utc};
+    use crate::core::EventZone;
+
+    fn snapshot_et()->EventTable {
+        let mut et = EventTable::new();
+        let mut spec = EventSpec::new("Meeting".to_string(), Utc.with_ymd_and_hms(2026, 5, 4, 9, 0, 0).unwrap(), Utc.with_ymd_and_hms(2026, 5, 4, 10, 0, 0).unwrap());
+        spec.zone = EventZone::Tz(chrono_tz::Europe::Berlin);
+        spec.fields.insert("room".to_string(), "A".to_string());
+        et.add_spec(spec).unwrap();
+        et.add_spec(EventSpec::new_all_day("Holiday".to_string(), NaiveDate::from_ymd_opt(2026, 5, 1).unwrap(), NaiveDate::from_ymd_opt(2026, 5, 2).unwrap())).unwrap();
+        et
+    }
+
+    fn import(path: &str)->Result<Vec<EventSpec>, String> {
+        let path: Box<dyn Any+Send+Sync> = Box::new(path.to_string());
+        let mut outputs = EtImport.run(HashMap::from([("path".to_string(), &path)]))?;
+        Ok(*outputs.remove("events").unwrap().downcast::<Vec<EventSpec>>().unwrap())
+    }
+
+    #[test]
+    fn export_and_import_keep_events() {
+        let et = snapshot_et();
+        let expected: Vec<EventSpec> = et.get_events().map(EventSpec::from).collect();
+        for extension in ["json", "bin"] {
+            let path = std::env::temp_dir().join(format!("rea_et_file_test_{}.{}", std::process::id(), extension));
+            let path = path.to_str().unwrap().to_string();
+            let input: Box<dyn Any+Send+Sync> = Box::new(path.clone());
+            EtExport.run(et.clone(), HashMap::from([("path".to_string(), &input)])).unwrap();
+            let imported = import(&path);
+            std::fs::remove_file(&path).unwrap();
+            assert_eq!(imported.unwrap(), expected, "{}", extension);
+        }
+    }
+
+    #[test]
+    fn invalid_snapshot_is_rejected() {
+        let path = std::env::temp_dir().join(format!("rea_et_file_test_{}_invalid.json", std::process::id()));
+        let path = path.to_str().unwrap().to_string();
+        let json = snapshot_et().to_json().unwrap();
+        let event = serde_json::from_str::<serde_json::Value>(&json).unwrap()["events"][0].clone();
+        std::fs::write(&path, serde_json::json!({"events": [event.clone(), event]}).to_string()).unwrap();
+        let imported = import(&path);
+        std::fs::remove_file(&path).unwrap();
+        assert!(imported.unwrap_err().contains("duplicated"));
+
+        assert!(import("snapshot.txt").unwrap_err().contains("unknown snapshot format"));
+    }
+}
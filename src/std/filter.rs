@@ -0,0 +1,534 @@
+//Responsible for Filter App and its predicate language
+// Events can not be deleted, so Filter hides non-matching Events: they get field HIDDEN_FIELD with name of stream they are routed to.
+// Downstream stages skip hidden Events (see is_hidden()), Renders may show streams separately.
+//
+// Syntax (keywords are case-insensitive, times are wall-clock times in "zone" input of Filter):
+//     predicate   := or
+//     or          := and ("or" and)*
+//     and         := unary ("and" unary)*
+//     unary       := "not" unary | "(" predicate ")" | condition
+//     condition   := ("start" | "end") op time             e.g. start >= 2026-01-05T08:00, end < 2026-02-01
+//                  | "duration" op duration                e.g. duration >= 1h30m (units: d, h, m)
+//                  | "overlaps" time "to" time             Event overlaps [time, time)
+//                  | "weekday" "in" "[" weekday, ... "]"   weekday of start: mon, tue, ...
+//                  | "uuid" "in" "[" uuid, ... "]"
+//                  | "field.<key>" ("=" | "!=" | "contains") "text"
+//                  | "has" "field.<key>"
+//                  | "all_day" | "recurring" | "hidden"
+//     op          := "=" | "!=" | "<" | "<=" | ">" | ">="
+//     time        := YYYY-MM-DD | YYYY-MM-DDTHH:MM[:SS]
+// Names of Events can not be used (Apps MUST NOT read names).
+
+use std::any::TypeId;
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+use std::sync::LazyLock;
+
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Weekday};
+use chrono_tz::Tz;
+use uuid::Uuid;
+
+use crate::core::{get_input, App, Event, EventTable, IOListOfTypes, IOType, R_E_AClass, R_E_AGeneric};
+
+/// Field of hidden Event, value is name of stream Event was routed to
+pub const HIDDEN_FIELD: &str = "hidden";
+
+/// true if Event was hidden by some Filter, such Events SHOULD be skipped by downstream Apps
+pub fn is_hidden(event: &Event)->bool {
+    event.fields.contains_key(HIDDEN_FIELD)
+}
+
+/// Name of stream hidden Event was routed to
+pub fn stream_of(event: &Event)->Option<&str> {
+    event.fields.get(HIDDEN_FIELD).map(String::as_str)
+}
+
+
+
+// ==========FILTER APP==========
+
+/// Hides Events that do not match predicate (already hidden Events stay in their stream)
+/// Inputs: "predicate": String (see syntax above), "stream": String (name of stream for non-matching Events), "zone": Tz (zone of times in predicate)
+/// Output: ET where non-matching Events have field HIDDEN_FIELD=stream
+pub struct Filter;
+
+impl R_E_AGeneric for Filter {
+    fn get_name(&self)->&'static str {
+        "Filter"
+    }
+    fn get_class(&self)->&'static R_E_AClass {
+        &R_E_AClass::App
+    }
+    fn get_inputs(&self)->&'static IOListOfTypes {
+        static INPUTS: LazyLock<IOListOfTypes> = LazyLock::new(|| HashMap::from([
+            ("predicate".to_string(), TypeId::of::<String>()),
+            ("stream".to_string(), TypeId::of::<String>()),
+            ("zone".to_string(), TypeId::of::<Tz>()),
+        ]));
+        &INPUTS
+    }
+}
+
+impl App for Filter {
+    fn run(&self,
+        mut et: EventTable,
+        inputs: IOType
+    )->Result<EventTable, String> {
+        let predicate: Predicate = get_input::<String>(&inputs, "predicate")?.parse()
+            .map_err(|e| format!("Filter: wrong predicate: {}", e))?;
+        let stream = get_input::<String>(&inputs, "stream")?;
+        let zone = *get_input::<Tz>(&inputs, "zone")?;
+        if stream.is_empty() {
+            return Err("Filter: stream name is empty".to_string());
+        }
+
+        let rejected: Vec<Uuid> = et.get_events()
+            .filter(|event| !is_hidden(event) && !predicate.matches(event, zone))
+            .map(|event| event.uuid)
+            .collect();
+        for uuid in rejected {
+            et.set_field(uuid, HIDDEN_FIELD, Some(stream.clone()))?;
+        }
+        Ok(et)
+    }
+}
+
+
+
+// ==========PREDICATE==========
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CompareOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl CompareOp {
+    fn apply<T: Ord>(self, a: T, b: T)->bool {
+        match self {
+            Self::Eq => a == b,
+            Self::Ne => a != b,
+            Self::Lt => a < b,
+            Self::Le => a <= b,
+            Self::Gt => a > b,
+            Self::Ge => a >= b,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FieldOp {
+    Eq,
+    Ne,
+    Contains,
+}
+
+/// Parsed predicate, see syntax at the top of file
+#[derive(Clone, Debug, PartialEq)]
+pub enum Predicate {
+    And(Box<Predicate>, Box<Predicate>),
+    Or(Box<Predicate>, Box<Predicate>),
+    Not(Box<Predicate>),
+    Start(CompareOp, NaiveDateTime),
+    End(CompareOp, NaiveDateTime),
+    Duration(CompareOp, Duration),
+    /// [from, to)
+    Overlaps(NaiveDateTime, NaiveDateTime),
+    Weekday(Vec<Weekday>),
+    Uuid(HashSet<Uuid>),
+    Field(String, FieldOp, String),
+    HasField(String),
+    AllDay,
+    Recurring,
+    Hidden,
+}
+
+impl Predicate {
+    /// Times of Event are compared as wall-clock times in zone (Floating Events as they are)
+    pub fn matches(&self, event: &Event, zone: Tz)->bool {
+        match self {
+            Self::And(a, b) => a.matches(event, zone) && b.matches(event, zone),
+            Self::Or(a, b) => a.matches(event, zone) || b.matches(event, zone),
+            Self::Not(a) => !a.matches(event, zone),
+            Self::Start(op, t) => op.apply(event.start_in(zone), *t),
+            Self::End(op, t) => op.apply(event.end_in(zone), *t),
+            Self::Duration(op, d) => op.apply(event.end - event.start, *d),
+            Self::Overlaps(from, to) => event.start_in(zone) < *to && *from < event.end_in(zone),
+            Self::Weekday(days) => days.contains(&event.start_in(zone).weekday()),
+            Self::Uuid(uuids) => uuids.contains(&event.uuid),
+            Self::Field(key, op, value) => {
+                let field = event.fields.get(key);
+                match op {
+                    FieldOp::Eq => field == Some(value),
+                    FieldOp::Ne => field != Some(value),
+                    FieldOp::Contains => field.is_some_and(|field| field.contains(value.as_str())),
+                }
+            }
+            Self::HasField(key) => event.fields.contains_key(key),
+            Self::AllDay => event.all_day.is_some(),
+            Self::Recurring => event.recurrence.is_some(),
+            Self::Hidden => is_hidden(event),
+        }
+    }
+}
+
+impl FromStr for Predicate {
+    type Err = String;
+
+    fn from_str(text: &str)->Result<Self, String> {
+        let mut parser = Parser { tokens: tokenize(text)?, pos: 0 };
+        let predicate = parser.or()?;
+        match parser.tokens.get(parser.pos) {
+            None => Ok(predicate),
+            Some(token) => Err(format!("Unexpected {}", token)),
+        }
+    }
+}
+
+
+
+// ==========PARSING==========
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Word(String),
+    Text(String),
+    Symbol(&'static str),
+}
+
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter)->std::fmt::Result {
+        match self {
+            Self::Word(word) => write!(f, "'{}'", word),
+            Self::Text(text) => write!(f, "\"{}\"", text),
+            Self::Symbol(symbol) => write!(f, "'{}'", symbol),
+        }
+    }
+}
+
+const SYMBOLS: [&str; 10] = ["!=", "<=", ">=", "=", "<", ">", "(", ")", "[", "]"];
+
+fn tokenize(text: &str)->Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut rest = text.trim_start();
+    while !rest.is_empty() {
+        if let Some(symbol) = SYMBOLS.iter().find(|symbol| rest.starts_with(**symbol)) {
+            tokens.push(Token::Symbol(symbol));
+            rest = &rest[symbol.len()..];
+        } else if let Some(after) = rest.strip_prefix(',') {
+            rest = after; // separators of lists are optional
+        } else if let Some(after) = rest.strip_prefix('"') {
+            let end = after.find('"').ok_or("Unclosed \"")?;
+            tokens.push(Token::Text(after[..end].to_string()));
+            rest = &after[end + 1..];
+        } else {
+            let end = rest.find(|c: char| !(c.is_alphanumeric() || "-_:.".contains(c))).unwrap_or(rest.len());
+            if end == 0 {
+                return Err(format!("Unexpected '{}'", rest.chars().next().unwrap_or_default()));
+            }
+            tokens.push(Token::Word(rest[..end].to_string()));
+            rest = &rest[end..];
+        }
+        rest = rest.trim_start();
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn next(&mut self)->Result<Token, String> {
+        let token = self.tokens.get(self.pos).cloned().ok_or("Unexpected end of predicate")?;
+        self.pos += 1;
+        Ok(token)
+    }
+
+    /// Skips keyword if it is next
+    fn keyword(&mut self, keyword: &str)->bool {
+        let found = matches!(self.tokens.get(self.pos), Some(Token::Word(word)) if word.eq_ignore_ascii_case(keyword));
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn expect(&mut self, symbol: &str)->Result<(), String> {
+        match self.next()? {
+            Token::Symbol(s) if s == symbol => Ok(()),
+            token => Err(format!("Expected '{}', got {}", symbol, token)),
+        }
+    }
+
+    fn word(&mut self)->Result<String, String> {
+        match self.next()? {
+            Token::Word(word) => Ok(word),
+            token => Err(format!("Expected word, got {}", token)),
+        }
+    }
+
+    fn or(&mut self)->Result<Predicate, String> {
+        let mut predicate = self.and()?;
+        while self.keyword("or") {
+            predicate = Predicate::Or(Box::new(predicate), Box::new(self.and()?));
+        }
+        Ok(predicate)
+    }
+
+    fn and(&mut self)->Result<Predicate, String> {
+        let mut predicate = self.unary()?;
+        while self.keyword("and") {
+            predicate = Predicate::And(Box::new(predicate), Box::new(self.unary()?));
+        }
+        Ok(predicate)
+    }
+
+    fn unary(&mut self)->Result<Predicate, String> {
+        if self.keyword("not") {
+            return Ok(Predicate::Not(Box::new(self.unary()?)));
+        }
+        if self.tokens.get(self.pos) == Some(&Token::Symbol("(")) {
+            self.pos += 1;
+            let predicate = self.or()?;
+            self.expect(")")?;
+            return Ok(predicate);
+        }
+        self.condition()
+    }
+
+    fn condition(&mut self)->Result<Predicate, String> {
+        let word = self.word()?;
+        Ok(match word.to_lowercase().as_str() {
+            "start" => Predicate::Start(self.compare_op()?, parse_time(&self.word()?)?),
+            "end" => Predicate::End(self.compare_op()?, parse_time(&self.word()?)?),
+            "duration" => Predicate::Duration(self.compare_op()?, parse_duration(&self.word()?)?),
+            "overlaps" => {
+                let from = parse_time(&self.word()?)?;
+                if !self.keyword("to") {
+                    return Err("Expected 'to' in overlaps".to_string());
+                }
+                let to = parse_time(&self.word()?)?;
+                if from >= to {
+                    return Err(format!("Empty range {} to {}", from, to));
+                }
+                Predicate::Overlaps(from, to)
+            }
+            "weekday" => Predicate::Weekday(self.list()?.iter()
+                .map(|day| day.parse::<Weekday>().map_err(|_| format!("Wrong weekday {}", day)))
+                .collect::<Result<_, _>>()?),
+            "uuid" => Predicate::Uuid(self.list()?.iter()
+                .map(|uuid| Uuid::parse_str(uuid).map_err(|_| format!("Wrong UUID {}", uuid)))
+                .collect::<Result<_, _>>()?),
+            "has" => Predicate::HasField(field_key(&self.word()?)?),
+            "all_day" => Predicate::AllDay,
+            "recurring" => Predicate::Recurring,
+            "hidden" => Predicate::Hidden,
+            _ if word.starts_with("field.") => {
+                let key = field_key(&word)?;
+                let op = match self.next()? {
+                    Token::Symbol("=") => FieldOp::Eq,
+                    Token::Symbol("!=") => FieldOp::Ne,
+                    Token::Word(w) if w.eq_ignore_ascii_case("contains") => FieldOp::Contains,
+                    token => return Err(format!("Expected '=', '!=' or 'contains' after {}, got {}", word, token)),
+                };
+                match self.next()? {
+                    Token::Text(value) => Predicate::Field(key, op, value),
+                    token => return Err(format!("Expected \"text\" after {}, got {}", word, token)),
+                }
+            }
+            "name" => return Err("Names of Events can not be used, use fields".to_string()),
+            _ => return Err(format!("Unknown condition '{}'", word)),
+        })
+    }
+
+    fn compare_op(&mut self)->Result<CompareOp, String> {
+        Ok(match self.next()? {
+            Token::Symbol("=") => CompareOp::Eq,
+            Token::Symbol("!=") => CompareOp::Ne,
+            Token::Symbol("<") => CompareOp::Lt,
+            Token::Symbol("<=") => CompareOp::Le,
+            Token::Symbol(">") => CompareOp::Gt,
+            Token::Symbol(">=") => CompareOp::Ge,
+            token => return Err(format!("Expected comparison, got {}", token)),
+        })
+    }
+
+    /// "in" "[" word, ... "]"
+    fn list(&mut self)->Result<Vec<String>, String> {
+        if !self.keyword("in") {
+            return Err("Expected 'in'".to_string());
+        }
+        self.expect("[")?;
+        let mut items = Vec::new();
+        loop {
+            match self.next()? {
+                Token::Symbol("]") => return Ok(items),
+                Token::Word(word) | Token::Text(word) => items.push(word),
+                token => return Err(format!("Unexpected {} in list", token)),
+            }
+        }
+    }
+}
+
+fn field_key(word: &str)->Result<String, String> {
+    match word.strip_prefix("field.") {
+        Some(key) if !key.is_empty() => Ok(key.to_string()),
+        _ => Err(format!("Expected field.<key>, got '{}'", word)),
+    }
+}
+
+/// YYYY-MM-DD (midnight) or YYYY-MM-DDTHH:MM[:SS]
+fn parse_time(text: &str)->Result<NaiveDateTime, String> {
+    NaiveDateTime::parse_from_str(text, "%Y-%m-%dT%H:%M")
+        .or_else(|_| NaiveDateTime::parse_from_str(text, "%Y-%m-%dT%H:%M:%S"))
+        .or_else(|_| NaiveDate::parse_from_str(text, "%Y-%m-%d").map(|date| date.and_time(NaiveTime::MIN)))
+        .map_err(|_| format!("Wrong time {}, expected YYYY-MM-DD or YYYY-MM-DDTHH:MM", text))
+}
+
+/// e.g. 2d, 1h30m, 45m
+fn parse_duration(text: &str)->Result<Duration, String> {
+    let wrong = || format!("Wrong duration {}, expected e.g. 1h30m", text);
+    let mut total = Duration::zero();
+    let mut number = String::new();
+    for c in text.chars() {
+        if c.is_ascii_digit() {
+            number.push(c);
+            continue;
+        }
+        let n: i64 = number.parse().map_err(|_| wrong())?;
+        total += match c.to_ascii_lowercase() {
+            'd' => Duration::days(n),
+            'h' => Duration::hours(n),
+            'm' => Duration::minutes(n),
+            _ => return Err(wrong()),
+        };
+        number.clear();
+    }
+    if !number.is_empty() || text.is_empty() {
+        return Err(wrong());
+    }
+    Ok(total)
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::EventSpec;
+    use crate::core::testing::{at, TestInputs};
+    use crate::recurrence::{Frequency, Recurrence};
+
+    fn time(text: &str)->NaiveDateTime {
+        parse_time(text).unwrap()
+    }
+
+    #[test]
+    fn predicates_are_parsed_with_precedence() {
+        let start = Predicate::Start(CompareOp::Ge, time("2026-01-05T08:00"));
+        let end = Predicate::End(CompareOp::Lt, time("2026-02-01"));
+        assert_eq!("start >= 2026-01-05T08:00 or end < 2026-02-01 and all_day".parse::<Predicate>().unwrap(),
+            Predicate::Or(Box::new(start.clone()), Box::new(Predicate::And(Box::new(end.clone()), Box::new(Predicate::AllDay)))));
+        assert_eq!("(start >= 2026-01-05T08:00 OR end < 2026-02-01) AND NOT not all_day".parse::<Predicate>().unwrap(),
+            Predicate::And(Box::new(Predicate::Or(Box::new(start), Box::new(end))),
+                Box::new(Predicate::Not(Box::new(Predicate::Not(Box::new(Predicate::AllDay)))))));
+
+        assert_eq!("duration >= 1h30m".parse::<Predicate>().unwrap(), Predicate::Duration(CompareOp::Ge, Duration::minutes(90)));
+        assert_eq!("weekday in [mon, Fri sat]".parse::<Predicate>().unwrap(), Predicate::Weekday(vec![Weekday::Mon, Weekday::Fri, Weekday::Sat]));
+        assert_eq!("field.room contains \"A or B\"".parse::<Predicate>().unwrap(),
+            Predicate::Field("room".to_string(), FieldOp::Contains, "A or B".to_string()));
+        assert_eq!("has field.room".parse::<Predicate>().unwrap(), Predicate::HasField("room".to_string()));
+        assert_eq!("overlaps 2026-05-04 to 2026-05-05T12:30:15".parse::<Predicate>().unwrap(),
+            Predicate::Overlaps(time("2026-05-04"), time("2026-05-05T12:30:15")));
+        let uuid = Uuid::new_v4();
+        assert_eq!(format!("uuid in [\"{}\"]", uuid).parse::<Predicate>().unwrap(), Predicate::Uuid(HashSet::from([uuid])));
+    }
+
+    #[test]
+    fn wrong_predicates_are_rejected() {
+        for (text, error) in [
+            ("", "Unexpected end"),
+            ("start >= 2026-01-05 end < 2026-02-01", "Unexpected 'end'"),
+            ("(all_day", "Unexpected end"),
+            ("(all_day recurring", "Expected ')'"),
+            ("start 2026-01-05", "Expected comparison"),
+            ("start >= 05.01.2026", "Wrong time"),
+            ("duration > 90", "Wrong duration"),
+            ("duration > 1w", "Wrong duration"),
+            ("overlaps 2026-05-05 to 2026-05-04", "Empty range"),
+            ("overlaps 2026-05-04 2026-05-05", "Expected 'to'"),
+            ("weekday in [mon someday]", "Wrong weekday"),
+            ("weekday [mon]", "Expected 'in'"),
+            ("uuid in [1234]", "Wrong UUID"),
+            ("field.room = A", "Expected \"text\""),
+            ("field.room < \"A\"", "Expected '=', '!=' or 'contains'"),
+            ("field. = \"A\"", "Expected field.<key>"),
+            ("has room", "Expected field.<key>"),
+            ("field.room = \"A", "Unclosed"),
+            ("name = \"Standup\"", "Names of Events can not be used"),
+            ("busy", "Unknown condition"),
+            ("all_day & recurring", "Unexpected '&'"),
+        ] {
+            let result = text.parse::<Predicate>();
+            assert!(result.as_ref().is_err_and(|e| e.contains(error)), "{}: {:?}", text, result);
+        }
+    }
+
+    #[test]
+    fn filter_hides_non_matching_events() {
+        let mut et = EventTable::new();
+        // 07:00 UTC is 09:00 in Berlin
+        let mut morning = EventSpec::new("Morning".to_string(), at(4, 7, 0), at(4, 8, 0));
+        morning.fields.insert("room".to_string(), "A1".to_string());
+        let morning = et.add_spec(morning).unwrap();
+        let mut long = EventSpec::new("Long".to_string(), at(5, 12, 0), at(5, 15, 0));
+        long.fields.insert("room".to_string(), "B".to_string());
+        let long = et.add_spec(long).unwrap();
+        let mut daily = EventSpec::new("Daily".to_string(), at(9, 7, 0), at(9, 8, 0));
+        daily.recurrence = Some(Recurrence::new(Frequency::Daily));
+        let daily = et.add_spec(daily).unwrap();
+        let mut hidden = EventSpec::new("Hidden".to_string(), at(4, 7, 0), at(4, 8, 0));
+        hidden.fields.insert(HIDDEN_FIELD.to_string(), "old".to_string());
+        let hidden = et.add_spec(hidden).unwrap();
+
+        let run = |et: EventTable, predicate: &str| {
+            let inputs = TestInputs::new()
+                .with("predicate", predicate.to_string())
+                .with("stream", "rest".to_string())
+                .with("zone", chrono_tz::Europe::Berlin);
+            Filter.run(et, inputs.get())
+        };
+        let visible = |et: &EventTable| {
+            let mut uuids: Vec<Uuid> = et.get_events().filter(|e| !is_hidden(e)).map(|e| e.uuid).collect();
+            uuids.sort();
+            uuids
+        };
+        let sorted = |mut uuids: Vec<Uuid>| {
+            uuids.sort();
+            uuids
+        };
+
+        for (predicate, expected) in [
+            ("start = 2026-05-04T09:00", vec![morning]),
+            ("weekday in [mon] and field.room contains \"A\"", vec![morning]),
+            ("duration > 2h or recurring", vec![long, daily]),
+            ("overlaps 2026-05-05T16:59 to 2026-05-06", vec![long]),
+            ("not has field.room", vec![daily]),
+            ("field.room != \"B\"", vec![morning, daily]),
+            ("hidden", vec![]),
+        ] {
+            let et = run(et.clone(), predicate).unwrap();
+            assert_eq!(visible(&et), sorted(expected), "{}", predicate);
+            assert_eq!(stream_of(et.get(hidden).unwrap()), Some("old"));
+            assert!(et.get_events().filter(|e| e.uuid != hidden).all(|e| stream_of(e).is_none_or(|s| s == "rest")));
+        }
+
+        assert!(run(et.clone(), "start >").unwrap_err().starts_with("Filter: wrong predicate"));
+    }
+}
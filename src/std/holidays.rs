@@ -0,0 +1,242 @@
+//Responsible for Holidays Extension: non-working dates from built-in holiday rules, fixed dates and .ics holiday files
+
+use std::any::{Any, TypeId};
+use std::collections::{BTreeSet, HashMap};
+use std::sync::LazyLock;
+
+use chrono::{Datelike, Duration, NaiveDate, NaiveTime};
+
+use crate::core::{get_input, EventSpec, Extension, IOListOfTypes, IOOutputType, IOType, R_E_AClass, R_E_AGeneric};
+use crate::std::ical::parse_ics;
+
+/// Holiday that falls on same date every year or is bound to Easter Sunday
+#[derive(Clone, Copy, Debug)]
+enum Rule {
+    Fixed(u32, u32), // month, day
+    Easter(i64),     // days after Easter Sunday
+}
+
+/// Public holidays of whole Germany
+const GERMANY: [Rule; 9] = [
+    Rule::Fixed(1, 1),   // Neujahr
+    Rule::Easter(-2),    // Karfreitag
+    Rule::Easter(1),     // Ostermontag
+    Rule::Fixed(5, 1),   // Tag der Arbeit
+    Rule::Easter(39),    // Christi Himmelfahrt
+    Rule::Easter(50),    // Pfingstmontag
+    Rule::Fixed(10, 3),  // Tag der Deutschen Einheit
+    Rule::Fixed(12, 25), // 1. Weihnachtstag
+    Rule::Fixed(12, 26), // 2. Weihnachtstag
+];
+
+/// Additional public holidays of Bavaria (Mariä Himmelfahrt is a holiday in most Bavarian communities)
+const BAVARIA: [Rule; 4] = [
+    Rule::Fixed(1, 6),  // Heilige Drei Könige
+    Rule::Easter(60),   // Fronleichnam
+    Rule::Fixed(8, 15), // Mariä Himmelfahrt
+    Rule::Fixed(11, 1), // Allerheiligen
+];
+
+/// Rules of region code ("DE", "DE-BY"), regions include holidays of their country
+fn rules_of(region: &str)->Result<Vec<Rule>, String> {
+    match region.to_ascii_uppercase().as_str() {
+        "DE" => Ok(GERMANY.to_vec()),
+        "DE-BY" => Ok(GERMANY.iter().chain(&BAVARIA).copied().collect()),
+        _ => Err(format!("Unknown holiday region \"{}\" (known: DE, DE-BY)", region)),
+    }
+}
+
+/// Easter Sunday of (proleptic) Gregorian calendar (anonymous Gregorian algorithm), None for years before 1 and after last year of NaiveDate
+pub fn easter(year: i32)->Option<NaiveDate> {
+    if year < 1 {
+        return None;
+    }
+    let (a, b, c) = (year % 19, year / 100, year % 100);
+    let (d, e) = (b / 4, b % 4);
+    let f = (b + 8) / 25;
+    let g = (b - f + 1) / 3;
+    let h = (19 * a + b - d - g + 15) % 30;
+    let (i, k) = (c / 4, c % 4);
+    let l = (32 + 2 * e + 2 * i - h - k) % 7;
+    let m = (a + 11 * h + 22 * l) / 451;
+    let month = (h + l - 7 * m + 114) / 31;
+    let day = (h + l - 7 * m + 114) % 31 + 1;
+    NaiveDate::from_ymd_opt(year, month as u32, day as u32)
+}
+
+/// Dates of all-day Events (and their occurrences) between from and to; timed Events are not holidays
+fn dates_of(spec: &EventSpec, from: NaiveDate, to: NaiveDate)->Vec<NaiveDate> {
+    let Some((first, after_last)) = spec.all_day else { return Vec::new() };
+    let days = (after_last - first).num_days();
+    let starts = match &spec.recurrence {
+        Some(recurrence) => {
+            let midnight = |date: NaiveDate| spec.zone.resolve_local(date.and_time(NaiveTime::MIN));
+            recurrence.occurrences(spec.start, spec.end - spec.start, spec.zone, midnight(from), midnight(to + Duration::days(1))).into_iter()
+                .map(|start| spec.zone.to_local(start).date())
+                .collect()
+        }
+        None => vec![first],
+    };
+    starts.into_iter()
+        .flat_map(|start| (0..days).map(move |n| start + Duration::days(n)))
+        .filter(|date| (from..=to).contains(date))
+        .collect()
+}
+
+
+
+// ==========HOLIDAYS EXTENSION==========
+
+/// Collects non-working dates between from and to (inclusive) from built-in holiday rules, fixed dates and .ics files (all-day VEVENTs, yearly rules are expanded)
+/// Inputs: "regions": Vec<String> (e.g. ["DE-BY"], see rules_of()), "dates": Vec<NaiveDate>, "ics_paths": Vec<String>, "from": NaiveDate, "to": NaiveDate
+/// Output: "holidays": Vec<NaiveDate> (ordered, without duplicates; e.g. for WorkingCalendar)
+pub struct Holidays;
+
+impl R_E_AGeneric for Holidays {
+    fn get_name(&self)->&'static str {
+        "Holidays"
+    }
+    fn get_class(&self)->&'static R_E_AClass {
+        &R_E_AClass::Extension
+    }
+    fn get_inputs(&self)->&'static IOListOfTypes {
+        static INPUTS: LazyLock<IOListOfTypes> = LazyLock::new(|| HashMap::from([
+            ("regions".to_string(), TypeId::of::<Vec<String>>()),
+            ("dates".to_string(), TypeId::of::<Vec<NaiveDate>>()),
+            ("ics_paths".to_string(), TypeId::of::<Vec<String>>()),
+            ("from".to_string(), TypeId::of::<NaiveDate>()),
+            ("to".to_string(), TypeId::of::<NaiveDate>()),
+        ]));
+        &INPUTS
+    }
+}
+
+impl Extension for Holidays {
+    fn get_outputs(&self)->&'static IOListOfTypes {
+        static OUTPUTS: LazyLock<IOListOfTypes> = LazyLock::new(|| HashMap::from([
+            ("holidays".to_string(), TypeId::of::<Vec<NaiveDate>>()),
+        ]));
+        &OUTPUTS
+    }
+
+    fn run(&self,
+        inputs: IOType
+    )->Result<IOOutputType, String> {
+        let regions = get_input::<Vec<String>>(&inputs, "regions")?;
+        let dates = get_input::<Vec<NaiveDate>>(&inputs, "dates")?;
+        let ics_paths = get_input::<Vec<String>>(&inputs, "ics_paths")?;
+        let (from, to) = (*get_input::<NaiveDate>(&inputs, "from")?, *get_input::<NaiveDate>(&inputs, "to")?);
+        if from > to {
+            return Err(format!("Holidays: from {} is after to {}", from, to));
+        }
+
+        let mut holidays: BTreeSet<NaiveDate> = dates.iter().copied().filter(|date| (from..=to).contains(date)).collect();
+        for region in regions {
+            let rules = rules_of(region).map_err(|e| format!("Holidays: {}", e))?;
+            for year in from.year()..=to.year() {
+                for rule in &rules {
+                    let date = match rule {
+                        Rule::Fixed(month, day) => NaiveDate::from_ymd_opt(year, *month, *day),
+                        Rule::Easter(offset) => easter(year).and_then(|easter| easter.checked_add_signed(Duration::days(*offset))),
+                    };
+                    match date {
+                        Some(date) if (from..=to).contains(&date) => {
+                            holidays.insert(date);
+                        }
+                        Some(_) => {}
+                        None if matches!(rule, Rule::Easter(_)) => return Err(format!("Holidays: Easter of year {} is out of supported range", year)),
+                        None => {} // e.g. no such date in year
+                    }
+                }
+            }
+        }
+        for path in ics_paths {
+            let text = std::fs::read_to_string(path).map_err(|e| format!("Holidays: can not read {}: {}", path, e))?;
+            // unknown zones do not matter: holidays are all-day
+            let (specs, _) = parse_ics(&text).map_err(|e| format!("Holidays: {}: {}", path, e))?;
+            holidays.extend(specs.iter().flat_map(|spec| dates_of(spec, from, to)));
+        }
+        Ok(HashMap::from([
+            ("holidays".to_string(), Box::new(holidays.into_iter().collect::<Vec<NaiveDate>>()) as Box<dyn Any+Send+Sync>),
+        ]))
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::testing::TestInputs;
+
+    fn date(y: i32, m: u32, d: u32)->NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn run(regions: &[&str], dates: Vec<NaiveDate>, ics_paths: Vec<String>, from: NaiveDate, to: NaiveDate)->Result<Vec<NaiveDate>, String> {
+        let inputs = TestInputs::new()
+            .with("regions", regions.iter().map(|r| r.to_string()).collect::<Vec<String>>())
+            .with("dates", dates)
+            .with("ics_paths", ics_paths)
+            .with("from", from)
+            .with("to", to);
+        let mut outputs = Holidays.run(inputs.get())?;
+        Ok(*outputs.remove("holidays").unwrap().downcast::<Vec<NaiveDate>>().unwrap())
+    }
+
+    #[test]
+    fn easter_dates() {
+        assert_eq!(easter(1961), Some(date(1961, 4, 2)));
+        assert_eq!(easter(2000), Some(date(2000, 4, 23)));
+        assert_eq!(easter(2008), Some(date(2008, 3, 23)));
+        assert_eq!(easter(2024), Some(date(2024, 3, 31)));
+        assert_eq!(easter(2025), Some(date(2025, 4, 20)));
+        assert_eq!(easter(2026), Some(date(2026, 4, 5)));
+        assert_eq!(easter(2038), Some(date(2038, 4, 25)));
+        assert_eq!(easter(0), None);
+        assert_eq!(easter(-500), None);
+        assert_eq!(easter(i32::MAX), None);
+    }
+
+    #[test]
+    fn region_rules() {
+        let germany = run(&["DE"], Vec::new(), Vec::new(), date(2026, 1, 1), date(2026, 12, 31)).unwrap();
+        assert_eq!(germany, vec![
+            date(2026, 1, 1), date(2026, 4, 3), date(2026, 4, 6), date(2026, 5, 1), date(2026, 5, 14),
+            date(2026, 5, 25), date(2026, 10, 3), date(2026, 12, 25), date(2026, 12, 26),
+        ]);
+
+        // Bavarian holidays between from and to, fixed date is merged, dates outside are dropped
+        let bavaria = run(&["de-by", "DE"], vec![date(2026, 6, 4), date(2026, 6, 5), date(2026, 9, 1)], Vec::new(), date(2026, 5, 20), date(2026, 8, 31)).unwrap();
+        assert_eq!(bavaria, vec![date(2026, 5, 25), date(2026, 6, 4), date(2026, 6, 5), date(2026, 8, 15)]);
+
+        // range over new year
+        let new_year = run(&["DE"], Vec::new(), Vec::new(), date(2025, 12, 25), date(2026, 1, 1)).unwrap();
+        assert_eq!(new_year, vec![date(2025, 12, 25), date(2025, 12, 26), date(2026, 1, 1)]);
+    }
+
+    #[test]
+    fn ics_files_give_all_day_dates() {
+        let path = std::env::temp_dir().join(format!("rea_holidays_test_{}.ics", std::process::id()));
+        let path = path.to_str().unwrap().to_string();
+        std::fs::write(&path, [
+            "BEGIN:VCALENDAR",
+            "BEGIN:VEVENT", "UID:founding", "SUMMARY:Founding day", "DTSTART;VALUE=DATE:20200612", "DTEND;VALUE=DATE:20200613", "RRULE:FREQ=YEARLY", "END:VEVENT",
+            "BEGIN:VEVENT", "UID:closed", "SUMMARY:Closed", "DTSTART;VALUE=DATE:20261230", "DTEND;VALUE=DATE:20270102", "END:VEVENT",
+            "BEGIN:VEVENT", "UID:party", "SUMMARY:Party", "DTSTART:20260612T180000Z", "DTEND:20260612T230000Z", "END:VEVENT",
+            "END:VCALENDAR",
+        ].join("\r\n")).unwrap();
+        let holidays = run(&[], Vec::new(), vec![path.clone()], date(2026, 1, 1), date(2027, 6, 30));
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(holidays.unwrap(), vec![date(2026, 6, 12), date(2026, 12, 30), date(2026, 12, 31), date(2027, 1, 1), date(2027, 6, 12)]);
+    }
+
+    #[test]
+    fn wrong_inputs_are_rejected() {
+        assert!(run(&["DE"], Vec::new(), Vec::new(), date(2026, 2, 1), date(2026, 1, 1)).unwrap_err().contains("is after"));
+        assert!(run(&["FR"], Vec::new(), Vec::new(), date(2026, 1, 1), date(2026, 2, 1)).unwrap_err().contains("Unknown holiday region"));
+        assert_eq!(run(&["DE"], Vec::new(), Vec::new(), date(-1, 1, 1), date(-1, 12, 31)).unwrap_err(),
+            "Holidays: Easter of year -1 is out of supported range");
+        assert!(run(&[], Vec::new(), vec!["/nonexistent/holidays.ics".to_string()], date(2026, 1, 1), date(2026, 2, 1)).unwrap_err().contains("can not read"));
+    }
+}
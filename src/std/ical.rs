@@ -0,0 +1,862 @@
+//Responsible for iCalendar (RFC 5545) files: .ics import Extension and export Render
+// Supported: VEVENT with UID, SUMMARY, DTSTART, DTEND/DURATION, TZID (IANA and Windows names, other zones are read as floating time), RRULE (see recurrence.rs), EXDATE, RECURRENCE-ID, LOCATION, DESCRIPTION
+
+use std::any::{Any, TypeId};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::str::FromStr;
+use std::sync::LazyLock;
+
+use chrono::{Duration, NaiveDate, NaiveDateTime, NaiveTime, Offset, TimeZone, Utc, Weekday};
+use chrono_tz::{OffsetComponents, OffsetName, Tz};
+use uuid::Uuid;
+
+use crate::core::{get_input, DateTimeType, Event, EventSpec, EventTable, EventZone, Extension, IOListOfTypes, IOOutputType, IOType, R_E_AClass, R_E_AGeneric, Render};
+use crate::recurrence::{ByDay, Frequency, Recurrence};
+
+/// Namespace of UUIDs derived from iCalendar UIDs, so same UID always gives same UUID
+const ICS_NAMESPACE: Uuid = Uuid::from_u128(0x7d1c_53e2_4a0b_4f1e_9c4e_2b7a_1f0d_a3c5);
+
+/// Field of imported Event where original UID is stored
+pub const UID_FIELD: &str = "ics_uid";
+
+/// Windows zone names (Outlook, Exchange) → IANA names, as in CLDR windowsZones.xml (territory "001")
+const WINDOWS_ZONES: &[(&str, &str)] = &[
+    ("Dateline Standard Time", "Etc/GMT+12"),
+    ("UTC-11", "Etc/GMT+11"),
+    ("Hawaiian Standard Time", "Pacific/Honolulu"),
+    ("Alaskan Standard Time", "America/Anchorage"),
+    ("Pacific Standard Time", "America/Los_Angeles"),
+    ("US Mountain Standard Time", "America/Phoenix"),
+    ("Mountain Standard Time", "America/Denver"),
+    ("Central America Standard Time", "America/Guatemala"),
+    ("Central Standard Time", "America/Chicago"),
+    ("Central Standard Time (Mexico)", "America/Mexico_City"),
+    ("Canada Central Standard Time", "America/Regina"),
+    ("SA Pacific Standard Time", "America/Bogota"),
+    ("Eastern Standard Time", "America/New_York"),
+    ("US Eastern Standard Time", "America/Indiana/Indianapolis"),
+    ("Atlantic Standard Time", "America/Halifax"),
+    ("Newfoundland Standard Time", "America/St_Johns"),
+    ("E. South America Standard Time", "America/Sao_Paulo"),
+    ("Argentina Standard Time", "America/Argentina/Buenos_Aires"),
+    ("UTC", "Etc/UTC"),
+    ("GMT Standard Time", "Europe/London"),
+    ("Greenwich Standard Time", "Atlantic/Reykjavik"),
+    ("W. Europe Standard Time", "Europe/Berlin"),
+    ("Central Europe Standard Time", "Europe/Budapest"),
+    ("Romance Standard Time", "Europe/Paris"),
+    ("Central European Standard Time", "Europe/Warsaw"),
+    ("W. Central Africa Standard Time", "Africa/Lagos"),
+    ("GTB Standard Time", "Europe/Bucharest"),
+    ("E. Europe Standard Time", "Europe/Chisinau"),
+    ("FLE Standard Time", "Europe/Kiev"),
+    ("Egypt Standard Time", "Africa/Cairo"),
+    ("South Africa Standard Time", "Africa/Johannesburg"),
+    ("Israel Standard Time", "Asia/Jerusalem"),
+    ("Turkey Standard Time", "Europe/Istanbul"),
+    ("Russian Standard Time", "Europe/Moscow"),
+    ("Arabian Standard Time", "Asia/Dubai"),
+    ("Iran Standard Time", "Asia/Tehran"),
+    ("Pakistan Standard Time", "Asia/Karachi"),
+    ("India Standard Time", "Asia/Kolkata"),
+    ("Nepal Standard Time", "Asia/Kathmandu"),
+    ("Bangladesh Standard Time", "Asia/Dhaka"),
+    ("SE Asia Standard Time", "Asia/Bangkok"),
+    ("China Standard Time", "Asia/Shanghai"),
+    ("Singapore Standard Time", "Asia/Singapore"),
+    ("Taipei Standard Time", "Asia/Taipei"),
+    ("Tokyo Standard Time", "Asia/Tokyo"),
+    ("Korea Standard Time", "Asia/Seoul"),
+    ("AUS Central Standard Time", "Australia/Darwin"),
+    ("AUS Eastern Standard Time", "Australia/Sydney"),
+    ("E. Australia Standard Time", "Australia/Brisbane"),
+    ("W. Australia Standard Time", "Australia/Perth"),
+    ("New Zealand Standard Time", "Pacific/Auckland"),
+];
+
+
+
+// ==========IMPORT EXTENSION==========
+
+/// Reads .ics file
+/// Input: "path": String
+/// Output: "events": Vec<EventSpec> (UUID of every Event is derived from its UID → repeated import gives same UUIDs),
+///         "warnings": Vec<String> (e.g. TZIDs that are not known, their times are read as floating)
+pub struct IcsImport;
+
+impl R_E_AGeneric for IcsImport {
+    fn get_name(&self)->&'static str {
+        "IcsImport"
+    }
+    fn get_class(&self)->&'static R_E_AClass {
+        &R_E_AClass::Extension
+    }
+    fn get_inputs(&self)->&'static IOListOfTypes {
+        static INPUTS: LazyLock<IOListOfTypes> = LazyLock::new(|| HashMap::from([
+            ("path".to_string(), TypeId::of::<String>()),
+        ]));
+        &INPUTS
+    }
+}
+
+impl Extension for IcsImport {
+    fn get_outputs(&self)->&'static IOListOfTypes {
+        static OUTPUTS: LazyLock<IOListOfTypes> = LazyLock::new(|| HashMap::from([
+            ("events".to_string(), TypeId::of::<Vec<EventSpec>>()),
+            ("warnings".to_string(), TypeId::of::<Vec<String>>()),
+        ]));
+        &OUTPUTS
+    }
+
+    fn run(&self,
+        inputs: IOType
+    )->Result<IOOutputType, String> {
+        let path = get_input::<String>(&inputs, "path")?;
+        let text = std::fs::read_to_string(path).map_err(|e| format!("IcsImport: can not read {}: {}", path, e))?;
+        let (events, warnings) = parse_ics(&text).map_err(|e| format!("IcsImport: {}: {}", path, e))?;
+        Ok(HashMap::from([
+            ("events".to_string(), Box::new(events) as Box<dyn Any+Send+Sync>),
+            ("warnings".to_string(), Box::new(warnings) as Box<dyn Any+Send+Sync>),
+        ]))
+    }
+}
+
+
+
+// ==========PARSING==========
+
+/// Single unfolded "NAME;PARAM=VALUE:value" line
+struct ContentLine {
+    name: String,
+    params: HashMap<String, String>,
+    value: String,
+}
+
+/// All VEVENTs of .ics text as EventSpecs (recurring ones are not expanded) + warnings about data that was read approximately
+pub fn parse_ics(text: &str
+)->Result<(Vec<EventSpec>, Vec<String>), String> {
+    let mut events: Vec<EventSpec> = Vec::new();
+    let mut unknown_zones: BTreeSet<String> = BTreeSet::new();
+    // (UID, RECURRENCE-ID) of VEVENTs that override single occurrence of recurring VEVENT
+    let mut overrides: Vec<(String, DateTimeType)> = Vec::new();
+
+    let mut current: Option<Vec<ContentLine>> = None;
+    let mut nested = 0; // depth of components inside VEVENT (e.g. VALARM), their lines are skipped
+    for line in unfold(text) {
+        let line = parse_line(&line)?;
+        if let Some(tzid) = line.params.get("TZID") && zone_of_tzid(tzid).is_none() {
+            unknown_zones.insert(tzid.clone());
+        }
+        match (line.name.as_str(), line.value.to_ascii_uppercase().as_str()) {
+            ("BEGIN", "VEVENT") if current.is_none() => current = Some(Vec::new()),
+            ("BEGIN", _) if current.is_some() => nested += 1,
+            ("END", "VEVENT") if nested == 0 => {
+                let lines = current.take().ok_or("END:VEVENT without BEGIN:VEVENT")?;
+                let (spec, recurrence_id) = parse_vevent(&lines)?;
+                if let Some(recurrence_id) = recurrence_id {
+                    overrides.push((spec.fields[UID_FIELD].clone(), recurrence_id));
+                }
+                events.push(spec);
+            }
+            ("END", _) if current.is_some() => nested -= 1,
+            _ => {
+                if let Some(lines) = current.as_mut() && nested == 0 {
+                    lines.push(line);
+                }
+            }
+        }
+    }
+    if current.is_some() {
+        return Err("VEVENT is not closed with END:VEVENT".into());
+    }
+
+    // overridden occurrences are excluded from their recurring Event, override itself is separate Event
+    for (uid, recurrence_id) in overrides {
+        let master = events.iter_mut()
+            .find(|e| e.recurrence.is_some() && e.fields.get(UID_FIELD) == Some(&uid));
+        if let Some(recurrence) = master.and_then(|m| m.recurrence.as_mut()) {
+            recurrence.exdates.push(recurrence_id);
+        }
+    }
+    let warnings = unknown_zones.into_iter()
+        .map(|tzid| format!("Unknown TZID \"{}\", its times are read as floating (same wall-clock time in any zone)", tzid))
+        .collect();
+    Ok((events, warnings))
+}
+
+/// Lines with continuation lines (starting with space or tab) joined
+fn unfold(text: &str)->Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for raw in text.lines() {
+        match (raw.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(continuation), Some(last)) => last.push_str(continuation),
+            _ if raw.trim().is_empty() => {}
+            _ => lines.push(raw.to_string()),
+        }
+    }
+    lines
+}
+
+fn parse_line(line: &str)->Result<ContentLine, String> {
+    // ':' and ';' inside quoted parameter values are not separators
+    let mut in_quotes = false;
+    let mut parts: Vec<&str> = Vec::new();
+    let mut part_start = 0;
+    let mut value_start = None;
+    for (i, c) in line.char_indices() {
+        match c {
+            '"' => in_quotes = !in_quotes,
+            ';' if !in_quotes => {
+                parts.push(&line[part_start..i]);
+                part_start = i + 1;
+            }
+            ':' if !in_quotes => {
+                parts.push(&line[part_start..i]);
+                value_start = Some(i + 1);
+                break;
+            }
+            _ => {}
+        }
+    }
+    let value_start = value_start.ok_or(format!("Line without ':': {}", line))?;
+
+    let mut params = HashMap::new();
+    for param in &parts[1..] {
+        let (key, value) = param.split_once('=').ok_or(format!("Wrong parameter {} in line {}", param, line))?;
+        params.insert(key.to_ascii_uppercase(), value.trim_matches('"').to_string());
+    }
+    Ok(ContentLine {
+        name: parts[0].to_ascii_uppercase(),
+        params,
+        value: line[value_start..].to_string(),
+    })
+}
+
+/// EventSpec + RECURRENCE-ID (if VEVENT overrides occurrence of recurring VEVENT)
+fn parse_vevent(lines: &[ContentLine]
+)->Result<(EventSpec, Option<DateTimeType>), String> {
+    let get = |name: &str| lines.iter().find(|l| l.name == name);
+
+    let dtstart = get("DTSTART").ok_or("VEVENT without DTSTART")?;
+    let start = parse_time(dtstart)?;
+    let uid = match get("UID") {
+        Some(uid) => uid.value.clone(),
+        // UID is required by RFC 5545, without it UUID is at least stable for unchanged VEVENT
+        None => format!("{}/{}", dtstart.value, get("SUMMARY").map(|l| l.value.as_str()).unwrap_or_default()),
+    };
+    let recurrence_id = get("RECURRENCE-ID").map(parse_time).transpose()?;
+    let uuid = match (&recurrence_id, Uuid::parse_str(&uid)) {
+        (None, Ok(uuid)) => uuid, // UID written by REA itself
+        (None, Err(_)) => Uuid::new_v5(&ICS_NAMESPACE, uid.as_bytes()),
+        (Some(_), _) => Uuid::new_v5(&ICS_NAMESPACE, format!("{}/{}", uid, get("RECURRENCE-ID").map(|l| l.value.as_str()).unwrap_or_default()).as_bytes()),
+    };
+
+    let end = match (get("DTEND"), get("DURATION")) {
+        (Some(dtend), _) => parse_time(dtend)?,
+        (None, Some(duration)) => start.plus(parse_duration(&duration.value)?),
+        // RFC 5545: date lasts 1 day, date-time lasts 0
+        (None, None) => start.plus(if start.is_date() { Duration::days(1) } else { Duration::zero() }),
+    };
+
+    let mut spec = EventSpec::new(
+        unescape(&get("SUMMARY").map(|l| l.value.clone()).unwrap_or_default()),
+        start.absolute(),
+        end.absolute(),
+    );
+    spec.uuid = Some(uuid);
+    spec.zone = start.zone();
+    if let (IcsTime::Date(first), IcsTime::Date(after_last)) = (&start, &end) {
+        spec.all_day = Some((*first, *after_last));
+    }
+    if spec.start > spec.end {
+        return Err(format!("VEVENT {} ends before it starts", uid));
+    }
+
+    if let Some(rrule) = get("RRULE") {
+        let mut recurrence = parse_rrule(&rrule.value, spec.zone).map_err(|e| format!("VEVENT {}: {}", uid, e))?;
+        for exdate in lines.iter().filter(|l| l.name == "EXDATE") {
+            for value in exdate.value.split(',') {
+                let single = ContentLine { name: exdate.name.clone(), params: exdate.params.clone(), value: value.to_string() };
+                recurrence.exdates.push(parse_time(&single)?.absolute());
+            }
+        }
+        spec.recurrence = Some(recurrence);
+    }
+
+    let mut fields = BTreeMap::from([(UID_FIELD.to_string(), uid)]);
+    for (property, field) in [("LOCATION", "location"), ("DESCRIPTION", "description")] {
+        if let Some(line) = get(property) {
+            fields.insert(field.to_string(), unescape(&line.value));
+        }
+    }
+    spec.fields = fields;
+
+    Ok((spec, recurrence_id.map(|t| t.absolute())))
+}
+
+/// DATE or DATE-TIME value of iCalendar
+enum IcsTime {
+    Date(NaiveDate),
+    DateTime(DateTimeType, EventZone),
+}
+
+impl IcsTime {
+    fn is_date(&self)->bool {
+        matches!(self, IcsTime::Date(_))
+    }
+
+    fn zone(&self)->EventZone {
+        match self {
+            IcsTime::Date(_) => EventZone::Floating,
+            IcsTime::DateTime(_, zone) => *zone,
+        }
+    }
+
+    fn absolute(&self)->DateTimeType {
+        match self {
+            IcsTime::Date(date) => EventZone::Floating.resolve_local(date.and_time(NaiveTime::MIN)),
+            IcsTime::DateTime(t, _) => *t,
+        }
+    }
+
+    /// Dates stay dates if whole days are added
+    fn plus(&self, duration: Duration)->IcsTime {
+        match self {
+            IcsTime::Date(date) if duration.num_seconds() % 86400 == 0 => IcsTime::Date(*date + duration),
+            IcsTime::Date(_) => IcsTime::DateTime(self.absolute() + duration, EventZone::Floating),
+            IcsTime::DateTime(t, zone) => IcsTime::DateTime(*t + duration, *zone),
+        }
+    }
+}
+
+fn parse_time(line: &ContentLine)->Result<IcsTime, String> {
+    let value = line.value.trim();
+    if line.params.get("VALUE").map(|v| v.eq_ignore_ascii_case("DATE")).unwrap_or(false) || value.len() == 8 {
+        return NaiveDate::parse_from_str(value, "%Y%m%d")
+            .map(IcsTime::Date)
+            .map_err(|e| format!("{}: wrong date {}: {}", line.name, value, e));
+    }
+
+    let (local, is_utc) = match value.strip_suffix(['Z', 'z']) {
+        Some(local) => (local, true),
+        None => (value, false),
+    };
+    let local = NaiveDateTime::parse_from_str(local, "%Y%m%dT%H%M%S")
+        .map_err(|e| format!("{}: wrong date-time {}: {}", line.name, value, e))?;
+    let zone = match (is_utc, line.params.get("TZID")) {
+        (true, _) => EventZone::Utc,
+        // custom VTIMEZONEs are not interpreted, see parse_ics() warnings
+        (false, Some(tzid)) => zone_of_tzid(tzid).map(EventZone::Tz).unwrap_or(EventZone::Floating),
+        (false, None) => EventZone::Floating,
+    };
+    Ok(IcsTime::DateTime(zone.resolve_local(local), zone))
+}
+
+/// IANA zone of TZID: IANA name, IANA name with prefix (e.g. "/mozilla.org/20050126_1/Europe/Berlin") or Windows name
+fn zone_of_tzid(tzid: &str)->Option<Tz> {
+    let tzid = tzid.trim();
+    if let Some((_, iana)) = WINDOWS_ZONES.iter().find(|(windows, _)| windows.eq_ignore_ascii_case(tzid)) {
+        return Tz::from_str(iana).ok();
+    }
+    // prefixed name: parts after every '/' are tried, longest first
+    std::iter::once(tzid)
+        .chain(tzid.match_indices('/').map(|(i, _)| &tzid[i + 1..]))
+        .find_map(|name| Tz::from_str(name).ok())
+}
+
+/// "P1W", "-PT15M", "P1DT2H30M" etc.
+fn parse_duration(value: &str)->Result<Duration, String> {
+    let wrong = || format!("Wrong DURATION {}", value);
+    let (sign, rest) = match value.strip_prefix('-') {
+        Some(rest) => (-1, rest),
+        None => (1, value.strip_prefix('+').unwrap_or(value)),
+    };
+    let rest = rest.strip_prefix('P').ok_or_else(wrong)?;
+
+    let mut total = Duration::zero();
+    let mut number = String::new();
+    let mut in_time = false;
+    for c in rest.chars() {
+        match c {
+            '0'..='9' => number.push(c),
+            'T' => in_time = true,
+            _ => {
+                let n: i64 = number.parse().map_err(|_| wrong())?;
+                number.clear();
+                total += match (c, in_time) {
+                    ('W', false) => Duration::weeks(n),
+                    ('D', false) => Duration::days(n),
+                    ('H', true) => Duration::hours(n),
+                    ('M', true) => Duration::minutes(n),
+                    ('S', true) => Duration::seconds(n),
+                    _ => return Err(wrong()),
+                };
+            }
+        }
+    }
+    if !number.is_empty() {
+        return Err(wrong());
+    }
+    Ok(total * sign)
+}
+
+fn parse_rrule(value: &str, zone: EventZone)->Result<Recurrence, String> {
+    let mut recurrence: Option<Recurrence> = None;
+    let mut parts: Vec<(&str, &str)> = Vec::new();
+    for part in value.split(';').filter(|p| !p.is_empty()) {
+        let (key, value) = part.split_once('=').ok_or(format!("Wrong RRULE part {}", part))?;
+        if key.eq_ignore_ascii_case("FREQ") {
+            recurrence = Some(Recurrence::new(match value.to_ascii_uppercase().as_str() {
+                "DAILY" => Frequency::Daily,
+                "WEEKLY" => Frequency::Weekly,
+                "MONTHLY" => Frequency::Monthly,
+                "YEARLY" => Frequency::Yearly,
+                _ => return Err(format!("Unsupported RRULE FREQ {}", value)),
+            }));
+        } else {
+            parts.push((key, value));
+        }
+    }
+    let mut recurrence = recurrence.ok_or("RRULE without FREQ")?;
+
+    for (key, value) in parts {
+        let wrong = || format!("Wrong RRULE {}={}", key, value);
+        match key.to_ascii_uppercase().as_str() {
+            "INTERVAL" => recurrence.interval = value.parse().map_err(|_| wrong())?,
+            "COUNT" => recurrence.count = Some(value.parse().map_err(|_| wrong())?),
+            "UNTIL" => {
+                let until = ContentLine { name: "UNTIL".into(), params: HashMap::new(), value: value.to_string() };
+                recurrence.until = Some(match parse_time(&until)? {
+                    // date UNTIL includes whole day
+                    IcsTime::Date(date) => zone.resolve_local(date.and_time(NaiveTime::MIN) + Duration::days(1)) - Duration::seconds(1),
+                    IcsTime::DateTime(t, _) => t,
+                });
+            }
+            "BYDAY" => {
+                for day in value.split(',') {
+                    let split_at = day.len().checked_sub(2).ok_or_else(wrong)?;
+                    let (ordinal, weekday) = day.split_at(split_at);
+                    recurrence.by_day.push(ByDay {
+                        ordinal: if ordinal.is_empty() { None } else { Some(ordinal.trim_start_matches('+').parse().map_err(|_| wrong())?) },
+                        weekday: parse_weekday(weekday).ok_or_else(wrong)?,
+                    });
+                }
+            }
+            "WKST" => {} // weeks always start on Monday, only matters for rare rules
+            _ => return Err(format!("Unsupported RRULE part {}={}", key, value)),
+        }
+    }
+    recurrence.check()?;
+    Ok(recurrence)
+}
+
+fn parse_weekday(value: &str)->Option<Weekday> {
+    Some(match value.to_ascii_uppercase().as_str() {
+        "MO" => Weekday::Mon,
+        "TU" => Weekday::Tue,
+        "WE" => Weekday::Wed,
+        "TH" => Weekday::Thu,
+        "FR" => Weekday::Fri,
+        "SA" => Weekday::Sat,
+        "SU" => Weekday::Sun,
+        _ => return None,
+    })
+}
+
+/// TEXT value unescaping: \n \, \; \\
+fn unescape(value: &str)->String {
+    let mut result = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n' | 'N') => result.push('\n'),
+            Some(other) => result.push(other),
+            None => result.push('\\'),
+        }
+    }
+    result
+}
+
+
+
+// ==========EXPORT RENDER==========
+
+/// Writes ET to .ics file, UID of every Event is its UUID (so file can be imported back by IcsImport with same UUIDs)
+/// Input: "path": String
+pub struct IcsExport;
+
+impl R_E_AGeneric for IcsExport {
+    fn get_name(&self)->&'static str {
+        "IcsExport"
+    }
+    fn get_class(&self)->&'static R_E_AClass {
+        &R_E_AClass::Render
+    }
+    fn get_inputs(&self)->&'static IOListOfTypes {
+        static INPUTS: LazyLock<IOListOfTypes> = LazyLock::new(|| HashMap::from([
+            ("path".to_string(), TypeId::of::<String>()),
+        ]));
+        &INPUTS
+    }
+}
+
+impl Render for IcsExport {
+    fn run(&self,
+        et: EventTable,
+        inputs: IOType
+    )->Result<(), String> {
+        let path = get_input::<String>(&inputs, "path")?;
+        std::fs::write(path, to_ics(&et)).map_err(|e| format!("IcsExport: can not write {}: {}", path, e))
+    }
+}
+
+
+
+// ==========WRITING==========
+
+/// Years after the last Event for which DST transitions are written (recurring Events may last longer than ET)
+const VTIMEZONE_YEARS_AHEAD: i64 = 5;
+
+/// Whole ET as iCalendar text. Materialised occurrences of recurring Events are written as RECURRENCE-ID overrides
+pub fn to_ics(et: &EventTable
+)->String {
+    let mut lines: Vec<String> = ["BEGIN:VCALENDAR", "VERSION:2.0", "PRODID:-//REA//REA//EN", "CALSCALE:GREGORIAN"]
+        .map(String::from).to_vec();
+
+    // every TZID MUST have VTIMEZONE, it covers all times zone is used for
+    let mut zones: BTreeMap<&str, (Tz, DateTimeType, DateTimeType)> = BTreeMap::new();
+    for event in et.get_events() {
+        if let EventZone::Tz(tz) = event.zone {
+            let last = event.recurrence.as_ref().and_then(|r| r.until).unwrap_or(event.end).max(event.end);
+            let range = zones.entry(tz.name()).or_insert((tz, event.start, last));
+            range.1 = range.1.min(event.start);
+            range.2 = range.2.max(last);
+        }
+    }
+    for (tz, from, to) in zones.into_values() {
+        lines.extend(vtimezone(tz, from - Duration::days(1), to + Duration::days(365 * VTIMEZONE_YEARS_AHEAD)));
+    }
+
+    let stamp = Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+    for event in et.get_events() {
+        lines.extend(vevent(et, event, &stamp));
+    }
+    lines.push("END:VCALENDAR".into());
+
+    lines.iter().map(|l| fold(l)).collect()
+}
+
+fn vevent(et: &EventTable, event: &Event, stamp: &str)->Vec<String> {
+    let mut lines = vec!["BEGIN:VEVENT".to_string()];
+
+    let master = event.parent.and_then(|p| et.get(p)).filter(|p| p.recurrence.is_some());
+    match (master, event.recurrence_id) {
+        (Some(master), Some(recurrence_id)) => {
+            lines.push(format!("UID:{}", master.uuid));
+            lines.push(time_property("RECURRENCE-ID", recurrence_id, master.zone, master.all_day.is_some()));
+        }
+        _ => lines.push(format!("UID:{}", event.uuid)),
+    }
+    lines.push(format!("DTSTAMP:{}", stamp));
+
+    match event.all_day {
+        Some((first, after_last)) => {
+            lines.push(format!("DTSTART;VALUE=DATE:{}", first.format("%Y%m%d")));
+            lines.push(format!("DTEND;VALUE=DATE:{}", after_last.format("%Y%m%d")));
+        }
+        None => {
+            lines.push(time_property("DTSTART", event.start, event.zone, false));
+            lines.push(time_property("DTEND", event.end, event.zone, false));
+        }
+    }
+    lines.push(format!("SUMMARY:{}", escape(event.get_name())));
+    for (field, property) in [("location", "LOCATION"), ("description", "DESCRIPTION")] {
+        if let Some(value) = event.fields.get(field) {
+            lines.push(format!("{}:{}", property, escape(value)));
+        }
+    }
+
+    if let Some(recurrence) = &event.recurrence {
+        lines.push(rrule(recurrence, event.zone, event.all_day.is_some()));
+        for exdate in &recurrence.exdates {
+            lines.push(time_property("EXDATE", *exdate, event.zone, event.all_day.is_some()));
+        }
+    }
+
+    lines.push("END:VEVENT".into());
+    lines
+}
+
+/// "NAME;TZID=...:value" in form that matches zone (DATE if is_date)
+fn time_property(name: &str, t: DateTimeType, zone: EventZone, is_date: bool)->String {
+    let local = zone.to_local(t);
+    match zone {
+        _ if is_date => format!("{};VALUE=DATE:{}", name, local.format("%Y%m%d")),
+        EventZone::Utc => format!("{}:{}", name, t.format("%Y%m%dT%H%M%SZ")),
+        EventZone::Tz(tz) => format!("{};TZID={}:{}", name, tz.name(), local.format("%Y%m%dT%H%M%S")),
+        EventZone::Floating => format!("{}:{}", name, local.format("%Y%m%dT%H%M%S")),
+    }
+}
+
+fn rrule(recurrence: &Recurrence, zone: EventZone, is_date: bool)->String {
+    let mut parts = vec![format!("FREQ={}", match recurrence.frequency {
+        Frequency::Daily => "DAILY",
+        Frequency::Weekly => "WEEKLY",
+        Frequency::Monthly => "MONTHLY",
+        Frequency::Yearly => "YEARLY",
+    })];
+    if recurrence.interval != 1 {
+        parts.push(format!("INTERVAL={}", recurrence.interval));
+    }
+    if let Some(count) = recurrence.count {
+        parts.push(format!("COUNT={}", count));
+    }
+    if let Some(until) = recurrence.until {
+        // UNTIL MUST be DATE for DATE DTSTART, floating for floating DTSTART and UTC otherwise
+        let local = zone.to_local(until);
+        parts.push(format!("UNTIL={}", match zone {
+            _ if is_date => local.format("%Y%m%d").to_string(),
+            EventZone::Floating => local.format("%Y%m%dT%H%M%S").to_string(),
+            EventZone::Utc | EventZone::Tz(_) => until.format("%Y%m%dT%H%M%SZ").to_string(),
+        }));
+    }
+    if !recurrence.by_day.is_empty() {
+        let by_day: Vec<String> = recurrence.by_day.iter()
+            .map(|b| format!("{}{}", b.ordinal.map(|o| o.to_string()).unwrap_or_default(), weekday_code(b.weekday)))
+            .collect();
+        parts.push(format!("BYDAY={}", by_day.join(",")));
+    }
+    format!("RRULE:{}", parts.join(";"))
+}
+
+/// VTIMEZONE of tz with all its offset changes in [from, to)
+fn vtimezone(tz: Tz, from: DateTimeType, to: DateTimeType)->Vec<String> {
+    let offset_at = |t: DateTimeType| tz.offset_from_utc_datetime(&t.naive_utc());
+    let seconds_of = |t: DateTimeType| offset_at(t).fix().local_minus_utc();
+
+    let mut lines = vec!["BEGIN:VTIMEZONE".to_string(), format!("TZID:{}", tz.name())];
+    let mut observance = |kind: &str, at: DateTimeType, offset_from: i32| {
+        let offset = offset_at(at);
+        lines.push(format!("BEGIN:{}", kind));
+        // DTSTART of observance is local time before the change
+        lines.push(format!("DTSTART:{}", (at.naive_utc() + Duration::seconds(offset_from.into())).format("%Y%m%dT%H%M%S")));
+        lines.push(format!("TZOFFSETFROM:{}", utc_offset(offset_from)));
+        lines.push(format!("TZOFFSETTO:{}", utc_offset(offset.fix().local_minus_utc())));
+        if let Some(name) = offset.abbreviation() {
+            lines.push(format!("TZNAME:{}", name));
+        }
+        lines.push(format!("END:{}", kind));
+    };
+    let kind_at = |t: DateTimeType| if offset_at(t).dst_offset().is_zero() { "STANDARD" } else { "DAYLIGHT" };
+
+    observance(kind_at(from), from, seconds_of(from));
+    let mut day = from;
+    while day < to {
+        let next_day = day + Duration::days(1);
+        if seconds_of(day) != seconds_of(next_day) {
+            // exact moment of change: first second with new offset
+            let (mut before, mut after) = (day, next_day);
+            while after - before > Duration::seconds(1) {
+                let middle = before + (after - before) / 2;
+                if seconds_of(middle) == seconds_of(before) {
+                    before = middle;
+                } else {
+                    after = middle;
+                }
+            }
+            observance(kind_at(after), after, seconds_of(before));
+        }
+        day = next_day;
+    }
+
+    lines.push("END:VTIMEZONE".into());
+    lines
+}
+
+/// "+0100", "-0330", "+053328"
+fn utc_offset(seconds: i32)->String {
+    let sign = if seconds < 0 { '-' } else { '+' };
+    let seconds = seconds.abs();
+    let (h, m, s) = (seconds / 3600, seconds % 3600 / 60, seconds % 60);
+    if s == 0 {
+        format!("{}{:02}{:02}", sign, h, m)
+    } else {
+        format!("{}{:02}{:02}{:02}", sign, h, m, s)
+    }
+}
+
+fn weekday_code(weekday: Weekday)->&'static str {
+    match weekday {
+        Weekday::Mon => "MO",
+        Weekday::Tue => "TU",
+        Weekday::Wed => "WE",
+        Weekday::Thu => "TH",
+        Weekday::Fri => "FR",
+        Weekday::Sat => "SA",
+        Weekday::Sun => "SU",
+    }
+}
+
+/// TEXT value escaping, reverse of unescape()
+fn escape(value: &str)->String {
+    value.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+/// Line split in parts of max 75 octets, each ending with CRLF, continuation parts start with space
+fn fold(line: &str)->String {
+    let mut folded = String::with_capacity(line.len() + 8);
+    let mut part_len = 0;
+    let mut limit = 75;
+    for c in line.chars() {
+        if part_len + c.len_utf8() > limit {
+            folded.push_str("\r\n ");
+            part_len = 0;
+            limit = 74; // space is part of continuation line
+        }
+        folded.push(c);
+        part_len += c.len_utf8();
+    }
+    folded.push_str("\r\n");
+    folded
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono_tz::Europe::Berlin;
+
+    fn utc(d: u32, h: u32, m: u32)->DateTimeType {
+        Utc.with_ymd_and_hms(2026, 5, d, h, m, 0).unwrap()
+    }
+
+    fn calendar(vevents: &[&str])->String {
+        let mut text = "BEGIN:VCALENDAR\r\nVERSION:2.0\r\n".to_string();
+        for vevent in vevents {
+            text += &format!("BEGIN:VEVENT\r\n{}\r\nEND:VEVENT\r\n", vevent.trim().replace('\n', "\r\n"));
+        }
+        text + "END:VCALENDAR\r\n"
+    }
+
+    #[test]
+    fn lines_are_unfolded_and_unescaped() {
+        let lines = unfold("SUMMARY:Long\r\n  line\r\n\t continued\r\n\r\nLOCATION:Room\r\n");
+        assert_eq!(lines, vec!["SUMMARY:Long line continued".to_string(), "LOCATION:Room".to_string()]);
+
+        let line = parse_line("DTSTART;TZID=\"Custom: zone;1\";VALUE=DATE-TIME:20260504T090000").unwrap();
+        assert_eq!((line.name.as_str(), line.params["TZID"].as_str(), line.value.as_str()), ("DTSTART", "Custom: zone;1", "20260504T090000"));
+        assert!(parse_line("NO VALUE").is_err());
+
+        assert_eq!(unescape(r"a\, b\; c\nd\\e"), "a, b; c\nd\\e");
+    }
+
+    #[test]
+    fn times_durations_and_all_day_events() {
+        let (events, warnings) = parse_ics(&calendar(&[
+            "UID:a\nSUMMARY:Berlin\nDTSTART;TZID=Europe/Berlin:20260504T090000\nDURATION:PT1H30M\nLOCATION:Room\\, 1",
+            "UID:b\nSUMMARY:Utc\nDTSTART:20260504T090000Z\nDTEND:20260504T100000Z",
+            "UID:c\nSUMMARY:Holiday\nDTSTART;VALUE=DATE:20260501",
+        ])).unwrap();
+        assert!(warnings.is_empty());
+        assert_eq!((events[0].start, events[0].end, events[0].zone), (utc(4, 7, 0), utc(4, 8, 30), EventZone::Tz(Berlin)));
+        assert_eq!(events[0].fields["location"], "Room, 1");
+        assert_eq!(events[0].uuid, Some(Uuid::new_v5(&ICS_NAMESPACE, b"a")));
+        assert_eq!((events[1].start, events[1].zone), (utc(4, 9, 0), EventZone::Utc));
+        assert_eq!(events[2].all_day, NaiveDate::from_ymd_opt(2026, 5, 1).zip(NaiveDate::from_ymd_opt(2026, 5, 2)));
+        assert_eq!(parse_duration("-P1W2DT3H").unwrap(), -(Duration::days(9) + Duration::hours(3)));
+        assert!(parse_duration("PT1H2").is_err());
+    }
+
+    #[test]
+    fn windows_prefixed_and_unknown_zones() {
+        let (events, warnings) = parse_ics(&calendar(&[
+            "UID:a\nDTSTART;TZID=W. Europe Standard Time:20260504T090000\nDTEND;TZID=W. Europe Standard Time:20260504T100000",
+            "UID:b\nDTSTART;TZID=/mozilla.org/20050126_1/Europe/Berlin:20260504T090000",
+            "UID:c\nDTSTART;TZID=My Office Zone:20260504T090000\nDTEND;TZID=My Office Zone:20260504T100000",
+        ])).unwrap();
+        assert_eq!((events[0].start, events[0].zone), (utc(4, 7, 0), EventZone::Tz(Berlin)));
+        assert_eq!((events[1].start, events[1].zone), (utc(4, 7, 0), EventZone::Tz(Berlin)));
+        // import goes on, Event keeps its wall-clock time
+        assert_eq!((events[2].start, events[2].end, events[2].zone), (utc(4, 9, 0), utc(4, 10, 0), EventZone::Floating));
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("My Office Zone"));
+
+        for (windows, iana) in WINDOWS_ZONES {
+            assert!(Tz::from_str(iana).is_ok(), "{} → {}", windows, iana);
+        }
+    }
+
+    #[test]
+    fn recurrence_and_overrides() {
+        let (events, _) = parse_ics(&calendar(&[
+            "UID:standup\nDTSTART:20260504T090000Z\nDTEND:20260504T091500Z\nRRULE:FREQ=WEEKLY;BYDAY=MO,WE;COUNT=4\nEXDATE:20260506T090000Z,20260511T090000Z",
+            "UID:standup\nRECURRENCE-ID:20260513T090000Z\nDTSTART:20260513T100000Z\nDTEND:20260513T101500Z",
+        ])).unwrap();
+        let recurrence = events[0].recurrence.as_ref().unwrap();
+        assert_eq!((recurrence.frequency, recurrence.count), (Frequency::Weekly, Some(4)));
+        assert_eq!(recurrence.by_day.iter().map(|b| b.weekday).collect::<Vec<_>>(), vec![Weekday::Mon, Weekday::Wed]);
+        // overridden occurrence is excluded from master and is separate Event
+        assert_eq!(recurrence.exdates, vec![utc(6, 9, 0), utc(11, 9, 0), utc(13, 9, 0)]);
+        assert_eq!((events[1].start, events[1].recurrence.is_none()), (utc(13, 10, 0), true));
+        assert_ne!(events[0].uuid, events[1].uuid);
+
+        assert!(parse_ics(&calendar(&["UID:x\nDTSTART:20260504T090000Z\nRRULE:FREQ=HOURLY"])).is_err());
+        assert!(parse_ics(&calendar(&["UID:x\nDTSTART:20260504T100000Z\nDTEND:20260504T090000Z"])).is_err());
+        assert!(parse_ics("BEGIN:VEVENT\r\nDTSTART:20260504T090000Z\r\n").is_err());
+    }
+
+    #[test]
+    fn lines_are_folded_by_octets_and_escaped() {
+        let line = format!("SUMMARY:{}", "ä".repeat(50));
+        let folded = fold(&line);
+        assert!(folded.split("\r\n").all(|part| part.len() <= 75));
+        assert_eq!(unfold(&folded), vec![line]);
+
+        let text = "a, b; c\\d\nnext line";
+        assert_eq!(unescape(&escape(text)), text);
+        assert_eq!(escape(text), r"a\, b\; c\\d\nnext line");
+    }
+
+    #[test]
+    fn export_and_import_roundtrip() {
+        let mut et = EventTable::new();
+        let mut spec = EventSpec::new("Meeting, weekly".to_string(), utc(4, 7, 0), utc(4, 8, 0));
+        spec.zone = EventZone::Tz(Berlin);
+        let mut recurrence = Recurrence::new(Frequency::Weekly);
+        recurrence.count = Some(3);
+        spec.recurrence = Some(recurrence);
+        spec.fields.insert("location".to_string(), "Room; 1".to_string());
+        let meeting = et.add_spec(spec).unwrap();
+        let occurrence = et.materialise(meeting, utc(10, 0, 0), utc(12, 0, 0)).unwrap()[0];
+        et.set_times(occurrence, utc(11, 9, 0), utc(11, 10, 0)).unwrap();
+        let holiday = et.add_spec(EventSpec::new_all_day("Holiday".to_string(), NaiveDate::from_ymd_opt(2026, 5, 14).unwrap(), NaiveDate::from_ymd_opt(2026, 5, 15).unwrap())).unwrap();
+
+        let text = to_ics(&et);
+        assert!(text.lines().all(|l| l.len() <= 75));
+        assert!(text.contains("BEGIN:VTIMEZONE\r\nTZID:Europe/Berlin\r\n"));
+        assert!(text.contains("TZOFFSETFROM:+0100\r\nTZOFFSETTO:+0200\r\nTZNAME:CEST"));
+        assert!(text.contains("DTSTART;TZID=Europe/Berlin:20260504T090000"));
+
+        let (events, warnings) = parse_ics(&text).unwrap();
+        assert!(warnings.is_empty());
+        assert_eq!(events.len(), 3);
+        let imported = events.iter().find(|e| e.uuid == Some(meeting)).unwrap();
+        assert_eq!((imported.name.as_str(), imported.start, imported.zone), ("Meeting, weekly", utc(4, 7, 0), EventZone::Tz(Berlin)));
+        assert_eq!(imported.fields["location"], "Room; 1");
+        // moved occurrence is RECURRENCE-ID override: excluded from master, imported as separate Event
+        let recurrence = imported.recurrence.as_ref().unwrap();
+        assert_eq!((recurrence.count, recurrence.exdates.clone()), (Some(3), vec![utc(11, 7, 0)]));
+        assert!(events.iter().any(|e| e.start == utc(11, 9, 0) && e.end == utc(11, 10, 0) && e.recurrence.is_none()));
+        let imported = events.iter().find(|e| e.uuid == Some(holiday)).unwrap();
+        assert_eq!(imported.all_day, et.get(holiday).unwrap().all_day);
+    }
+}
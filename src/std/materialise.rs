@@ -0,0 +1,97 @@
+//Responsible for Materialise App: occurrences of recurring Events become separate Events, so other Apps can move and split them
+
+use std::any::TypeId;
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+use uuid::Uuid;
+
+use crate::core::{get_input, App, DateTimeType, EventTable, IOListOfTypes, IOType, R_E_AClass, R_E_AGeneric};
+use crate::std::filter::is_hidden;
+
+/// Adds occurrences of visible recurring Events that overlap [from, to) as separate Events (see EventTable::materialise()).
+/// Master Event stays recurring and is its first occurrence; occurrences materialised by previous runs are not added again
+/// Inputs: "from": DateTimeType, "to": DateTimeType
+/// Output: ET with materialised occurrences (parent = UUID of recurring Event)
+pub struct Materialise;
+
+impl R_E_AGeneric for Materialise {
+    fn get_name(&self)->&'static str {
+        "Materialise"
+    }
+    fn get_class(&self)->&'static R_E_AClass {
+        &R_E_AClass::App
+    }
+    fn get_inputs(&self)->&'static IOListOfTypes {
+        static INPUTS: LazyLock<IOListOfTypes> = LazyLock::new(|| HashMap::from([
+            ("from".to_string(), TypeId::of::<DateTimeType>()),
+            ("to".to_string(), TypeId::of::<DateTimeType>()),
+        ]));
+        &INPUTS
+    }
+}
+
+impl App for Materialise {
+    fn run(&self,
+        mut et: EventTable,
+        inputs: IOType
+    )->Result<EventTable, String> {
+        let from = *get_input::<DateTimeType>(&inputs, "from")?;
+        let to = *get_input::<DateTimeType>(&inputs, "to")?;
+        if from > to {
+            return Err(format!("Materialise: from {} is after to {}", from, to));
+        }
+
+        let recurring: Vec<Uuid> = et.get_events()
+            .filter(|e| e.recurrence.is_some() && !is_hidden(e))
+            .map(|e| e.uuid)
+            .collect();
+        for uuid in recurring {
+            et.materialise(uuid, from, to).map_err(|e| format!("Materialise: {}", e))?;
+        }
+        Ok(et)
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use crate::core::EventSpec;
+    use crate::core::testing::{at, TestInputs};
+    use crate::recurrence::{Frequency, Recurrence};
+
+    fn run(et: EventTable, from: DateTimeType, to: DateTimeType)->Result<EventTable, String> {
+        Materialise.run(et, TestInputs::new().with("from", from).with("to", to).get())
+    }
+
+    #[test]
+    fn occurrences_are_added_once() {
+        let mut et = EventTable::new();
+        let mut spec = EventSpec::new("Standup".to_string(), at(4, 9, 0), at(4, 10, 0));
+        spec.recurrence = Some(Recurrence::new(Frequency::Daily));
+        spec.fields.insert("room".to_string(), "A".to_string());
+        let master = et.add_spec(spec).unwrap();
+        et.add_spec(EventSpec::new("Single".to_string(), at(5, 12, 0), at(5, 13, 0))).unwrap();
+
+        let et = run(et, at(4, 0, 0), at(7, 0, 0)).unwrap();
+        let mut occurrences: Vec<_> = et.get_events().filter(|e| e.parent == Some(master)).collect();
+        occurrences.sort_by_key(|e| e.start);
+        assert_eq!(occurrences.iter().map(|e| (e.start, e.recurrence_id)).collect::<Vec<_>>(),
+            vec![(at(5, 9, 0), Some(at(5, 9, 0))), (at(6, 9, 0), Some(at(6, 9, 0)))]);
+        assert!(occurrences.iter().all(|e| e.get_name() == "Standup" && e.fields["room"] == "A" && e.recurrence.is_none()));
+
+        // moved occurrence is not materialised again
+        let moved = occurrences[0].uuid;
+        let mut et = et;
+        et.set_times(moved, at(5, 15, 0), at(5, 16, 0)).unwrap();
+        let et = run(et, at(4, 0, 0), at(8, 0, 0)).unwrap();
+        assert_eq!(et.get_events().filter(|e| e.parent == Some(master)).count(), 3);
+        assert_eq!(et.get(moved).unwrap().start, at(5, 15, 0));
+        assert!(et.get_events().any(|e| e.parent == Some(master) && e.start == at(7, 9, 0)));
+
+        assert!(run(et, at(8, 0, 0), at(8, 0, 0) - Duration::hours(1)).is_err());
+    }
+}
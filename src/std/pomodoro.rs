@@ -0,0 +1,182 @@
+//Responsible for Pomodoro App: selected Events are split into work blocks with breaks between them
+
+use std::any::TypeId;
+use std::collections::{HashMap, HashSet};
+use std::sync::LazyLock;
+
+use chrono::Duration;
+use uuid::Uuid;
+
+use crate::core::{get_input, App, DateTimeType, EventTable, IOListOfTypes, IOType, R_E_AClass, R_E_AGeneric};
+use crate::std::filter::is_hidden;
+
+/// Field of children: "work", "short break" or "long break" (names can not be read by Apps)
+pub const PHASE_FIELD: &str = "pomodoro";
+
+/// Splits selected Events into work blocks with short breaks between them and long break after every cycle.
+/// All work time of Event is kept, so Event ends later by length of its breaks
+/// Inputs: "events": Vec<Uuid> (repeated UUIDs are split once), "work": u32, "short_break": u32, "long_break": u32 (minutes, usually 25/5/15), "cycles": u32 (work blocks per cycle, usually 4)
+/// Output: ET where each selected Event is replaced by children " (work 1/4)", " (short break)", ..., " (long break)", ...
+pub struct Pomodoro;
+
+impl R_E_AGeneric for Pomodoro {
+    fn get_name(&self)->&'static str {
+        "Pomodoro"
+    }
+    fn get_class(&self)->&'static R_E_AClass {
+        &R_E_AClass::App
+    }
+    fn get_inputs(&self)->&'static IOListOfTypes {
+        static INPUTS: LazyLock<IOListOfTypes> = LazyLock::new(|| HashMap::from([
+            ("events".to_string(), TypeId::of::<Vec<Uuid>>()),
+            ("work".to_string(), TypeId::of::<u32>()),
+            ("short_break".to_string(), TypeId::of::<u32>()),
+            ("long_break".to_string(), TypeId::of::<u32>()),
+            ("cycles".to_string(), TypeId::of::<u32>()),
+        ]));
+        &INPUTS
+    }
+}
+
+impl App for Pomodoro {
+    fn run(&self,
+        mut et: EventTable,
+        inputs: IOType
+    )->Result<EventTable, String> {
+        let events = get_input::<Vec<Uuid>>(&inputs, "events")?;
+        let minutes = |name: &str| get_input::<u32>(&inputs, name).map(|m| Duration::minutes(*m as i64));
+        let (work, short_break, long_break) = (minutes("work")?, minutes("short_break")?, minutes("long_break")?);
+        let cycles = *get_input::<u32>(&inputs, "cycles")?;
+        if work <= Duration::zero() || cycles == 0 {
+            return Err("Pomodoro: work and cycles must be greater than 0".to_string());
+        }
+
+        let mut seen = HashSet::new();
+        for uuid in events.iter().filter(|uuid| seen.insert(**uuid)) {
+            let event = et.get(*uuid).ok_or(format!("Pomodoro: missing Event {}", uuid))?;
+            if is_hidden(event) {
+                continue;
+            }
+            let blocks = blocks(event.start, event.end, work, short_break, long_break, cycles);
+            let phases: Vec<&str> = blocks.iter().map(|(_, _, _, phase)| *phase).collect();
+            let children = et.split(*uuid, blocks.into_iter().map(|(start, end, suffix, _)| (start, end, suffix)).collect())
+                .map_err(|e| format!("Pomodoro: {}", e))?;
+            for (child, phase) in children.into_iter().zip(phases) {
+                et.set_field(child, PHASE_FIELD, Some(phase.to_string()))?;
+            }
+        }
+        Ok(et)
+    }
+}
+
+/// (start, end, suffix, phase) of blocks. Last block is always work, so breaks are only between work blocks
+fn blocks(start: DateTimeType,
+    end: DateTimeType,
+    work: Duration,
+    short_break: Duration,
+    long_break: Duration,
+    cycles: u32
+)->Vec<(DateTimeType, DateTimeType, String, &'static str)> {
+    let mut blocks = Vec::new();
+    let mut remaining = end - start;
+    let mut t = start;
+    let mut n = 0;
+    loop {
+        let length = work.min(remaining);
+        blocks.push((t, t + length, format!(" (work {}/{})", n % cycles + 1, cycles), "work"));
+        t += length;
+        remaining -= length;
+        n += 1;
+        if remaining <= Duration::zero() {
+            return blocks;
+        }
+        let (length, phase) = if n % cycles == 0 { (long_break, "long break") } else { (short_break, "short break") };
+        if length > Duration::zero() {
+            blocks.push((t, t + length, format!(" ({})", phase), phase));
+            t += length;
+        }
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::EventSpec;
+    use crate::core::testing::{at, TestInputs};
+    use crate::std::filter::HIDDEN_FIELD;
+
+    fn run(et: EventTable, events: Vec<Uuid>, short_break: u32, cycles: u32)->Result<EventTable, String> {
+        let inputs = TestInputs::new()
+            .with("events", events)
+            .with("work", 25u32)
+            .with("short_break", short_break)
+            .with("long_break", 15u32)
+            .with("cycles", cycles);
+        Pomodoro.run(et, inputs.get())
+    }
+
+    /// (name, start, end, phase) of children of parent, ordered by start
+    fn children(et: &EventTable, parent: Uuid)->Vec<(String, DateTimeType, DateTimeType, String)> {
+        let mut children: Vec<_> = et.get_events()
+            .filter(|e| e.parent == Some(parent))
+            .map(|e| (e.get_name().to_string(), e.start, e.end, e.fields[PHASE_FIELD].clone()))
+            .collect();
+        children.sort_by_key(|c| c.1);
+        children
+    }
+
+    fn block(name: &str, start: DateTimeType, end: DateTimeType, phase: &str)->(String, DateTimeType, DateTimeType, String) {
+        (name.to_string(), start, end, phase.to_string())
+    }
+
+    #[test]
+    fn long_break_after_cycle_and_remainder_after_end() {
+        let mut et = EventTable::new();
+        let focus = et.add_spec(EventSpec::new("Focus".to_string(), at(4, 9, 0), at(4, 11, 0))).unwrap();
+        let et = run(et, vec![focus], 5, 4).unwrap();
+        assert!(et.get(focus).is_none());
+        // 120 minutes of work: 4 blocks of 25 minutes, long break, rest 20 minutes after original end
+        assert_eq!(children(&et, focus), vec![
+            block("Focus (work 1/4)", at(4, 9, 0), at(4, 9, 25), "work"),
+            block("Focus (short break)", at(4, 9, 25), at(4, 9, 30), "short break"),
+            block("Focus (work 2/4)", at(4, 9, 30), at(4, 9, 55), "work"),
+            block("Focus (short break)", at(4, 9, 55), at(4, 10, 0), "short break"),
+            block("Focus (work 3/4)", at(4, 10, 0), at(4, 10, 25), "work"),
+            block("Focus (short break)", at(4, 10, 25), at(4, 10, 30), "short break"),
+            block("Focus (work 4/4)", at(4, 10, 30), at(4, 10, 55), "work"),
+            block("Focus (long break)", at(4, 10, 55), at(4, 11, 10), "long break"),
+            block("Focus (work 1/4)", at(4, 11, 10), at(4, 11, 30), "work"),
+        ]);
+    }
+
+    #[test]
+    fn breaks_are_only_between_work_blocks() {
+        let mut et = EventTable::new();
+        let exact = et.add_spec(EventSpec::new("Exact".to_string(), at(4, 9, 0), at(4, 9, 50))).unwrap();
+        let et = run(et, vec![exact], 0, 2).unwrap();
+        // zero-length short break is not added, last block is work
+        assert_eq!(children(&et, exact), vec![
+            block("Exact (work 1/2)", at(4, 9, 0), at(4, 9, 25), "work"),
+            block("Exact (work 2/2)", at(4, 9, 25), at(4, 9, 50), "work"),
+        ]);
+    }
+
+    #[test]
+    fn repeated_and_hidden_events() {
+        let mut et = EventTable::new();
+        let focus = et.add_spec(EventSpec::new("Focus".to_string(), at(4, 9, 0), at(4, 9, 30))).unwrap();
+        let mut hidden = EventSpec::new("Hidden".to_string(), at(4, 12, 0), at(4, 13, 0));
+        hidden.fields.insert(HIDDEN_FIELD.to_string(), "rest".to_string());
+        let hidden = et.add_spec(hidden).unwrap();
+
+        let et = run(et, vec![focus, hidden, focus], 5, 4).unwrap();
+        assert_eq!(children(&et, focus).len(), 3);
+        assert_eq!(et.get_events().count(), 4);
+        assert!(et.get(hidden).is_some());
+
+        assert!(run(et.clone(), vec![], 5, 0).unwrap_err().contains("greater than 0"));
+        assert!(run(et, vec![focus], 5, 4).unwrap_err().contains("missing Event"));
+    }
+}
@@ -0,0 +1,314 @@
+//Responsible for Reschedule App: movable Events are reordered by priority and deadline within time they take
+
+use std::any::{Any, TypeId};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::LazyLock;
+
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, NaiveTime};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::core::{get_input, App, BusyTime, DateTimeType, Event, EventTable, EventZone, IOListOfTypes, IOOutputType, IOType, R_E_AClass, R_E_AGeneric};
+use crate::std::filter::is_hidden;
+
+/// Event that ends after its deadline (event is UUID before splitting)
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Late {
+    pub event: Uuid,
+    pub deadline: DateTimeType,
+    pub end: DateTimeType,
+}
+
+/// Movable Event of one assignee, absolute times
+struct Movable {
+    uuid: Uuid,
+    zone: EventZone,
+    start: DateTimeType,
+    end: DateTimeType,
+    priority: i64,
+    deadline: Option<DateTimeType>,
+}
+
+/// Fixed busy time and movable Events of one assignee
+type Group = (Vec<(DateTimeType, DateTimeType)>, Vec<Movable>);
+
+/// Reorders movable Events of each assignee (same grouping as Compact) so that higher priority and earlier deadline come first.
+/// Events are placed into time that movable Events took before (without pinned Events), so free time stays free;
+/// Event that does not fit into one piece of that time is split into parts, Events that do not fit at all are placed after it.
+/// Pinned Events, Events starting before from, hidden, all-day, recurring and zero-length Events are not moved.
+/// Inputs: "priority_field": String (integer, missing → 0), "deadline_field": String (RFC 3339 time, local time "2026-05-04T17:00:00" or date "2026-05-04" meaning end of that day in zone),
+///         "assignee_field": String, "pinned_field": String (empty → nothing is pinned), "from": DateTimeType, "zone": Tz
+/// Output: ET with reordered Events (split Events get " (part 1/n)" suffixes); report "late": Vec<Late>
+pub struct Reschedule;
+
+impl R_E_AGeneric for Reschedule {
+    fn get_name(&self)->&'static str {
+        "Reschedule"
+    }
+    fn get_class(&self)->&'static R_E_AClass {
+        &R_E_AClass::App
+    }
+    fn get_inputs(&self)->&'static IOListOfTypes {
+        static INPUTS: LazyLock<IOListOfTypes> = LazyLock::new(|| HashMap::from([
+            ("priority_field".to_string(), TypeId::of::<String>()),
+            ("deadline_field".to_string(), TypeId::of::<String>()),
+            ("assignee_field".to_string(), TypeId::of::<String>()),
+            ("pinned_field".to_string(), TypeId::of::<String>()),
+            ("from".to_string(), TypeId::of::<DateTimeType>()),
+            ("zone".to_string(), TypeId::of::<Tz>()),
+        ]));
+        &INPUTS
+    }
+}
+
+impl App for Reschedule {
+    fn get_outputs(&self)->&'static IOListOfTypes {
+        static OUTPUTS: LazyLock<IOListOfTypes> = LazyLock::new(|| HashMap::from([
+            ("late".to_string(), TypeId::of::<Vec<Late>>()),
+        ]));
+        &OUTPUTS
+    }
+
+    fn run(&self,
+        et: EventTable,
+        inputs: IOType
+    )->Result<EventTable, String> {
+        self.run_with_outputs(et, inputs).map(|(et, _)| et)
+    }
+
+    fn run_with_outputs(&self,
+        mut et: EventTable,
+        inputs: IOType
+    )->Result<(EventTable, IOOutputType), String> {
+        let priority_field = get_input::<String>(&inputs, "priority_field")?;
+        let deadline_field = get_input::<String>(&inputs, "deadline_field")?;
+        let assignee_field = get_input::<String>(&inputs, "assignee_field")?;
+        let pinned_field = get_input::<String>(&inputs, "pinned_field")?;
+        let from = *get_input::<DateTimeType>(&inputs, "from")?;
+        let zone = *get_input::<Tz>(&inputs, "zone")?;
+
+        // assignee → (fixed busy time, movable Events)
+        let mut groups: BTreeMap<String, Group> = BTreeMap::new();
+        for event in et.get_events() {
+            if is_hidden(event) || event.all_day.is_some() || event.recurrence.is_some() || event.start == event.end {
+                continue;
+            }
+            let assignee = event.fields.get(assignee_field).cloned().unwrap_or_default();
+            let (start, end) = event.timed_in(zone);
+            let (fixed, movable) = groups.entry(assignee).or_default();
+            if start < from || (!pinned_field.is_empty() && event.fields.contains_key(pinned_field)) {
+                fixed.push((start, end));
+                continue;
+            }
+            let (priority, deadline) = priority_and_deadline(event, priority_field, deadline_field, zone)
+                .map_err(|e| format!("Reschedule: Event {}: {}", event.uuid, e))?;
+            movable.push(Movable { uuid: event.uuid, zone: event.zone, start, end, priority, deadline });
+        }
+
+        let mut late = Vec::new();
+        for (fixed, mut movable) in groups.into_values() {
+            let fixed = BusyTime::new(fixed);
+            let slots = available(&fixed, &movable);
+            movable.sort_by_key(|m| (-m.priority, m.deadline.is_none(), m.deadline, m.start, m.uuid));
+
+            let mut slots = slots.into_iter();
+            let mut slot = slots.next();
+            let mut cursor = DateTimeType::MIN_UTC; // end of last piece
+            for m in movable {
+                let mut pieces: Vec<(DateTimeType, DateTimeType)> = Vec::new();
+                let mut remaining = m.end - m.start;
+                while remaining > Duration::zero() {
+                    let (piece_start, piece_end) = match slot {
+                        Some((slot_start, slot_end)) => {
+                            let piece_end = slot_end.min(slot_start + remaining);
+                            slot = if piece_end < slot_end { Some((piece_end, slot_end)) } else { slots.next() };
+                            (slot_start, piece_end)
+                        }
+                        // no time left: after everything of assignee
+                        None => {
+                            let start = fixed.free_slot(cursor, remaining);
+                            (start, start + remaining)
+                        }
+                    };
+                    pieces.push((piece_start, piece_end));
+                    remaining -= piece_end - piece_start;
+                    cursor = piece_end;
+                }
+
+                let new_end = pieces[pieces.len() - 1].1;
+                if let Some(deadline) = m.deadline.filter(|deadline| new_end > *deadline) {
+                    late.push(Late { event: m.uuid, deadline, end: new_end });
+                }
+                let stored = |t: DateTimeType| match m.zone {
+                    EventZone::Floating => EventZone::Tz(zone).to_local(t).and_utc(),
+                    EventZone::Utc | EventZone::Tz(_) => t,
+                };
+                if pieces.len() == 1 {
+                    if pieces[0] != (m.start, m.end) {
+                        et.set_times(m.uuid, stored(pieces[0].0), stored(pieces[0].1)).map_err(|e| format!("Reschedule: {}", e))?;
+                    }
+                    continue;
+                }
+                let n = pieces.len();
+                let parts = pieces.into_iter().enumerate()
+                    .map(|(i, (start, end))| (stored(start), stored(end), format!(" (part {}/{})", i + 1, n)))
+                    .collect();
+                et.split(m.uuid, parts).map_err(|e| format!("Reschedule: {}", e))?;
+            }
+        }
+        Ok((et, HashMap::from([
+            ("late".to_string(), Box::new(late) as Box<dyn Any+Send+Sync>),
+        ])))
+    }
+}
+
+/// Time taken by movable Events without fixed time, ordered pieces
+fn available(fixed: &BusyTime, movable: &[Movable])->Vec<(DateTimeType, DateTimeType)> {
+    let mut taken: Vec<(DateTimeType, DateTimeType)> = movable.iter().map(|m| (m.start, m.end)).collect();
+    taken.sort();
+    let mut union: Vec<(DateTimeType, DateTimeType)> = Vec::new();
+    for (start, end) in taken {
+        match union.last_mut() {
+            Some(last) if start <= last.1 => last.1 = last.1.max(end),
+            _ => union.push((start, end)),
+        }
+    }
+
+    let mut slots = Vec::new();
+    for (mut start, end) in union {
+        for (fixed_start, fixed_end) in fixed.after(start) {
+            if *fixed_start >= end {
+                break;
+            }
+            if *fixed_start > start {
+                slots.push((start, *fixed_start));
+            }
+            start = *fixed_end;
+        }
+        if start < end {
+            slots.push((start, end));
+        }
+    }
+    slots
+}
+
+/// Priority (missing → 0) and deadline (missing → None) of Event
+fn priority_and_deadline(event: &Event,
+    priority_field: &str,
+    deadline_field: &str,
+    zone: Tz
+)->Result<(i64, Option<DateTimeType>), String> {
+    let priority = match event.fields.get(priority_field) {
+        Some(value) => value.trim().parse::<i64>().map_err(|_| format!("invalid priority \"{}\"", value))?,
+        None => 0,
+    };
+    let deadline = match event.fields.get(deadline_field).map(|value| value.trim()).filter(|value| !value.is_empty()) {
+        Some(value) => Some(parse_deadline(value, zone).ok_or(format!("invalid deadline \"{}\"", value))?),
+        None => None,
+    };
+    Ok((priority, deadline))
+}
+
+/// RFC 3339 time, local time in zone or date (end of that day in zone)
+fn parse_deadline(value: &str, zone: Tz)->Option<DateTimeType> {
+    if let Ok(t) = DateTime::parse_from_rfc3339(value) {
+        return Some(t.to_utc());
+    }
+    let zone = EventZone::Tz(zone);
+    if let Ok(t) = NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S") {
+        return Some(zone.resolve_local(t));
+    }
+    let date = NaiveDate::parse_from_str(value, "%Y-%m-%d").ok()?;
+    Some(zone.resolve_local((date + Duration::days(1)).and_time(NaiveTime::MIN)))
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::EventSpec;
+    use crate::core::testing::{at, times, TestInputs};
+
+    fn add(et: &mut EventTable, name: &str, start: DateTimeType, end: DateTimeType, fields: &[(&str, &str)])->Uuid {
+        let mut spec = EventSpec::new(name.to_string(), start, end);
+        spec.fields.insert("assignee".to_string(), "alice".to_string());
+        spec.fields.extend(fields.iter().map(|(k, v)| (k.to_string(), v.to_string())));
+        et.add_spec(spec).unwrap()
+    }
+
+    fn run(et: EventTable, from: DateTimeType)->Result<(EventTable, Vec<Late>), String> {
+        let inputs = TestInputs::new()
+            .with("priority_field", "priority".to_string())
+            .with("deadline_field", "deadline".to_string())
+            .with("assignee_field", "assignee".to_string())
+            .with("pinned_field", "pinned".to_string())
+            .with("from", from)
+            .with("zone", chrono_tz::UTC);
+        let (et, mut outputs) = Reschedule.run_with_outputs(et, inputs.get())?;
+        Ok((et, *outputs.remove("late").unwrap().downcast::<Vec<Late>>().unwrap()))
+    }
+
+    /// (start, end) of Event with name (split parts have new UUIDs)
+    fn times_named(et: &EventTable, name: &str)->(DateTimeType, DateTimeType) {
+        times(et, et.get_events().find(|e| e.get_name() == name).unwrap().uuid)
+    }
+
+    #[test]
+    fn events_are_reordered_by_priority_and_deadline() {
+        // movable Events take 09:00-11:00 and 11:30-13:00, pinned Event stays at 11:00-11:30
+        let mut et = EventTable::new();
+        add(&mut et, "Early", at(4, 8, 0), at(4, 9, 0), &[("priority", "9")]);
+        add(&mut et, "Plain", at(4, 9, 0), at(4, 10, 0), &[]);
+        add(&mut et, "Urgent", at(4, 10, 0), at(4, 11, 0), &[("priority", " 5 ")]);
+        add(&mut et, "Pinned", at(4, 11, 0), at(4, 11, 30), &[("pinned", "")]);
+        let report = add(&mut et, "Report", at(4, 11, 30), at(4, 13, 0), &[("deadline", "2026-05-04T11:45:00")]);
+
+        let (et, late) = run(et, at(4, 9, 0)).unwrap();
+        assert_eq!(times_named(&et, "Early"), (at(4, 8, 0), at(4, 9, 0)));
+        assert_eq!(times_named(&et, "Urgent"), (at(4, 9, 0), at(4, 10, 0)));
+        assert_eq!(times_named(&et, "Report (part 1/2)"), (at(4, 10, 0), at(4, 11, 0)));
+        assert_eq!(times_named(&et, "Report (part 2/2)"), (at(4, 11, 30), at(4, 12, 0)));
+        assert_eq!(times_named(&et, "Pinned"), (at(4, 11, 0), at(4, 11, 30)));
+        assert_eq!(times_named(&et, "Plain"), (at(4, 12, 0), at(4, 13, 0)));
+        assert!(et.get(report).is_none());
+        assert!(et.get_events().filter(|e| e.get_name().starts_with("Report (part")).all(|e| e.parent == Some(report)));
+        assert_eq!(late, vec![Late { event: report, deadline: at(4, 11, 45), end: at(4, 12, 0) }]);
+    }
+
+    #[test]
+    fn events_that_do_not_fit_go_after_everything() {
+        // overlapping Events take only 09:00-10:30 together, rest goes after pinned Event
+        let mut et = EventTable::new();
+        add(&mut et, "First", at(4, 9, 0), at(4, 10, 0), &[]);
+        add(&mut et, "Second", at(4, 9, 30), at(4, 10, 30), &[("priority", "1")]);
+        add(&mut et, "Pinned", at(4, 10, 30), at(4, 11, 0), &[("pinned", "")]);
+        let (et, late) = run(et, at(4, 9, 0)).unwrap();
+        assert_eq!(times_named(&et, "Second"), (at(4, 9, 0), at(4, 10, 0)));
+        assert_eq!(times_named(&et, "First (part 1/2)"), (at(4, 10, 0), at(4, 10, 30)));
+        assert_eq!(times_named(&et, "First (part 2/2)"), (at(4, 11, 0), at(4, 11, 30)));
+        assert_eq!(late, vec![]);
+    }
+
+    #[test]
+    fn wrong_fields_are_rejected() {
+        let mut et = EventTable::new();
+        add(&mut et, "Task", at(4, 9, 0), at(4, 10, 0), &[("priority", "high")]);
+        assert!(run(et, at(4, 9, 0)).unwrap_err().contains("invalid priority \"high\""));
+
+        let mut et = EventTable::new();
+        add(&mut et, "Task", at(4, 9, 0), at(4, 10, 0), &[("deadline", "tomorrow")]);
+        assert!(run(et, at(4, 9, 0)).unwrap_err().contains("invalid deadline \"tomorrow\""));
+    }
+
+    #[test]
+    fn deadlines_are_parsed_in_zone() {
+        let berlin = chrono_tz::Europe::Berlin;
+        assert_eq!(parse_deadline("2026-05-04T12:00:00+02:00", berlin), Some(at(4, 10, 0)));
+        assert_eq!(parse_deadline("2026-05-04T12:00:00", berlin), Some(at(4, 10, 0)));
+        assert_eq!(parse_deadline("2026-05-04", berlin), Some(at(4, 22, 0)));
+        assert_eq!(parse_deadline("2026-05-04", chrono_tz::UTC), Some(at(4, 0, 0) + Duration::days(1)));
+        assert_eq!(parse_deadline("04.05.2026", berlin), None);
+    }
+}
@@ -0,0 +1,569 @@
+//Responsible for task scheduling: Task type (readable from SQL), TaskQuery Extension and Scheduler App that packs tasks into free working time of assignees
+
+use std::any::{Any, TypeId};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::LazyLock;
+
+use chrono::Duration;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::core::{get_input, App, DateTimeType, EventSpec, EventTable, Extension, IOListOfTypes, IOOutputType, IOType, R_E_AClass, R_E_AGeneric};
+use crate::std::filter::is_hidden;
+use crate::std::sql::{FromSqlRow, SqlPort, SqlQuery, SqlRow, SqlType};
+use crate::std::working_hours::WorkingTime;
+
+/// Namespace of UUIDs of scheduled tasks, so same task always gives same UUIDs
+const TASK_NAMESPACE: Uuid = Uuid::from_u128(0x3b8e_91c4_5d2a_4e7f_a1b6_0c9d_8e2f_4a71);
+
+/// Field of scheduled Events with id of their task
+pub const TASK_FIELD: &str = "task";
+
+/// Tasks are placed at most this far after start of planning
+const HORIZON_DAYS: i64 = 366;
+
+/// Free time shorter than this is not used for part of task (except last part)
+const MIN_PIECE_MINUTES: i64 = 15;
+
+/// Placement attempts of backtracking search before falling back to greedy placement
+const SEARCH_LIMIT: usize = 10_000;
+
+/// Work to be scheduled
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Task {
+    /// Unique, used in dependencies and TASK_FIELD
+    pub id: String,
+    pub name: String,
+    /// Estimated time of execution
+    pub minutes: u32,
+    #[serde(default)]
+    pub earliest: Option<DateTimeType>,
+    #[serde(default)]
+    pub deadline: Option<DateTimeType>,
+    /// Higher is scheduled first
+    #[serde(default)]
+    pub priority: i64,
+    /// People who can execute task (values of assignee field of Events)
+    pub assignees: Vec<String>,
+    /// Ids of tasks that must be finished before this one starts
+    #[serde(default)]
+    pub dependencies: Vec<String>,
+    /// Copied to scheduled Events (e.g. location)
+    #[serde(default)]
+    pub fields: BTreeMap<String, String>,
+}
+
+/// Columns: id, name, minutes, earliest, deadline, priority, assignees (separated by ','), dependencies (separated by ','),
+/// optional columns may be NULL. Other columns become fields
+impl FromSqlRow for Task {
+    fn from_row(row: &SqlRow)->Result<Self, String> {
+        const COLUMNS: [&str; 8] = ["id", "name", "minutes", "earliest", "deadline", "priority", "assignees", "dependencies"];
+        let optional = |column: &str| if row.is_null(column) { Ok(None) } else { row.datetime(column).map(Some) };
+        Ok(Self {
+            id: row.text("id")?,
+            name: row.text("name")?,
+            minutes: u32::try_from(row.duration("minutes")?.num_minutes()).map_err(|_| "Column minutes is negative".to_string())?,
+            earliest: optional("earliest")?,
+            deadline: optional("deadline")?,
+            priority: if row.is_null("priority") { 0 } else { row.integer("priority")? },
+            assignees: row.list("assignees")?,
+            dependencies: row.list("dependencies")?,
+            fields: row.values.keys()
+                .filter(|column| !COLUMNS.contains(&column.as_str()))
+                .filter_map(|column| Some((column.clone(), row.text(column).ok()?)))
+                .collect(),
+        })
+    }
+}
+
+/// Port of SqlQuery that reads Vec<Task>, query must return all columns of Task (see FromSqlRow for Task)
+pub fn task_port(name: &str, query: &str)->SqlPort {
+    SqlPort::new::<Task>(name.to_string(), query.to_string(), vec![
+        ("minutes".to_string(), SqlType::Minutes),
+        ("earliest".to_string(), SqlType::DateTime),
+        ("deadline".to_string(), SqlType::DateTime),
+        ("priority".to_string(), SqlType::Integer),
+        ("assignees".to_string(), SqlType::List),
+        ("dependencies".to_string(), SqlType::List),
+    ])
+}
+
+/// Task that Scheduler could not place
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Unplaced {
+    pub task: String,
+    pub reason: String,
+}
+
+
+
+// ==========TASK QUERY EXTENSION==========
+
+/// Reads tasks from SQLite database (opened read-only), see task_port()
+/// Inputs: "path": String, "query": String
+/// Output: "tasks": Vec<Task>
+pub struct TaskQuery;
+
+impl R_E_AGeneric for TaskQuery {
+    fn get_name(&self)->&'static str {
+        "TaskQuery"
+    }
+    fn get_class(&self)->&'static R_E_AClass {
+        &R_E_AClass::Extension
+    }
+    fn get_inputs(&self)->&'static IOListOfTypes {
+        static INPUTS: LazyLock<IOListOfTypes> = LazyLock::new(|| HashMap::from([
+            ("path".to_string(), TypeId::of::<String>()),
+            ("query".to_string(), TypeId::of::<String>()),
+        ]));
+        &INPUTS
+    }
+}
+
+impl Extension for TaskQuery {
+    fn get_outputs(&self)->&'static IOListOfTypes {
+        static OUTPUTS: LazyLock<IOListOfTypes> = LazyLock::new(|| HashMap::from([
+            ("tasks".to_string(), TypeId::of::<Vec<Task>>()),
+        ]));
+        &OUTPUTS
+    }
+
+    fn run(&self,
+        inputs: IOType
+    )->Result<IOOutputType, String> {
+        let query = get_input::<String>(&inputs, "query")?;
+        SqlQuery::new(vec![task_port("tasks", query)])?.named(self.get_name()).run(inputs)
+    }
+}
+
+
+
+// ==========SCHEDULER APP==========
+
+/// Places tasks into free working time of their assignees. Events with assignee field block only that assignee, Events without it block everyone.
+/// Tasks may be split into parts between busy Events. Backtracking search tries to place all tasks before their deadlines,
+/// if it is impossible, tasks are placed greedily (higher priority and earlier deadline first) and rest is reported.
+/// Tasks already in ET (Events with TASK_FIELD) are not placed again.
+/// Inputs: "tasks": Vec<Task>, "working_time": WorkingTime, "assignee_field": String (e.g. "assignee"), "from": DateTimeType (start of planning)
+/// Output: ET with task Events (fields of task, assignee field and TASK_FIELD); report "unplaced": Vec<Unplaced>
+pub struct Scheduler;
+
+impl R_E_AGeneric for Scheduler {
+    fn get_name(&self)->&'static str {
+        "Scheduler"
+    }
+    fn get_class(&self)->&'static R_E_AClass {
+        &R_E_AClass::App
+    }
+    fn get_inputs(&self)->&'static IOListOfTypes {
+        static INPUTS: LazyLock<IOListOfTypes> = LazyLock::new(|| HashMap::from([
+            ("tasks".to_string(), TypeId::of::<Vec<Task>>()),
+            ("working_time".to_string(), TypeId::of::<WorkingTime>()),
+            ("assignee_field".to_string(), TypeId::of::<String>()),
+            ("from".to_string(), TypeId::of::<DateTimeType>()),
+        ]));
+        &INPUTS
+    }
+}
+
+impl App for Scheduler {
+    fn get_outputs(&self)->&'static IOListOfTypes {
+        static OUTPUTS: LazyLock<IOListOfTypes> = LazyLock::new(|| HashMap::from([
+            ("unplaced".to_string(), TypeId::of::<Vec<Unplaced>>()),
+        ]));
+        &OUTPUTS
+    }
+
+    fn run(&self,
+        et: EventTable,
+        inputs: IOType
+    )->Result<EventTable, String> {
+        self.run_with_outputs(et, inputs).map(|(et, _)| et)
+    }
+
+    fn run_with_outputs(&self,
+        mut et: EventTable,
+        inputs: IOType
+    )->Result<(EventTable, IOOutputType), String> {
+        let tasks = get_input::<Vec<Task>>(&inputs, "tasks")?;
+        let working_time = get_input::<WorkingTime>(&inputs, "working_time")?;
+        let field = get_input::<String>(&inputs, "assignee_field")?;
+        let from = *get_input::<DateTimeType>(&inputs, "from")?;
+        working_time.check().map_err(|e| format!("Scheduler: {}", e))?;
+
+        let mut ids = HashSet::new();
+        if let Some(task) = tasks.iter().find(|task| !ids.insert(task.id.as_str())) {
+            return Err(format!("Scheduler: task id {} is used twice", task.id));
+        }
+
+        let mut state = State::new(&et, working_time, field, from);
+        let (order, mut unplaced) = order(tasks, &state.finished);
+        let order: Vec<&Task> = order.into_iter().filter(|task| !state.finished.contains_key(&task.id)).collect();
+
+        let mut steps = 0;
+        let mut placements = Vec::new();
+        if !search(&order, &mut state, &mut placements, &mut steps) {
+            placements.clear();
+            for task in &order {
+                if let Some(dependency) = task.dependencies.iter().find(|d| !state.finished.contains_key(*d)) {
+                    unplaced.push(Unplaced { task: task.id.clone(), reason: format!("depends on unplaced task {}", dependency) });
+                    continue;
+                }
+                match state.candidates(task).into_iter().next() {
+                    Some(placement) => {
+                        state.apply(&placement);
+                        placements.push(placement);
+                    }
+                    None => unplaced.push(Unplaced { task: task.id.clone(), reason: state.failure(task) }),
+                }
+            }
+        }
+
+        for placement in placements {
+            let task = order.iter().find(|task| task.id == placement.task).ok_or("Scheduler: placed unknown task")?;
+            let n = placement.pieces.len();
+            for (i, (start, end)) in placement.pieces.iter().enumerate() {
+                let mut spec = EventSpec::new(
+                    if n == 1 { task.name.clone() } else { format!("{} (part {}/{})", task.name, i + 1, n) },
+                    *start, *end,
+                );
+                spec.uuid = Some(Uuid::new_v5(&TASK_NAMESPACE, format!("{}/{}", task.id, i).as_bytes()));
+                spec.fields = task.fields.clone();
+                spec.fields.insert(field.clone(), placement.assignee.clone());
+                spec.fields.insert(TASK_FIELD.to_string(), task.id.clone());
+                et.add_spec(spec).map_err(|e| format!("Scheduler: task {}: {}", task.id, e))?;
+            }
+        }
+        Ok((et, HashMap::from([
+            ("unplaced".to_string(), Box::new(unplaced) as Box<dyn Any+Send+Sync>),
+        ])))
+    }
+}
+
+/// Tasks in order of placement: dependencies first, then higher priority, earlier deadline. Tasks in dependency cycles or with unknown dependencies are reported
+fn order<'a>(tasks: &'a [Task],
+    finished: &HashMap<String, DateTimeType>
+)->(Vec<&'a Task>, Vec<Unplaced>) {
+    let known: HashSet<&str> = tasks.iter().map(|task| task.id.as_str()).collect();
+    let mut unplaced = Vec::new();
+    let mut waiting: Vec<&Task> = Vec::new();
+    for task in tasks {
+        match task.dependencies.iter().find(|d| !known.contains(d.as_str()) && !finished.contains_key(*d)) {
+            Some(dependency) => unplaced.push(Unplaced { task: task.id.clone(), reason: format!("depends on unknown task {}", dependency) }),
+            None => waiting.push(task),
+        }
+    }
+
+    let mut ordered: Vec<&Task> = Vec::new();
+    let mut done: HashSet<&str> = HashSet::new();
+    loop {
+        let ready = waiting.iter().enumerate()
+            .filter(|(_, task)| task.dependencies.iter().all(|d| done.contains(d.as_str()) || !known.contains(d.as_str())))
+            .min_by_key(|(_, task)| (-task.priority, task.deadline.is_none(), task.deadline, task.id.clone()))
+            .map(|(i, _)| i);
+        let Some(i) = ready else { break };
+        let task = waiting.remove(i);
+        done.insert(&task.id);
+        ordered.push(task);
+    }
+    unplaced.extend(waiting.into_iter().map(|task| Unplaced { task: task.id.clone(), reason: "dependency cycle".to_string() }));
+    (ordered, unplaced)
+}
+
+/// Tries to place all tasks before their deadlines, candidates of each task are tried in order of their finish
+fn search(tasks: &[&Task],
+    state: &mut State,
+    placements: &mut Vec<Placement>,
+    steps: &mut usize
+)->bool {
+    let Some((task, rest)) = tasks.split_first() else { return true };
+    for placement in state.candidates(task) {
+        *steps += 1;
+        if *steps > SEARCH_LIMIT {
+            return false;
+        }
+        state.apply(&placement);
+        placements.push(placement);
+        if search(rest, state, placements, steps) {
+            return true;
+        }
+        if let Some(placement) = placements.pop() {
+            state.undo(&placement);
+        }
+    }
+    false
+}
+
+struct Placement {
+    task: String,
+    assignee: String,
+    pieces: Vec<(DateTimeType, DateTimeType)>,
+}
+
+/// Busy time of assignees and finish times of placed tasks
+struct State<'a> {
+    working_time: &'a WorkingTime,
+    from: DateTimeType,
+    /// Busy time of Events without assignee field
+    common: Vec<(DateTimeType, DateTimeType)>,
+    busy: HashMap<String, Vec<(DateTimeType, DateTimeType)>>,
+    /// Task id → end of its last part
+    finished: HashMap<String, DateTimeType>,
+}
+
+impl<'a> State<'a> {
+    fn new(et: &EventTable, working_time: &'a WorkingTime, field: &str, from: DateTimeType)->Self {
+        let horizon = from + Duration::days(HORIZON_DAYS);
+        let mut state = Self { working_time, from, common: Vec::new(), busy: HashMap::new(), finished: HashMap::new() };
+        for event in et.get_events().filter(|e| !is_hidden(e)) {
+            if let Some(task) = event.fields.get(TASK_FIELD) {
+                let end = state.finished.entry(task.clone()).or_insert(event.end);
+                *end = (*end).max(event.end);
+            }
+            let (start, end) = event.timed_in(working_time.zone);
+            let occurrences = match event.recurrence {
+                Some(_) => et.occurrences(event.uuid, from, horizon).unwrap_or_default().into_iter()
+                    .map(|(s, e)| (s + (start - event.start), e + (end - event.end)))
+                    .collect(),
+                None => vec![(start, end)],
+            };
+            let list = match event.fields.get(field) {
+                Some(assignee) => state.busy.entry(assignee.clone()).or_default(),
+                None => &mut state.common,
+            };
+            list.extend(occurrences.into_iter().filter(|(s, e)| s < e && *e > from && *s < horizon));
+        }
+        state.common.sort();
+        state.busy.values_mut().for_each(|list| list.sort());
+        state
+    }
+
+    /// Earliest start of task: after planning start, its earliest start and its dependencies
+    fn earliest(&self, task: &Task)->Option<DateTimeType> {
+        let mut t = self.from.max(task.earliest.unwrap_or(self.from));
+        for dependency in &task.dependencies {
+            t = t.max(*self.finished.get(dependency)?);
+        }
+        Some(t)
+    }
+
+    /// Possible placements (one per assignee) that finish before deadline, earliest finish first
+    fn candidates(&self, task: &Task)->Vec<Placement> {
+        let Some(earliest) = self.earliest(task) else { return Vec::new() };
+        let length = Duration::minutes(task.minutes as i64);
+        let mut candidates: Vec<Placement> = task.assignees.iter()
+            .filter_map(|assignee| {
+                let pieces = self.pieces(assignee, earliest, length, task.deadline)?;
+                Some(Placement { task: task.id.clone(), assignee: assignee.clone(), pieces })
+            })
+            .collect();
+        candidates.sort_by_key(|p| (p.pieces[p.pieces.len() - 1].1, p.pieces.len(), p.assignee.clone()));
+        candidates
+    }
+
+    /// Free working time of assignee of total length from t, None if it does not end before deadline or horizon
+    fn pieces(&self,
+        assignee: &str,
+        t: DateTimeType,
+        length: Duration,
+        deadline: Option<DateTimeType>
+    )->Option<Vec<(DateTimeType, DateTimeType)>> {
+        let limit = deadline.unwrap_or(DateTimeType::MAX_UTC).min(self.from + Duration::days(HORIZON_DAYS));
+        let no_busy = Vec::new();
+        let own = self.busy.get(assignee).unwrap_or(&no_busy);
+        let mut pieces = Vec::new();
+        let mut remaining = length;
+        if remaining <= Duration::zero() {
+            return Some(vec![(t, t)]);
+        }
+        for (from, to) in self.working_time.windows_after(t) {
+            if from >= limit {
+                return None;
+            }
+            let mut blocking: Vec<&(DateTimeType, DateTimeType)> = self.common.iter().chain(own)
+                .filter(|(s, e)| *s < to && *e > from)
+                .collect();
+            blocking.sort();
+            let mut cursor = from;
+            for (s, e) in blocking.into_iter().map(|(s, e)| (*s, *e)).chain([(to, to)]) {
+                if s > cursor {
+                    let piece = (s - cursor).min(remaining);
+                    if piece == remaining || piece >= Duration::minutes(MIN_PIECE_MINUTES) {
+                        pieces.push((cursor, cursor + piece));
+                        remaining -= piece;
+                        if remaining <= Duration::zero() {
+                            return (cursor + piece <= limit).then_some(pieces);
+                        }
+                    }
+                }
+                cursor = cursor.max(e);
+            }
+        }
+        None
+    }
+
+    fn apply(&mut self, placement: &Placement) {
+        let busy = self.busy.entry(placement.assignee.clone()).or_default();
+        busy.extend(&placement.pieces);
+        busy.sort();
+        self.finished.insert(placement.task.clone(), placement.pieces[placement.pieces.len() - 1].1);
+    }
+
+    fn undo(&mut self, placement: &Placement) {
+        if let Some(busy) = self.busy.get_mut(&placement.assignee) {
+            busy.retain(|piece| !placement.pieces.contains(piece));
+        }
+        self.finished.remove(&placement.task);
+    }
+
+    /// Why task has no candidates
+    fn failure(&self, task: &Task)->String {
+        if task.assignees.is_empty() {
+            return "no eligible assignees".to_string();
+        }
+        let Some(earliest) = self.earliest(task) else { return "dependencies are not placed".to_string() };
+        let length = Duration::minutes(task.minutes as i64);
+        match (task.deadline, task.assignees.iter().any(|a| self.pieces(a, earliest, length, None).is_some())) {
+            (Some(deadline), true) => format!("not enough free time before deadline {}", deadline),
+            _ => format!("no free time within {} days", HORIZON_DAYS),
+        }
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveTime;
+    use crate::core::testing::{at, TestInputs};
+
+    fn task(id: &str, minutes: u32, priority: i64, assignees: &[&str], deadline: Option<DateTimeType>)->Task {
+        Task {
+            id: id.to_string(),
+            name: id.to_uppercase(),
+            minutes,
+            earliest: None,
+            deadline,
+            priority,
+            assignees: assignees.iter().map(|a| a.to_string()).collect(),
+            dependencies: Vec::new(),
+            fields: BTreeMap::new(),
+        }
+    }
+
+    fn busy(et: &mut EventTable, assignee: Option<&str>, start: DateTimeType, end: DateTimeType) {
+        let mut spec = EventSpec::new("Busy".to_string(), start, end);
+        if let Some(assignee) = assignee {
+            spec.fields.insert("assignee".to_string(), assignee.to_string());
+        }
+        et.add_spec(spec).unwrap();
+    }
+
+    /// 09:00-17:00 UTC from Monday to Friday, planning from Monday 2026-05-04
+    fn run(et: EventTable, tasks: Vec<Task>)->(EventTable, Vec<Unplaced>) {
+        let day = vec![(NaiveTime::from_hms_opt(9, 0, 0).unwrap(), NaiveTime::from_hms_opt(17, 0, 0).unwrap())];
+        let working_time = WorkingTime {
+            zone: chrono_tz::UTC,
+            weekly: [day.clone(), day.clone(), day.clone(), day.clone(), day, Vec::new(), Vec::new()],
+            exceptions: BTreeMap::new(),
+            holidays: Default::default(),
+        };
+        let inputs = TestInputs::new()
+            .with("tasks", tasks)
+            .with("working_time", working_time)
+            .with("assignee_field", "assignee".to_string())
+            .with("from", at(4, 0, 0));
+        let (et, mut outputs) = Scheduler.run_with_outputs(et, inputs.get()).unwrap();
+        (et, *outputs.remove("unplaced").unwrap().downcast::<Vec<Unplaced>>().unwrap())
+    }
+
+    /// (task, assignee, start, end) of scheduled Events
+    fn placed(et: &EventTable)->Vec<(String, String, DateTimeType, DateTimeType)> {
+        let mut placed: Vec<_> = et.get_events()
+            .filter_map(|e| Some((e.fields.get(TASK_FIELD)?.clone(), e.fields["assignee"].clone(), e.start, e.end)))
+            .collect();
+        placed.sort();
+        placed
+    }
+
+    #[test]
+    fn backtracking_meets_deadlines() {
+        // greedy choice puts "a" on alice (finishes at 13:00), then "b" misses its deadline
+        let mut et = EventTable::new();
+        busy(&mut et, Some("bob"), at(4, 9, 0), at(4, 13, 0));
+        let tasks = vec![
+            task("a", 240, 1, &["alice", "bob"], None),
+            task("b", 480, 0, &["alice"], Some(at(4, 17, 0))),
+        ];
+        let (et, unplaced) = run(et, tasks.clone());
+        assert_eq!(unplaced, vec![]);
+        assert_eq!(placed(&et), vec![
+            ("a".to_string(), "bob".to_string(), at(4, 13, 0), at(4, 17, 0)),
+            ("b".to_string(), "alice".to_string(), at(4, 9, 0), at(4, 17, 0)),
+        ]);
+
+        // tasks in ET are not placed again
+        let (again, unplaced) = run(et.clone(), tasks);
+        assert_eq!(unplaced, vec![]);
+        assert_eq!(placed(&again), placed(&et));
+    }
+
+    #[test]
+    fn impossible_plan_falls_back_to_greedy() {
+        let mut dependent = task("d", 60, 5, &["alice"], None);
+        dependent.dependencies = vec!["b".to_string()];
+        let mut unknown = task("u", 60, 0, &["alice"], None);
+        unknown.dependencies = vec!["missing".to_string()];
+        let mut cycle_1 = task("x", 60, 0, &["alice"], None);
+        cycle_1.dependencies = vec!["y".to_string()];
+        let mut cycle_2 = task("y", 60, 0, &["alice"], None);
+        cycle_2.dependencies = vec!["x".to_string()];
+        let tasks = vec![
+            task("a", 240, 1, &["alice"], Some(at(4, 13, 0))),
+            task("b", 360, 0, &["alice"], Some(at(4, 15, 0))),
+            task("c", 60, 0, &["alice"], Some(at(4, 14, 0))),
+            task("n", 60, 0, &[], None),
+            dependent, unknown, cycle_1, cycle_2,
+        ];
+        let (et, unplaced) = run(EventTable::new(), tasks);
+
+        // "a" and "c" are placed by priority and deadline, "b" does not fit before its deadline
+        assert_eq!(placed(&et), vec![
+            ("a".to_string(), "alice".to_string(), at(4, 9, 0), at(4, 13, 0)),
+            ("c".to_string(), "alice".to_string(), at(4, 13, 0), at(4, 14, 0)),
+        ]);
+        let mut reasons: Vec<(String, String)> = unplaced.into_iter().map(|u| (u.task, u.reason)).collect();
+        reasons.sort();
+        assert_eq!(reasons, vec![
+            ("b".to_string(), format!("not enough free time before deadline {}", at(4, 15, 0))),
+            ("d".to_string(), "depends on unplaced task b".to_string()),
+            ("n".to_string(), "no eligible assignees".to_string()),
+            ("u".to_string(), "depends on unknown task missing".to_string()),
+            ("x".to_string(), "dependency cycle".to_string()),
+            ("y".to_string(), "dependency cycle".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn tasks_are_split_around_busy_time() {
+        // 10 minutes before common Event are too short for part of task
+        let mut et = EventTable::new();
+        busy(&mut et, None, at(4, 9, 10), at(4, 12, 0));
+        busy(&mut et, Some("bob"), at(4, 12, 0), at(4, 17, 0));
+        let mut second = task("b", 360, 0, &["alice"], None);
+        second.dependencies = vec!["a".to_string()];
+        second.fields.insert("location".to_string(), "Lab".to_string());
+        let (et, unplaced) = run(et, vec![task("a", 120, 1, &["alice", "bob"], None), second]);
+        assert_eq!(unplaced, vec![]);
+        assert_eq!(placed(&et), vec![
+            ("a".to_string(), "alice".to_string(), at(4, 12, 0), at(4, 14, 0)),
+            ("b".to_string(), "alice".to_string(), at(4, 14, 0), at(4, 17, 0)),
+            ("b".to_string(), "alice".to_string(), at(5, 9, 0), at(5, 12, 0)),
+        ]);
+        let mut parts: Vec<_> = et.get_events().filter(|e| e.fields.get(TASK_FIELD).is_some_and(|t| t == "b")).collect();
+        parts.sort_by_key(|e| e.start);
+        assert_eq!(parts.iter().map(|e| e.get_name()).collect::<Vec<_>>(), vec!["B (part 1/2)", "B (part 2/2)"]);
+        assert!(parts.iter().all(|e| e.fields["location"] == "Lab"));
+    }
+}
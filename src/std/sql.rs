@@ -0,0 +1,406 @@
+//Responsible for reading arbitrary data (coworkers, tasks, locations, ...) from SQL databases as typed rows for Apps
+
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+use chrono::{DateTime, Duration, NaiveDateTime};
+use rusqlite::types::ValueRef;
+use rusqlite::{Connection, OpenFlags};
+use serde::{Deserialize, Serialize};
+
+use crate::core::{get_input, DateTimeType, Extension, IOListOfTypes, IOOutputType, IOType, R_E_AClass, R_E_AGeneric};
+
+/// How value of column is read
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SqlType {
+    Text,
+    Integer,
+    Real,
+    /// Integer (0 = false) or "true"/"false"
+    Bool,
+    /// RFC 3339 text, "%Y-%m-%d %H:%M:%S" text in UTC or Unix time in seconds
+    DateTime,
+    /// Amount of minutes
+    Minutes,
+    /// Text with values separated by ',' (e.g. eligible people of task)
+    List,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum SqlValue {
+    Null,
+    Text(String),
+    Integer(i64),
+    Real(f64),
+    Bool(bool),
+    DateTime(DateTimeType),
+    Duration(Duration),
+    List(Vec<String>),
+}
+
+impl SqlValue {
+    /// Value of column read as sql_type. Columns without declared type keep type of SQLite value
+    fn read(value: ValueRef, sql_type: Option<SqlType>)->Result<Self, String> {
+        let text = |value: ValueRef| match value {
+            ValueRef::Integer(n) => n.to_string(),
+            ValueRef::Real(x) => x.to_string(),
+            ValueRef::Text(t) | ValueRef::Blob(t) => String::from_utf8_lossy(t).trim().to_string(),
+            ValueRef::Null => String::new(),
+        };
+        let wrong = |value: ValueRef| format!("can not read {} as {:?}", text(value), sql_type.unwrap_or(SqlType::Text));
+
+        Ok(match (sql_type, value) {
+            (_, ValueRef::Null) => Self::Null,
+            (None, ValueRef::Integer(n)) => Self::Integer(n),
+            (None, ValueRef::Real(x)) => Self::Real(x),
+            (None | Some(SqlType::Text), value) => Self::Text(text(value)),
+            (Some(SqlType::Integer), ValueRef::Integer(n)) => Self::Integer(n),
+            (Some(SqlType::Integer), value) => Self::Integer(text(value).parse().map_err(|_| wrong(value))?),
+            (Some(SqlType::Real), ValueRef::Real(x)) => Self::Real(x),
+            (Some(SqlType::Real), ValueRef::Integer(n)) => Self::Real(n as f64),
+            (Some(SqlType::Real), value) => Self::Real(text(value).parse().map_err(|_| wrong(value))?),
+            (Some(SqlType::Bool), ValueRef::Integer(n)) => Self::Bool(n != 0),
+            (Some(SqlType::Bool), value) => match text(value).to_lowercase().as_str() {
+                "true" | "1" => Self::Bool(true),
+                "false" | "0" => Self::Bool(false),
+                _ => return Err(wrong(value)),
+            },
+            (Some(SqlType::DateTime), ValueRef::Integer(n)) => Self::DateTime(DateTime::from_timestamp(n, 0).ok_or_else(|| wrong(value))?),
+            (Some(SqlType::DateTime), value) => {
+                let text = text(value);
+                let time = DateTime::parse_from_rfc3339(&text).map(|t| t.to_utc())
+                    .or_else(|_| NaiveDateTime::parse_from_str(&text, "%Y-%m-%d %H:%M:%S").map(|t| t.and_utc()))
+                    .map_err(|_| wrong(value))?;
+                Self::DateTime(time)
+            }
+            (Some(SqlType::Minutes), ValueRef::Integer(n)) => Self::Duration(Duration::minutes(n)),
+            (Some(SqlType::Minutes), value) => {
+                let minutes: f64 = text(value).parse().map_err(|_| wrong(value))?;
+                Self::Duration(Duration::seconds((minutes * 60.0).round() as i64))
+            }
+            (Some(SqlType::List), value) => Self::List(
+                text(value).split(',').map(str::trim).filter(|s| !s.is_empty()).map(String::from).collect()
+            ),
+        })
+    }
+}
+
+/// Row of query result, values are read according to SqlPort.columns
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SqlRow {
+    pub values: HashMap<String, SqlValue>,
+}
+
+impl SqlRow {
+    fn get(&self, column: &str)->Result<&SqlValue, String> {
+        match self.values.get(column) {
+            None => Err(format!("Column {} is missing", column)),
+            Some(SqlValue::Null) => Err(format!("Column {} is NULL", column)),
+            Some(value) => Ok(value),
+        }
+    }
+
+    /// true also if column is missing
+    pub fn is_null(&self, column: &str)->bool {
+        matches!(self.values.get(column), None | Some(SqlValue::Null))
+    }
+
+    pub fn text(&self, column: &str)->Result<String, String> {
+        match self.get(column)? {
+            SqlValue::Text(t) => Ok(t.clone()),
+            SqlValue::Integer(n) => Ok(n.to_string()),
+            SqlValue::Real(x) => Ok(x.to_string()),
+            value => Err(format!("Column {} is {:?}, not text", column, value)),
+        }
+    }
+    pub fn integer(&self, column: &str)->Result<i64, String> {
+        match self.get(column)? {
+            SqlValue::Integer(n) => Ok(*n),
+            value => Err(format!("Column {} is {:?}, not integer", column, value)),
+        }
+    }
+    pub fn datetime(&self, column: &str)->Result<DateTimeType, String> {
+        match self.get(column)? {
+            SqlValue::DateTime(t) => Ok(*t),
+            value => Err(format!("Column {} is {:?}, not time", column, value)),
+        }
+    }
+    pub fn duration(&self, column: &str)->Result<Duration, String> {
+        match self.get(column)? {
+            SqlValue::Duration(d) => Ok(*d),
+            value => Err(format!("Column {} is {:?}, not duration", column, value)),
+        }
+    }
+    /// NULL is empty list
+    pub fn list(&self, column: &str)->Result<Vec<String>, String> {
+        if self.is_null(column) {
+            return Ok(Vec::new());
+        }
+        match self.get(column)? {
+            SqlValue::List(l) => Ok(l.clone()),
+            value => Err(format!("Column {} is {:?}, not list", column, value)),
+        }
+    }
+}
+
+/// Type that can be built from row of query result (for example task of scheduler)
+pub trait FromSqlRow: Sized+Send+Sync+'static {
+    fn from_row(row: &SqlRow)->Result<Self, String>;
+}
+
+/// Untyped rows
+impl FromSqlRow for SqlRow {
+    fn from_row(row: &SqlRow)->Result<Self, String> {
+        Ok(row.clone())
+    }
+}
+
+/// Converts rows to boxed Vec<T> of port
+type RowsConverter = fn(Vec<SqlRow>)->Result<Box<dyn Any+Send+Sync>, String>;
+
+/// One output of SqlQuery: rows of query converted to Vec<T>
+pub struct SqlPort {
+    /// Name of output
+    pub name: String,
+    pub query: String,
+    /// (column, type). Columns that are not listed keep type of SQLite value
+    pub columns: Vec<(String, SqlType)>,
+    type_id: TypeId,
+    convert: RowsConverter,
+}
+
+impl SqlPort {
+    /// Port with output of type Vec<T>
+    pub fn new<T: FromSqlRow>(name: String,
+        query: String,
+        columns: Vec<(String, SqlType)>
+    )->Self {
+        Self {
+            name,
+            query,
+            columns,
+            type_id: TypeId::of::<Vec<T>>(),
+            convert: convert_rows::<T>,
+        }
+    }
+
+    fn run(&self, conn: &Connection)->Result<Box<dyn Any+Send+Sync>, String> {
+        let mut stmt = conn.prepare(&self.query).map_err(|e| format!("Wrong query: {}", e))?;
+        let names: Vec<String> = stmt.column_names().into_iter().map(String::from).collect();
+        if let Some((missing, _)) = self.columns.iter().find(|(column, _)| !names.contains(column)) {
+            return Err(format!("Query result has no column {}", missing));
+        }
+        let types: Vec<Option<SqlType>> = names.iter()
+            .map(|name| self.columns.iter().find(|(column, _)| column == name).map(|(_, t)| *t))
+            .collect();
+
+        let mut rows = stmt.query([]).map_err(|e| e.to_string())?;
+        let mut read = Vec::new();
+        while let Some(row) = rows.next().map_err(|e| e.to_string())? {
+            let number = read.len() + 1;
+            let mut values = HashMap::new();
+            for (i, name) in names.iter().enumerate() {
+                let value = row.get_ref(i).map_err(|e| format!("Row {}: {}", number, e))?;
+                let value = SqlValue::read(value, types[i]).map_err(|e| format!("Row {}: column {}: {}", number, name, e))?;
+                values.insert(name.clone(), value);
+            }
+            read.push(SqlRow { values });
+        }
+        (self.convert)(read)
+    }
+}
+
+fn convert_rows<T: FromSqlRow>(rows: Vec<SqlRow>)->Result<Box<dyn Any+Send+Sync>, String> {
+    let typed = rows.iter().enumerate()
+        .map(|(i, row)| T::from_row(row).map_err(|e| format!("Row {}: {}", i + 1, e)))
+        .collect::<Result<Vec<T>, String>>()?;
+    Ok(Box::new(typed))
+}
+
+
+
+// ==========QUERY EXTENSION==========
+
+/// Reads any data from SQLite database (opened read-only), one output per SqlPort
+/// Input: "path": String
+/// Outputs: "<name of port>": Vec<T> of port
+/// SqlQuery::from_inputs() (registered in std) has single port defined by inputs:
+/// Inputs: "path": String, "query": String, "columns": Vec<(String, SqlType)>
+/// Output: "rows": Vec<SqlRow>
+pub struct SqlQuery {
+    /// Name in errors and get_name(), e.g. of Extension that wraps SqlQuery
+    name: &'static str,
+    ports: Vec<SqlPort>,
+    outputs: IOListOfTypes,
+    /// Port "rows" is defined by inputs "query" and "columns"
+    from_inputs: bool,
+}
+
+impl SqlQuery {
+    pub fn new(ports: Vec<SqlPort>
+    )->Result<Self, String> {
+        let mut outputs = IOListOfTypes::new();
+        for port in &ports {
+            if outputs.insert(port.name.clone(), port.type_id).is_some() {
+                return Err(format!("SqlQuery: port {} is defined twice", port.name));
+            }
+        }
+        Ok(Self { name: "SqlQuery", ports, outputs, from_inputs: false })
+    }
+
+    /// Name used in errors instead of "SqlQuery", e.g. of Extension that runs this query
+    pub fn named(mut self, name: &'static str)->Self {
+        self.name = name;
+        self
+    }
+
+    /// SqlQuery that can be set up without code (from GUI or pipeline file): query and column types are inputs, rows are untyped
+    pub fn from_inputs()->Self {
+        Self {
+            name: "SqlQuery",
+            ports: Vec::new(),
+            outputs: HashMap::from([("rows".to_string(), TypeId::of::<Vec<SqlRow>>())]),
+            from_inputs: true,
+        }
+    }
+}
+
+impl R_E_AGeneric for SqlQuery {
+    fn get_name(&self)->&'static str {
+        self.name
+    }
+    fn get_class(&self)->&'static R_E_AClass {
+        &R_E_AClass::Extension
+    }
+    fn get_inputs(&self)->&'static IOListOfTypes {
+        static INPUTS: LazyLock<IOListOfTypes> = LazyLock::new(|| HashMap::from([
+            ("path".to_string(), TypeId::of::<String>()),
+        ]));
+        static FROM_INPUTS: LazyLock<IOListOfTypes> = LazyLock::new(|| HashMap::from([
+            ("path".to_string(), TypeId::of::<String>()),
+            ("query".to_string(), TypeId::of::<String>()),
+            ("columns".to_string(), TypeId::of::<Vec<(String, SqlType)>>()),
+        ]));
+        if self.from_inputs { &FROM_INPUTS } else { &INPUTS }
+    }
+}
+
+impl Extension for SqlQuery {
+    fn get_outputs(&self)->&IOListOfTypes {
+        &self.outputs
+    }
+
+    fn run(&self,
+        inputs: IOType
+    )->Result<IOOutputType, String> {
+        let path = get_input::<String>(&inputs, "path")?;
+        let conn = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+            .map_err(|e| format!("{}: can not open {}: {}", self.name, path, e))?;
+        let mut outputs = IOOutputType::new();
+        if self.from_inputs {
+            let query = get_input::<String>(&inputs, "query")?;
+            let columns = get_input::<Vec<(String, SqlType)>>(&inputs, "columns")?;
+            let port = SqlPort::new::<SqlRow>("rows".to_string(), query.clone(), columns.clone());
+            let output = port.run(&conn).map_err(|e| format!("{}: {}: {}", self.name, path, e))?;
+            outputs.insert(port.name, output);
+        }
+        for port in &self.ports {
+            let output = port.run(&conn).map_err(|e| format!("{}: {}: port {}: {}", self.name, path, port.name, e))?;
+            outputs.insert(port.name.clone(), output);
+        }
+        Ok(outputs)
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+    use crate::std::scheduler::{task_port, Task};
+
+    /// Database file with table people(name, hours, active, since, tags), removed when dropped
+    struct TestDb(String);
+
+    impl TestDb {
+        fn new(name: &str)->Self {
+            let path = std::env::temp_dir().join(format!("rea_sql_test_{}_{}.sqlite", std::process::id(), name));
+            let path = path.to_str().unwrap().to_string();
+            let _ = std::fs::remove_file(&path);
+            Connection::open(&path).unwrap().execute_batch("
+                CREATE TABLE people (name TEXT, hours TEXT, active TEXT, since INTEGER, tags TEXT);
+                INSERT INTO people VALUES ('Anna', '7.5', 'true', 1777881600, 'dev, ops'), ('Ben', 4, 0, NULL, NULL);
+                CREATE TABLE tasks (id TEXT, name TEXT, minutes INTEGER, earliest TEXT, deadline TEXT, priority INTEGER, assignees TEXT, dependencies TEXT, room TEXT);
+                INSERT INTO tasks VALUES ('t1', 'Review', 90, NULL, '2026-05-08T17:00:00Z', 2, 'Anna,Ben', '', 'B');
+            ").unwrap();
+            Self(path)
+        }
+    }
+
+    impl Drop for TestDb {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_file(&self.0);
+        }
+    }
+
+    fn run(query: &SqlQuery, vals: Vec<(&str, Box<dyn Any+Send+Sync>)>)->Result<IOOutputType, String> {
+        query.run(vals.iter().map(|(k, v)| (k.to_string(), v)).collect())
+    }
+
+    #[test]
+    fn values_are_read_by_column_type() {
+        let db = TestDb::new("types");
+        let columns = vec![
+            ("hours".to_string(), SqlType::Real),
+            ("active".to_string(), SqlType::Bool),
+            ("since".to_string(), SqlType::DateTime),
+            ("tags".to_string(), SqlType::List),
+        ];
+        let query = SqlQuery::from_inputs();
+        assert!(query.get_inputs().contains_key("columns"));
+        let mut outputs = run(&query, vec![
+            ("path", Box::new(db.0.clone())),
+            ("query", Box::new("SELECT * FROM people ORDER BY name".to_string())),
+            ("columns", Box::new(columns)),
+        ]).unwrap();
+        let rows = *outputs.remove("rows").unwrap().downcast::<Vec<SqlRow>>().unwrap();
+        assert_eq!(rows[0].values["hours"], SqlValue::Real(7.5));
+        assert_eq!(rows[0].values["active"], SqlValue::Bool(true));
+        assert_eq!(rows[0].datetime("since").unwrap(), Utc.with_ymd_and_hms(2026, 5, 4, 8, 0, 0).unwrap());
+        assert_eq!(rows[0].list("tags").unwrap(), vec!["dev".to_string(), "ops".to_string()]);
+        assert_eq!((rows[1].values["hours"].clone(), rows[1].values["active"].clone()), (SqlValue::Real(4.0), SqlValue::Bool(false)));
+        assert!(rows[1].is_null("since") && rows[1].list("tags").unwrap().is_empty());
+        // undeclared column keeps SQLite type
+        assert_eq!(rows[0].text("name").unwrap(), "Anna");
+        assert!(rows[0].integer("name").is_err());
+
+        let wrong = run(&query, vec![
+            ("path", Box::new(db.0.clone())),
+            ("query", Box::new("SELECT name FROM people".to_string())),
+            ("columns", Box::new(vec![("name".to_string(), SqlType::Integer)])),
+        ]);
+        assert!(wrong.unwrap_err().contains("Row 1: column name"));
+    }
+
+    #[test]
+    fn ports_give_typed_outputs() {
+        let db = TestDb::new("ports");
+        let query = SqlQuery::new(vec![task_port("tasks", "SELECT * FROM tasks")]).unwrap();
+        assert_eq!(query.get_outputs()["tasks"], TypeId::of::<Vec<Task>>());
+        let mut outputs = run(&query, vec![("path", Box::new(db.0.clone()))]).unwrap();
+        let tasks = *outputs.remove("tasks").unwrap().downcast::<Vec<Task>>().unwrap();
+        assert_eq!((tasks[0].id.as_str(), tasks[0].minutes, tasks[0].priority), ("t1", 90, 2));
+        assert_eq!(tasks[0].assignees, vec!["Anna".to_string(), "Ben".to_string()]);
+        assert_eq!((tasks[0].earliest, tasks[0].deadline), (None, Some(Utc.with_ymd_and_hms(2026, 5, 8, 17, 0, 0).unwrap())));
+        assert_eq!(tasks[0].fields["room"], "B");
+
+        assert!(SqlQuery::new(vec![task_port("tasks", "SELECT 1"), task_port("tasks", "SELECT 2")]).is_err());
+        let missing = SqlQuery::new(vec![task_port("tasks", "SELECT id, name FROM tasks")]).unwrap();
+        assert!(run(&missing, vec![("path", Box::new(db.0.clone()))]).unwrap_err().contains("no column minutes"));
+        let named = SqlQuery::new(vec![task_port("tasks", "SELECT id FROM tasks")]).unwrap().named("TaskQuery");
+        assert_eq!(named.get_name(), "TaskQuery");
+        assert!(run(&named, vec![("path", Box::new(db.0.clone()))]).unwrap_err().starts_with("TaskQuery: "));
+    }
+}
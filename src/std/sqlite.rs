@@ -0,0 +1,380 @@
+//Responsible for SQLite databases: ET saving Render and Events loading Extension
+
+use std::any::{Any, TypeId};
+use std::collections::{BTreeMap, HashMap};
+use std::str::FromStr;
+use std::sync::LazyLock;
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime};
+use chrono_tz::Tz;
+use rusqlite::types::ValueRef;
+use rusqlite::{params, Connection, OpenFlags};
+use uuid::Uuid;
+
+use crate::core::{get_input, DateTimeType, EventSpec, EventTable, EventZone, Extension, IOListOfTypes, IOOutputType, IOType, R_E_AClass, R_E_AGeneric, Render};
+use crate::recurrence::Recurrence;
+
+/// Schema of REA tables, n-th element migrates database from version n to n+1 (version is stored in PRAGMA user_version).
+/// Tables have "rea_" prefix not to collide with tables of user
+const MIGRATIONS: &[&str] = &[
+    "CREATE TABLE rea_events (
+        uuid          TEXT PRIMARY KEY,
+        name          TEXT NOT NULL,
+        start         TEXT NOT NULL,
+        end           TEXT NOT NULL,
+        zone          TEXT NOT NULL,
+        all_day_start TEXT,
+        all_day_end   TEXT,
+        recurrence    TEXT,
+        parent        TEXT,
+        recurrence_id TEXT
+    );
+    CREATE INDEX rea_events_start ON rea_events(start);
+    CREATE TABLE rea_event_fields (
+        uuid  TEXT NOT NULL REFERENCES rea_events(uuid),
+        key   TEXT NOT NULL,
+        value TEXT NOT NULL,
+        PRIMARY KEY (uuid, key)
+    );",
+];
+
+/// Columns of rea_events that are not custom fields when loaded
+const EVENT_COLUMNS: [&str; 10] = ["uuid", "name", "start", "end", "zone", "all_day_start", "all_day_end", "recurrence", "parent", "recurrence_id"];
+
+/// Opens (creates if needed) database and migrates it to the latest schema
+pub fn open_migrated(path: &str
+)->Result<Connection, String> {
+    let mut conn = Connection::open(path).map_err(|e| format!("Can not open {}: {}", path, e))?;
+    let version: usize = conn.query_row("PRAGMA user_version", [], |row| row.get(0))
+        .map_err(|e| format!("{}: can not read schema version: {}", path, e))?;
+    if version > MIGRATIONS.len() {
+        return Err(format!("{}: schema version {} is newer than supported {}", path, version, MIGRATIONS.len()));
+    }
+
+    for (from, migration) in MIGRATIONS.iter().enumerate().skip(version) {
+        let tx = conn.transaction().map_err(|e| format!("{}: {}", path, e))?;
+        tx.execute_batch(migration).map_err(|e| format!("{}: migration {}->{} failed: {}", path, from, from + 1, e))?;
+        tx.pragma_update(None, "user_version", from + 1).map_err(|e| format!("{}: {}", path, e))?;
+        tx.commit().map_err(|e| format!("{}: {}", path, e))?;
+    }
+    Ok(conn)
+}
+
+fn zone_to_text(zone: EventZone)->String {
+    match zone {
+        EventZone::Utc => "UTC".into(),
+        EventZone::Floating => "Floating".into(),
+        EventZone::Tz(tz) => tz.name().into(),
+    }
+}
+
+fn zone_from_text(text: &str)->Result<EventZone, String> {
+    match text {
+        "UTC" | "" => Ok(EventZone::Utc),
+        "Floating" => Ok(EventZone::Floating),
+        name => Tz::from_str(name).map(EventZone::Tz).map_err(|_| format!("Unknown zone {}", name)),
+    }
+}
+
+
+
+// ==========SAVE RENDER==========
+
+/// Saves ET to SQLite database: Events are inserted or updated by UUID, Events that are not in ET anymore (e.g. split parents) are deleted,
+/// so database always has same Events as last saved ET.
+/// Input: "path": String
+pub struct SqliteSave;
+
+impl R_E_AGeneric for SqliteSave {
+    fn get_name(&self)->&'static str {
+        "SqliteSave"
+    }
+    fn get_class(&self)->&'static R_E_AClass {
+        &R_E_AClass::Render
+    }
+    fn get_inputs(&self)->&'static IOListOfTypes {
+        static INPUTS: LazyLock<IOListOfTypes> = LazyLock::new(|| HashMap::from([
+            ("path".to_string(), TypeId::of::<String>()),
+        ]));
+        &INPUTS
+    }
+}
+
+impl Render for SqliteSave {
+    fn run(&self,
+        et: EventTable,
+        inputs: IOType
+    )->Result<(), String> {
+        let path = get_input::<String>(&inputs, "path")?;
+        let mut conn = open_migrated(path)?;
+        save(&mut conn, &et).map_err(|e| format!("SqliteSave: {}: {}", path, e))
+    }
+}
+
+/// Upsert of all Events of ET and deletion of Events that are not in ET, in one transaction
+pub fn save(conn: &mut Connection,
+    et: &EventTable
+)->Result<(), String> {
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    {
+        let saved: Vec<String> = tx.prepare("SELECT uuid FROM rea_events").map_err(|e| e.to_string())?
+            .query_map([], |row| row.get(0)).map_err(|e| e.to_string())?
+            .collect::<Result<_, _>>().map_err(|e| e.to_string())?;
+        let mut delete_fields = tx.prepare("DELETE FROM rea_event_fields WHERE uuid = ?1").map_err(|e| e.to_string())?;
+        let mut delete_event = tx.prepare("DELETE FROM rea_events WHERE uuid = ?1").map_err(|e| e.to_string())?;
+        for uuid in saved {
+            if Uuid::parse_str(&uuid).is_ok_and(|u| et.get(u).is_some()) {
+                continue;
+            }
+            delete_fields.execute(params![uuid]).map_err(|e| format!("Event {}: {}", uuid, e))?;
+            delete_event.execute(params![uuid]).map_err(|e| format!("Event {}: {}", uuid, e))?;
+        }
+
+        let mut upsert = tx.prepare(
+            "INSERT INTO rea_events (uuid, name, start, end, zone, all_day_start, all_day_end, recurrence, parent, recurrence_id)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
+             ON CONFLICT(uuid) DO UPDATE SET
+                name=excluded.name, start=excluded.start, end=excluded.end, zone=excluded.zone,
+                all_day_start=excluded.all_day_start, all_day_end=excluded.all_day_end, recurrence=excluded.recurrence,
+                parent=excluded.parent, recurrence_id=excluded.recurrence_id"
+        ).map_err(|e| e.to_string())?;
+        let mut insert_field = tx.prepare("INSERT INTO rea_event_fields (uuid, key, value) VALUES (?1, ?2, ?3)").map_err(|e| e.to_string())?;
+
+        for event in et.get_events() {
+            let uuid = event.uuid.to_string();
+            let recurrence = event.recurrence.as_ref()
+                .map(serde_json::to_string)
+                .transpose()
+                .map_err(|e| format!("Event {}: {}", uuid, e))?;
+            upsert.execute(params![
+                uuid,
+                event.get_name(),
+                event.start.to_rfc3339(),
+                event.end.to_rfc3339(),
+                zone_to_text(event.zone),
+                event.all_day.map(|d| d.0.to_string()),
+                event.all_day.map(|d| d.1.to_string()),
+                recurrence,
+                event.parent.map(|p| p.to_string()),
+                event.recurrence_id.map(|t| t.to_rfc3339()),
+            ]).map_err(|e| format!("Event {}: {}", uuid, e))?;
+
+            delete_fields.execute(params![uuid]).map_err(|e| format!("Event {}: {}", uuid, e))?;
+            for (key, value) in &event.fields {
+                insert_field.execute(params![uuid, key, value]).map_err(|e| format!("Event {}: {}", uuid, e))?;
+            }
+        }
+    }
+    tx.commit().map_err(|e| e.to_string())
+}
+
+
+
+// ==========LOAD EXTENSION==========
+
+/// Loads Events from SQLite database (opened read-only) with any query.
+/// Columns of query result are matched by name: "name", "start", "end" are required; "uuid", "zone", "all_day_start", "all_day_end", "recurrence", "parent", "recurrence_id" are optional (see rea_events table);
+/// all other columns become user-defined fields. So both Events saved by SqliteSave ("SELECT * FROM rea_events") and any user table
+/// ("SELECT title AS name, begin AS start, finish AS end, room FROM tasks") can be loaded.
+/// Custom fields saved by SqliteSave are loaded too if query returns "uuid".
+/// Inputs: "path": String, "query": String
+/// Output: "events": Vec<EventSpec>
+pub struct SqliteLoad;
+
+impl R_E_AGeneric for SqliteLoad {
+    fn get_name(&self)->&'static str {
+        "SqliteLoad"
+    }
+    fn get_class(&self)->&'static R_E_AClass {
+        &R_E_AClass::Extension
+    }
+    fn get_inputs(&self)->&'static IOListOfTypes {
+        static INPUTS: LazyLock<IOListOfTypes> = LazyLock::new(|| HashMap::from([
+            ("path".to_string(), TypeId::of::<String>()),
+            ("query".to_string(), TypeId::of::<String>()),
+        ]));
+        &INPUTS
+    }
+}
+
+impl Extension for SqliteLoad {
+    fn get_outputs(&self)->&'static IOListOfTypes {
+        static OUTPUTS: LazyLock<IOListOfTypes> = LazyLock::new(|| HashMap::from([
+            ("events".to_string(), TypeId::of::<Vec<EventSpec>>()),
+        ]));
+        &OUTPUTS
+    }
+
+    fn run(&self,
+        inputs: IOType
+    )->Result<IOOutputType, String> {
+        let path = get_input::<String>(&inputs, "path")?;
+        let query = get_input::<String>(&inputs, "query")?;
+        let conn = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+            .map_err(|e| format!("SqliteLoad: can not open {}: {}", path, e))?;
+        let events = load(&conn, query).map_err(|e| format!("SqliteLoad: {}: {}", path, e))?;
+        Ok(HashMap::from([
+            ("events".to_string(), Box::new(events) as Box<dyn Any+Send+Sync>),
+        ]))
+    }
+}
+
+/// Events from result of query, see SqliteLoad
+pub fn load(conn: &Connection,
+    query: &str
+)->Result<Vec<EventSpec>, String> {
+    let mut stmt = conn.prepare(query).map_err(|e| format!("Wrong query: {}", e))?;
+    let columns: Vec<String> = stmt.column_names().into_iter().map(String::from).collect();
+    for required in ["name", "start", "end"] {
+        if !columns.iter().any(|c| c == required) {
+            return Err(format!("Query result has no column {}", required));
+        }
+    }
+
+    // row as column → text, NULLs are skipped
+    let rows = stmt.query_map([], |row| {
+        let mut values = BTreeMap::new();
+        for (i, column) in columns.iter().enumerate() {
+            let value = match row.get_ref(i)? {
+                ValueRef::Null => continue,
+                ValueRef::Integer(n) => n.to_string(),
+                ValueRef::Real(x) => x.to_string(),
+                ValueRef::Text(t) | ValueRef::Blob(t) => String::from_utf8_lossy(t).into_owned(),
+            };
+            values.insert(column.clone(), value);
+        }
+        Ok(values)
+    }).map_err(|e| e.to_string())?;
+
+    let mut events = Vec::new();
+    for (i, values) in rows.enumerate() {
+        let values = values.map_err(|e| format!("Row {}: {}", i + 1, e))?;
+        events.push(spec_of_row(values).map_err(|e| format!("Row {}: {}", i + 1, e))?);
+    }
+
+    load_saved_fields(conn, &mut events)?;
+    Ok(events)
+}
+
+fn spec_of_row(mut values: BTreeMap<String, String>)->Result<EventSpec, String> {
+    let mut take = |column: &str| values.remove(column);
+    let zone = zone_from_text(&take("zone").unwrap_or_default())?;
+    let parse_time = |text: String| -> Result<DateTimeType, String> {
+        match DateTime::parse_from_rfc3339(&text) {
+            Ok(t) => Ok(t.to_utc()),
+            Err(_) => NaiveDateTime::parse_from_str(&text, "%Y-%m-%d %H:%M:%S")
+                .map(|local| zone.resolve_local(local))
+                .map_err(|e| format!("Wrong time {}: {}", text, e)),
+        }
+    };
+    let parse_date = |text: String| NaiveDate::parse_from_str(&text, "%Y-%m-%d").map_err(|e| format!("Wrong date {}: {}", text, e));
+
+    let name = take("name").unwrap_or_default();
+    let start = parse_time(take("start").ok_or("start is NULL")?)?;
+    let end = parse_time(take("end").ok_or("end is NULL")?)?;
+    let mut spec = EventSpec::new(name, start, end);
+    spec.zone = zone;
+    spec.uuid = take("uuid").map(|u| Uuid::parse_str(&u).map_err(|e| format!("Wrong UUID {}: {}", u, e))).transpose()?;
+    if let (Some(first), Some(after_last)) = (take("all_day_start"), take("all_day_end")) {
+        spec.all_day = Some((parse_date(first)?, parse_date(after_last)?));
+    }
+    spec.recurrence = take("recurrence")
+        .map(|r| serde_json::from_str::<Recurrence>(&r).map_err(|e| format!("Wrong recurrence {}: {}", r, e)))
+        .transpose()?;
+    spec.parent = take("parent").map(|p| Uuid::parse_str(&p).map_err(|e| format!("Wrong parent UUID {}: {}", p, e))).transpose()?;
+    spec.recurrence_id = take("recurrence_id").map(parse_time).transpose()?;
+
+    for column in EVENT_COLUMNS {
+        values.remove(column);
+    }
+    spec.fields = values;
+    Ok(spec)
+}
+
+/// Adds fields from rea_event_fields (if database has it) to Events with UUID
+fn load_saved_fields(conn: &Connection, events: &mut [EventSpec])->Result<(), String> {
+    let has_table: bool = conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type='table' AND name='rea_event_fields')", [], |row| row.get(0)
+    ).map_err(|e| e.to_string())?;
+    if !has_table {
+        return Ok(());
+    }
+
+    let mut stmt = conn.prepare("SELECT key, value FROM rea_event_fields WHERE uuid = ?1").map_err(|e| e.to_string())?;
+    for event in events.iter_mut() {
+        let Some(uuid) = event.uuid else { continue };
+        let fields = stmt.query_map(params![uuid.to_string()], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))
+            .map_err(|e| e.to_string())?;
+        for field in fields {
+            let (key, value) = field.map_err(|e| e.to_string())?;
+            event.fields.insert(key, value);
+        }
+    }
+    Ok(())
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+    use crate::recurrence::Frequency;
+
+    fn at(d: u32, h: u32)->DateTimeType {
+        Utc.with_ymd_and_hms(2026, 5, d, h, 0, 0).unwrap()
+    }
+
+    fn count(conn: &Connection, table: &str)->usize {
+        conn.query_row(&format!("SELECT COUNT(*) FROM {}", table), [], |row| row.get(0)).unwrap()
+    }
+
+    #[test]
+    fn saved_database_mirrors_et() {
+        let mut conn = open_migrated(":memory:").unwrap();
+        let mut et = EventTable::new();
+        let mut spec = EventSpec::new("Task".to_string(), at(4, 9), at(4, 11));
+        spec.fields.insert("assignee".to_string(), "Anna".to_string());
+        let task = et.add_spec(spec).unwrap();
+        let mut spec = EventSpec::new("Standup".to_string(), at(4, 8), at(4, 9));
+        spec.recurrence = Some(Recurrence::new(Frequency::Daily));
+        let standup = et.add_spec(spec).unwrap();
+        let occurrence = et.materialise(standup, at(5, 0), at(6, 0)).unwrap()[0];
+        et.set_times(occurrence, at(5, 10), at(5, 11)).unwrap();
+
+        save(&mut conn, &et).unwrap();
+        assert_eq!((count(&conn, "rea_events"), count(&conn, "rea_event_fields")), (3, 1));
+
+        // split parent is deleted with its fields, parts are added
+        let parts = et.split(task, vec![(at(4, 9), at(4, 10), " (1)".to_string()), (at(4, 12), at(4, 13), " (2)".to_string())]).unwrap();
+        save(&mut conn, &et).unwrap();
+        assert_eq!((count(&conn, "rea_events"), count(&conn, "rea_event_fields")), (4, 2));
+
+        let mut loaded = EventTable::new();
+        for spec in load(&conn, "SELECT * FROM rea_events").unwrap() {
+            loaded.add_spec(spec).unwrap();
+        }
+        assert_eq!(loaded.get_events().count(), 4);
+        for event in et.get_events() {
+            let same = loaded.get(event.uuid).unwrap();
+            assert_eq!((same.get_name(), same.start, same.end, &same.fields), (event.get_name(), event.start, event.end, &event.fields));
+            assert_eq!((same.parent, same.recurrence_id, &same.recurrence), (event.parent, event.recurrence_id, &event.recurrence));
+        }
+        assert_eq!(loaded.get(parts[1]).unwrap().parent, Some(task));
+        assert_eq!(loaded.get(occurrence).unwrap().recurrence_id, Some(at(5, 8)));
+        // occurrence is not materialised again after load
+        assert!(loaded.materialise(standup, at(5, 0), at(6, 0)).unwrap().is_empty());
+    }
+
+    #[test]
+    fn user_tables_are_loaded_with_fields() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch("CREATE TABLE tasks (title TEXT, begin TEXT, finish TEXT, room TEXT);
+            INSERT INTO tasks VALUES ('Review', '2026-05-04 09:00:00', '2026-05-04T10:00:00Z', NULL), ('Call', '2026-05-04 11:00:00', '2026-05-04 11:30:00', 'B');").unwrap();
+        let events = load(&conn, "SELECT title AS name, begin AS start, finish AS end, room FROM tasks").unwrap();
+        assert_eq!((events[0].name.as_str(), events[0].start, events[0].end), ("Review", at(4, 9), at(4, 10)));
+        assert!(events[0].fields.is_empty());
+        assert_eq!(events[1].fields["room"], "B");
+        assert!(load(&conn, "SELECT title AS name, begin AS start FROM tasks").unwrap_err().contains("end"));
+    }
+}
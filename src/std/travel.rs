@@ -0,0 +1,363 @@
+//Responsible for travel time between Events at different locations: TravelTimes matrix, TravelQuery Extension and Travel App
+
+use std::any::{Any, TypeId};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::LazyLock;
+
+use chrono::{Duration, NaiveDate};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::core::{get_input, App, DateTimeType, EventSpec, EventTable, EventZone, Extension, IOListOfTypes, IOOutputType, IOType, R_E_AClass, R_E_AGeneric};
+use crate::std::filter::is_hidden;
+use crate::std::sql::{SqlPort, SqlQuery, SqlRow, SqlType};
+
+/// Namespace of UUIDs of travel Events, so travel between same Events always gives same UUID
+const TRAVEL_NAMESPACE: Uuid = Uuid::from_u128(0x9a4f_26d1_c7e3_4b58_8d0a_5e1f_b3c9_7260);
+
+/// Field of travel Events: "<from> → <to>"
+pub const TRAVEL_FIELD: &str = "travel";
+
+/// Travel minutes between locations, e.g. {"Office A": {"Office B": 30}}. Same in both directions unless both are given
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct TravelTimes {
+    pub minutes: BTreeMap<String, BTreeMap<String, u32>>,
+}
+
+impl TravelTimes {
+    pub fn insert(&mut self, from: &str, to: &str, minutes: u32) {
+        self.minutes.entry(from.to_string()).or_default().insert(to.to_string(), minutes);
+    }
+
+    /// None if route is unknown, zero for same location
+    pub fn get(&self, from: &str, to: &str)->Option<Duration> {
+        if from == to {
+            return Some(Duration::zero());
+        }
+        let one_way = |a: &str, b: &str| self.minutes.get(a)?.get(b).copied();
+        one_way(from, to).or_else(|| one_way(to, from)).map(|m| Duration::minutes(m as i64))
+    }
+}
+
+/// Day on which Events of assignee do not fit because of travel
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct InfeasibleDay {
+    pub assignee: String,
+    pub date: NaiveDate,
+    pub reason: String,
+}
+
+
+
+// ==========TRAVEL QUERY EXTENSION==========
+
+/// Reads travel times from SQLite database (opened read-only)
+/// Inputs: "path": String, "query": String (columns "from", "to", "minutes")
+/// Output: "travel_times": TravelTimes
+pub struct TravelQuery;
+
+impl R_E_AGeneric for TravelQuery {
+    fn get_name(&self)->&'static str {
+        "TravelQuery"
+    }
+    fn get_class(&self)->&'static R_E_AClass {
+        &R_E_AClass::Extension
+    }
+    fn get_inputs(&self)->&'static IOListOfTypes {
+        static INPUTS: LazyLock<IOListOfTypes> = LazyLock::new(|| HashMap::from([
+            ("path".to_string(), TypeId::of::<String>()),
+            ("query".to_string(), TypeId::of::<String>()),
+        ]));
+        &INPUTS
+    }
+}
+
+impl Extension for TravelQuery {
+    fn get_outputs(&self)->&'static IOListOfTypes {
+        static OUTPUTS: LazyLock<IOListOfTypes> = LazyLock::new(|| HashMap::from([
+            ("travel_times".to_string(), TypeId::of::<TravelTimes>()),
+        ]));
+        &OUTPUTS
+    }
+
+    fn run(&self,
+        inputs: IOType
+    )->Result<IOOutputType, String> {
+        let query = get_input::<String>(&inputs, "query")?;
+        let port = SqlPort::new::<SqlRow>("rows".to_string(), query.clone(), vec![
+            ("from".to_string(), SqlType::Text),
+            ("to".to_string(), SqlType::Text),
+            ("minutes".to_string(), SqlType::Minutes),
+        ]);
+        let mut outputs = SqlQuery::new(vec![port])?.named(self.get_name()).run(inputs)?;
+        let rows = outputs.remove("rows")
+            .and_then(|rows| rows.downcast::<Vec<SqlRow>>().ok())
+            .ok_or("TravelQuery: query gave no rows")?;
+
+        let mut travel_times = TravelTimes::default();
+        for (i, row) in rows.iter().enumerate() {
+            let (from, to, minutes) = route_of(row).map_err(|e| format!("TravelQuery: row {}: {}", i + 1, e))?;
+            travel_times.insert(&from, &to, minutes);
+        }
+        Ok(HashMap::from([
+            ("travel_times".to_string(), Box::new(travel_times) as Box<dyn Any+Send+Sync>),
+        ]))
+    }
+}
+
+/// (from, to, minutes) of row
+fn route_of(row: &SqlRow)->Result<(String, String, u32), String> {
+    let minutes = u32::try_from(row.duration("minutes")?.num_minutes()).map_err(|_| "Column minutes is negative".to_string())?;
+    Ok((row.text("from")?, row.text("to")?, minutes))
+}
+
+
+
+// ==========TRAVEL APP==========
+
+/// Inserts travel Events between consecutive Events of same assignee at different locations and moves later Events if gap is too short
+/// (Events without assignee field are treated as Events of one person "").
+/// Events without location are moved like others and travel is not placed over them, it starts at last known location. Hidden, all-day and recurring Events are not considered.
+/// Input: "travel_times": TravelTimes, "location_field": String (e.g. "location"), "assignee_field": String (e.g. "assignee"), "zone": Tz (zone of days in report)
+/// Output: ET with travel Events (fields TRAVEL_FIELD and assignee field); report "infeasible": Vec<InfeasibleDay> (unknown routes and days whose Events were moved past midnight)
+pub struct Travel;
+
+impl R_E_AGeneric for Travel {
+    fn get_name(&self)->&'static str {
+        "Travel"
+    }
+    fn get_class(&self)->&'static R_E_AClass {
+        &R_E_AClass::App
+    }
+    fn get_inputs(&self)->&'static IOListOfTypes {
+        static INPUTS: LazyLock<IOListOfTypes> = LazyLock::new(|| HashMap::from([
+            ("travel_times".to_string(), TypeId::of::<TravelTimes>()),
+            ("location_field".to_string(), TypeId::of::<String>()),
+            ("assignee_field".to_string(), TypeId::of::<String>()),
+            ("zone".to_string(), TypeId::of::<Tz>()),
+        ]));
+        &INPUTS
+    }
+}
+
+impl App for Travel {
+    fn get_outputs(&self)->&'static IOListOfTypes {
+        static OUTPUTS: LazyLock<IOListOfTypes> = LazyLock::new(|| HashMap::from([
+            ("infeasible".to_string(), TypeId::of::<Vec<InfeasibleDay>>()),
+        ]));
+        &OUTPUTS
+    }
+
+    fn run(&self,
+        et: EventTable,
+        inputs: IOType
+    )->Result<EventTable, String> {
+        self.run_with_outputs(et, inputs).map(|(et, _)| et)
+    }
+
+    fn run_with_outputs(&self,
+        mut et: EventTable,
+        inputs: IOType
+    )->Result<(EventTable, IOOutputType), String> {
+        let travel_times = get_input::<TravelTimes>(&inputs, "travel_times")?;
+        let location_field = get_input::<String>(&inputs, "location_field")?;
+        let assignee_field = get_input::<String>(&inputs, "assignee_field")?;
+        let zone = *get_input::<Tz>(&inputs, "zone")?;
+        let day_of = |t: DateTimeType| EventZone::Tz(zone).to_local(t).date();
+
+        // assignee → (start, uuid, location, end) before moving
+        let mut chains: BTreeMap<String, Vec<_>> = BTreeMap::new();
+        for event in et.get_events() {
+            if is_hidden(event) || event.all_day.is_some() || event.recurrence.is_some() || event.fields.contains_key(TRAVEL_FIELD) {
+                continue;
+            }
+            let location = event.fields.get(location_field).filter(|l| !l.is_empty()).cloned();
+            let assignee = event.fields.get(assignee_field).cloned().unwrap_or_default();
+            let (start, end) = event.timed_in(zone);
+            chains.entry(assignee).or_default().push((start, event.uuid, location, end));
+        }
+
+        let mut infeasible: Vec<InfeasibleDay> = Vec::new();
+        let mut flag = |assignee: &str, date: NaiveDate, reason: String| {
+            if !infeasible.iter().any(|d| d.assignee == assignee && d.date == date) {
+                infeasible.push(InfeasibleDay { assignee: assignee.to_string(), date, reason });
+            }
+        };
+        for (assignee, mut chain) in chains {
+            chain.sort();
+            let mut last_location = chain.first().and_then(|(_, _, location, _)| location.clone());
+            for pair in chain.windows(2) {
+                let ((_, prev, _, original_end), (original_start, next, next_location, _)) = (&pair[0], &pair[1]);
+                let prev_end = et.get(*prev).ok_or(format!("Travel: missing Event {}", prev))?.timed_in(zone).1;
+                let next_event = et.get(*next).ok_or(format!("Travel: missing Event {}", next))?;
+                let (next_start, next_end) = next_event.timed_in(zone);
+                let original_day = day_of(next_start);
+                // travel to next Event from last known location
+                let route = last_location.clone().zip(next_location.clone());
+                if next_location.is_some() {
+                    last_location.clone_from(next_location);
+                }
+                let need = match &route {
+                    Some((from, to)) => match travel_times.get(from, to) {
+                        Some(need) => need,
+                        None => {
+                            flag(&assignee, original_day, format!("no travel time from {} to {}", from, to));
+                            continue;
+                        }
+                    },
+                    None => Duration::zero(),
+                };
+
+                // move next Event (and with it all later ones, they are checked in turn) if gap is too short.
+                // Without travel Events only must not overlap more than they did before
+                let required = if need > Duration::zero() { need } else { (*original_start - *original_end).min(Duration::zero()) };
+                let delay = (required - (next_start - prev_end)).max(Duration::zero());
+                if delay > Duration::zero() {
+                    let (start, end) = (next_event.start, next_event.end);
+                    et.set_times(*next, start + delay, end + delay).map_err(|e| format!("Travel: {}", e))?;
+                    if day_of(next_end + delay) > original_day {
+                        let reason = match &route {
+                            Some((from, to)) => format!("Events are moved past midnight by travel from {} to {}", from, to),
+                            None => "Events are moved past midnight by earlier travel".to_string(),
+                        };
+                        flag(&assignee, original_day, reason);
+                    }
+                }
+                let Some((from, to)) = route.filter(|_| need > Duration::zero()) else { continue };
+                let arrival = next_start + delay;
+                let uuid = Uuid::new_v5(&TRAVEL_NAMESPACE, format!("{}/{}", prev, next).as_bytes());
+                if et.get(uuid).is_none() {
+                    let mut spec = EventSpec::new(format!("Travel {} → {}", from, to), arrival - need, arrival);
+                    spec.uuid = Some(uuid);
+                    spec.fields.insert(TRAVEL_FIELD.to_string(), format!("{} → {}", from, to));
+                    if !assignee.is_empty() {
+                        spec.fields.insert(assignee_field.clone(), assignee.clone());
+                    }
+                    et.add_spec(spec).map_err(|e| format!("Travel: {}", e))?;
+                }
+            }
+        }
+        Ok((et, HashMap::from([
+            ("infeasible".to_string(), Box::new(infeasible) as Box<dyn Any+Send+Sync>),
+        ])))
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::testing::{at, times, TestInputs};
+
+    fn event(et: &mut EventTable, assignee: &str, location: Option<&str>, start: DateTimeType, end: DateTimeType)->Uuid {
+        let mut spec = EventSpec::new("Meeting".to_string(), start, end);
+        spec.fields.insert("assignee".to_string(), assignee.to_string());
+        if let Some(location) = location {
+            spec.fields.insert("location".to_string(), location.to_string());
+        }
+        et.add_spec(spec).unwrap()
+    }
+
+    /// 30 minutes between Office A and Office B, days in UTC
+    fn run(et: EventTable)->(EventTable, Vec<InfeasibleDay>) {
+        let mut travel_times = TravelTimes::default();
+        travel_times.insert("Office A", "Office B", 30);
+        let inputs = TestInputs::new()
+            .with("travel_times", travel_times)
+            .with("location_field", "location".to_string())
+            .with("assignee_field", "assignee".to_string())
+            .with("zone", Tz::UTC);
+        let (et, mut outputs) = Travel.run_with_outputs(et, inputs.get()).unwrap();
+        (et, *outputs.remove("infeasible").unwrap().downcast::<Vec<InfeasibleDay>>().unwrap())
+    }
+
+    /// (name, assignee, start, end) of travel Events
+    fn travels(et: &EventTable)->Vec<(String, String, DateTimeType, DateTimeType)> {
+        let mut travels: Vec<_> = et.get_events()
+            .filter(|e| e.fields.contains_key(TRAVEL_FIELD))
+            .map(|e| (e.get_name().to_string(), e.fields["assignee"].clone(), e.start, e.end))
+            .collect();
+        travels.sort();
+        travels
+    }
+
+    #[test]
+    fn travel_is_inserted_and_later_events_are_pushed() {
+        let mut et = EventTable::new();
+        let first = event(&mut et, "alice", Some("Office A"), at(4, 9, 0), at(4, 10, 0));
+        let second = event(&mut et, "alice", Some("Office B"), at(4, 10, 10), at(4, 11, 0));
+        let third = event(&mut et, "alice", Some("Office B"), at(4, 11, 10), at(4, 12, 0));
+        let same = event(&mut et, "bob", Some("Office A"), at(4, 10, 0), at(4, 11, 0));
+        event(&mut et, "bob", Some("Office A"), at(4, 9, 0), at(4, 10, 0));
+
+        let (et, infeasible) = run(et);
+        assert!(infeasible.is_empty());
+        assert_eq!(times(&et, first), (at(4, 9, 0), at(4, 10, 0)));
+        assert_eq!(times(&et, second), (at(4, 10, 30), at(4, 11, 20)));
+        // same location: pushed only as far as needed to not overlap
+        assert_eq!(times(&et, third), (at(4, 11, 20), at(4, 12, 10)));
+        assert_eq!(times(&et, same), (at(4, 10, 0), at(4, 11, 0)));
+        assert_eq!(travels(&et), vec![
+            ("Travel Office A → Office B".to_string(), "alice".to_string(), at(4, 10, 0), at(4, 10, 30)),
+        ]);
+
+        // travel Event has same UUID on rerun, nothing is added or moved
+        let (again, infeasible) = run(et.clone());
+        assert!(infeasible.is_empty());
+        assert_eq!(again.get_events().count(), et.get_events().count());
+        assert_eq!(travels(&again), travels(&et));
+        assert_eq!(times(&again, third), times(&et, third));
+    }
+
+    #[test]
+    fn events_without_location_are_obstacles() {
+        let mut et = EventTable::new();
+        event(&mut et, "alice", Some("Office A"), at(4, 9, 0), at(4, 10, 0));
+        let call = event(&mut et, "alice", None, at(4, 10, 0), at(4, 10, 30));
+        let arrival = event(&mut et, "alice", Some("Office B"), at(4, 10, 30), at(4, 11, 0));
+        event(&mut et, "carol", Some("Office A"), at(4, 9, 0), at(4, 10, 0));
+        let moved = event(&mut et, "carol", Some("Office B"), at(4, 10, 0), at(4, 10, 30));
+        let pushed = event(&mut et, "carol", None, at(4, 10, 30), at(4, 11, 0));
+
+        let (et, infeasible) = run(et);
+        assert!(infeasible.is_empty());
+        // travel starts from last known location after Event without location
+        assert_eq!(times(&et, call), (at(4, 10, 0), at(4, 10, 30)));
+        assert_eq!(times(&et, arrival), (at(4, 11, 0), at(4, 11, 30)));
+        // delayed Event pushes following Event without location
+        assert_eq!(times(&et, moved), (at(4, 10, 30), at(4, 11, 0)));
+        assert_eq!(times(&et, pushed), (at(4, 11, 0), at(4, 11, 30)));
+        assert_eq!(travels(&et), vec![
+            ("Travel Office A → Office B".to_string(), "alice".to_string(), at(4, 10, 30), at(4, 11, 0)),
+            ("Travel Office A → Office B".to_string(), "carol".to_string(), at(4, 10, 0), at(4, 10, 30)),
+        ]);
+    }
+
+    #[test]
+    fn infeasible_days_are_flagged() {
+        let mut et = EventTable::new();
+        event(&mut et, "alice", Some("Office A"), at(4, 22, 0), at(4, 23, 50));
+        let late = event(&mut et, "alice", Some("Office B"), at(4, 23, 50), at(4, 23, 59));
+        event(&mut et, "bob", Some("Office A"), at(5, 9, 0), at(5, 10, 0));
+        let unknown = event(&mut et, "bob", Some("Office C"), at(5, 10, 0), at(5, 11, 0));
+
+        let (et, infeasible) = run(et);
+        assert_eq!(times(&et, late), (at(5, 0, 20), at(5, 0, 29)));
+        assert_eq!(times(&et, unknown), (at(5, 10, 0), at(5, 11, 0)));
+        assert_eq!(infeasible, vec![
+            InfeasibleDay {
+                assignee: "alice".to_string(),
+                date: at(4, 0, 0).date_naive(),
+                reason: "Events are moved past midnight by travel from Office A to Office B".to_string(),
+            },
+            InfeasibleDay {
+                assignee: "bob".to_string(),
+                date: at(5, 0, 0).date_naive(),
+                reason: "no travel time from Office A to Office C".to_string(),
+            },
+        ]);
+        assert_eq!(travels(&et).len(), 1);
+    }
+}
@@ -0,0 +1,360 @@
+//Responsible for working time: WorkingTime calendar, WorkingCalendar Extension that adds holidays to it and WorkingHours App that fits Events into working time
+
+use std::any::{Any, TypeId};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::sync::LazyLock;
+
+use chrono::{Datelike, Duration, NaiveDate, NaiveTime};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::core::{get_input, App, DateTimeType, EventTable, EventZone, Extension, IOListOfTypes, IOOutputType, IOType, R_E_AClass, R_E_AGeneric};
+use crate::std::filter::is_hidden;
+
+/// Working time is searched at most this far after Event start
+const MAX_SEARCH_DAYS: i64 = 2 * 366;
+
+/// Working windows [from, to) of one day, wall-clock times
+pub type DayWindows = Vec<(NaiveTime, NaiveTime)>;
+
+/// Working-time calendar: weekly pattern with exceptions and holidays, wall-clock times in zone
+/// e.g. {"zone": "Europe/Berlin", "weekly": [[["09:00:00", "17:00:00"]], ..., [], []], "exceptions": {"2026-12-24": [["09:00:00", "12:00:00"]]}, "holidays": ["2026-12-25"]}
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct WorkingTime {
+    pub zone: Tz,
+    /// Windows of each weekday, Monday first
+    pub weekly: [DayWindows; 7],
+    /// Windows of date replace weekly windows of that day (empty → day off)
+    #[serde(default)]
+    pub exceptions: BTreeMap<NaiveDate, DayWindows>,
+    /// Days off
+    #[serde(default)]
+    pub holidays: BTreeSet<NaiveDate>,
+}
+
+impl WorkingTime {
+    /// Windows must be non-empty and ordered without overlaps
+    pub fn check(&self)->Result<(), String> {
+        let days = self.weekly.iter().map(|windows| ("weekly pattern".to_string(), windows))
+            .chain(self.exceptions.iter().map(|(date, windows)| (date.to_string(), windows)));
+        for (day, windows) in days {
+            for (i, (from, to)) in windows.iter().enumerate() {
+                if from >= to {
+                    return Err(format!("Working window {}-{} of {} is empty", from, to, day));
+                }
+                if i > 0 && windows[i - 1].1 > *from {
+                    return Err(format!("Working windows of {} overlap or are not ordered", day));
+                }
+            }
+        }
+        if self.weekly.iter().all(Vec::is_empty) {
+            return Err("Weekly pattern has no working time".to_string());
+        }
+        Ok(())
+    }
+
+    /// Wall-clock windows of date
+    pub fn windows_on(&self, date: NaiveDate)->&[(NaiveTime, NaiveTime)] {
+        if self.holidays.contains(&date) {
+            return &[];
+        }
+        match self.exceptions.get(&date) {
+            Some(windows) => windows,
+            None => &self.weekly[date.weekday().num_days_from_monday() as usize],
+        }
+    }
+
+    /// Absolute windows ending after t (first one is cut to start at t), ordered, up to MAX_SEARCH_DAYS after t
+    pub fn windows_after(&self, t: DateTimeType)->impl Iterator<Item=(DateTimeType, DateTimeType)>+'_ {
+        let zone = EventZone::Tz(self.zone);
+        let first = zone.to_local(t).date() - Duration::days(1);
+        (0..=MAX_SEARCH_DAYS + 1)
+            .map(move |n| first + Duration::days(n))
+            .flat_map(move |date| self.windows_on(date).iter().map(move |(from, to)| {
+                (zone.resolve_local(date.and_time(*from)), zone.resolve_local(date.and_time(*to)))
+            }))
+            .filter(move |(_, to)| *to > t)
+            .map(move |(from, to)| (from.max(t), to))
+    }
+
+    /// Pieces of working time of total length needed starting not earlier than t
+    pub fn place(&self,
+        t: DateTimeType,
+        length: Duration
+    )->Result<Vec<(DateTimeType, DateTimeType)>, String> {
+        let mut pieces = Vec::new();
+        let mut remaining = length;
+        for (from, to) in self.windows_after(t) {
+            let piece = (to - from).min(remaining);
+            pieces.push((from, from + piece));
+            remaining -= piece;
+            if remaining <= Duration::zero() {
+                return Ok(pieces);
+            }
+        }
+        Err(format!("No working time for {} min within {} days after {}", length.num_minutes(), MAX_SEARCH_DAYS, t))
+    }
+}
+
+
+
+// ==========CALENDAR EXTENSION==========
+
+/// Adds holidays (e.g. from holiday Extension) to working-time pattern
+/// Inputs: "pattern": WorkingTime, "holidays": Vec<NaiveDate>
+/// Output: "working_time": WorkingTime
+pub struct WorkingCalendar;
+
+impl R_E_AGeneric for WorkingCalendar {
+    fn get_name(&self)->&'static str {
+        "WorkingCalendar"
+    }
+    fn get_class(&self)->&'static R_E_AClass {
+        &R_E_AClass::Extension
+    }
+    fn get_inputs(&self)->&'static IOListOfTypes {
+        static INPUTS: LazyLock<IOListOfTypes> = LazyLock::new(|| HashMap::from([
+            ("pattern".to_string(), TypeId::of::<WorkingTime>()),
+            ("holidays".to_string(), TypeId::of::<Vec<NaiveDate>>()),
+        ]));
+        &INPUTS
+    }
+}
+
+impl Extension for WorkingCalendar {
+    fn get_outputs(&self)->&'static IOListOfTypes {
+        static OUTPUTS: LazyLock<IOListOfTypes> = LazyLock::new(|| HashMap::from([
+            ("working_time".to_string(), TypeId::of::<WorkingTime>()),
+        ]));
+        &OUTPUTS
+    }
+
+    fn run(&self,
+        inputs: IOType
+    )->Result<IOOutputType, String> {
+        let mut working_time = get_input::<WorkingTime>(&inputs, "pattern")?.clone();
+        working_time.holidays.extend(get_input::<Vec<NaiveDate>>(&inputs, "holidays")?);
+        working_time.check().map_err(|e| format!("WorkingCalendar: {}", e))?;
+        Ok(HashMap::from([
+            ("working_time".to_string(), Box::new(working_time) as Box<dyn Any+Send+Sync>),
+        ]))
+    }
+}
+
+
+
+// ==========WORKING HOURS APP==========
+
+/// Moves every Event (except hidden, all-day and recurring ones) to working time, Events longer than working window are split into parts.
+/// Event starts at first working time not earlier than its start; Events that did not overlap before do not overlap after, so order is kept
+/// Input: "working_time": WorkingTime
+/// Output: ET where all timed Events lie within working windows (split Events get " (part 1/n)" suffixes)
+pub struct WorkingHours;
+
+impl R_E_AGeneric for WorkingHours {
+    fn get_name(&self)->&'static str {
+        "WorkingHours"
+    }
+    fn get_class(&self)->&'static R_E_AClass {
+        &R_E_AClass::App
+    }
+    fn get_inputs(&self)->&'static IOListOfTypes {
+        static INPUTS: LazyLock<IOListOfTypes> = LazyLock::new(|| HashMap::from([
+            ("working_time".to_string(), TypeId::of::<WorkingTime>()),
+        ]));
+        &INPUTS
+    }
+}
+
+impl App for WorkingHours {
+    fn run(&self,
+        mut et: EventTable,
+        inputs: IOType
+    )->Result<EventTable, String> {
+        let working_time = get_input::<WorkingTime>(&inputs, "working_time")?;
+        working_time.check().map_err(|e| format!("WorkingHours: {}", e))?;
+
+        // absolute times (Floating Events are placed in zone of working time)
+        let mut events: Vec<(Uuid, EventZone, DateTimeType, DateTimeType)> = et.get_events()
+            .filter(|e| !is_hidden(e) && e.all_day.is_none() && e.recurrence.is_none())
+            .map(|e| {
+                let (start, end) = e.timed_in(working_time.zone);
+                (e.uuid, e.zone, start, end)
+            })
+            .collect();
+        events.sort_by_key(|(uuid, _, start, _)| (*start, *uuid));
+
+        let mut original_end = None; // latest end before placing
+        let mut placed_end = None;   // latest end after placing
+        let mut placed_start = None; // start of previous placed Event
+        for (uuid, zone, start, end) in events {
+            let earliest = match (original_end, placed_end, placed_start) {
+                (Some(original_end), Some(placed_end), _) if start >= original_end => start.max(placed_end),
+                (_, _, Some(placed_start)) => start.max(placed_start),
+                _ => start,
+            };
+            let pieces = working_time.place(earliest, end - start).map_err(|e| format!("WorkingHours: Event {}: {}", uuid, e))?;
+            let (new_start, new_end) = (pieces[0].0, pieces[pieces.len() - 1].1);
+            original_end = original_end.max(Some(end));
+            placed_end = placed_end.max(Some(new_end));
+            placed_start = Some(new_start);
+
+            let stored = |t: DateTimeType| match zone {
+                EventZone::Floating => EventZone::Tz(working_time.zone).to_local(t).and_utc(),
+                EventZone::Utc | EventZone::Tz(_) => t,
+            };
+            if pieces.len() == 1 {
+                if (new_start, new_end) != (start, end) {
+                    et.set_times(uuid, stored(new_start), stored(new_end)).map_err(|e| format!("WorkingHours: {}", e))?;
+                }
+                continue;
+            }
+            let n = pieces.len();
+            let parts = pieces.into_iter().enumerate()
+                .map(|(i, (from, to))| (stored(from), stored(to), format!(" (part {}/{})", i + 1, n)))
+                .collect();
+            et.split(uuid, parts).map_err(|e| format!("WorkingHours: {}", e))?;
+        }
+        Ok(et)
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::EventSpec;
+    use crate::core::testing::{at, times, TestInputs};
+
+    fn hm(h: u32, m: u32)->NaiveTime {
+        NaiveTime::from_hms_opt(h, m, 0).unwrap()
+    }
+
+    fn may(day: u32)->NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 5, day).unwrap()
+    }
+
+    /// Monday to Friday 9-12 and 13-17 UTC
+    fn office()->WorkingTime {
+        let day = vec![(hm(9, 0), hm(12, 0)), (hm(13, 0), hm(17, 0))];
+        WorkingTime {
+            zone: Tz::UTC,
+            weekly: [day.clone(), day.clone(), day.clone(), day.clone(), day, Vec::new(), Vec::new()],
+            exceptions: BTreeMap::from([(may(6), vec![(hm(9, 0), hm(12, 0))])]),
+            holidays: BTreeSet::from([may(7)]),
+        }
+    }
+
+    /// (name, start, end) of children of parent, ordered by start
+    fn parts(et: &EventTable, parent: Uuid)->Vec<(String, DateTimeType, DateTimeType)> {
+        let mut parts: Vec<_> = et.get_events()
+            .filter(|e| e.parent == Some(parent))
+            .map(|e| (e.get_name().to_string(), e.start, e.end))
+            .collect();
+        parts.sort_by_key(|p| p.1);
+        parts
+    }
+
+    #[test]
+    fn windows_with_exceptions_and_holidays() {
+        let office = office();
+        assert_eq!(office.windows_on(may(4)), &[(hm(9, 0), hm(12, 0)), (hm(13, 0), hm(17, 0))]);
+        assert_eq!(office.windows_on(may(6)), &[(hm(9, 0), hm(12, 0))]);
+        assert!(office.windows_on(may(7)).is_empty());
+        assert!(office.windows_on(may(9)).is_empty());
+
+        let windows: Vec<_> = office.windows_after(at(5, 20, 0)).take(3).collect();
+        assert_eq!(windows, vec![
+            (at(6, 9, 0), at(6, 12, 0)),
+            (at(8, 9, 0), at(8, 12, 0)),
+            (at(8, 13, 0), at(8, 17, 0)),
+        ]);
+        // first window is cut at t
+        assert_eq!(office.windows_after(at(4, 10, 30)).next(), Some((at(4, 10, 30), at(4, 12, 0))));
+        assert_eq!(office.windows_after(at(4, 12, 30)).next(), Some((at(4, 13, 0), at(4, 17, 0))));
+    }
+
+    #[test]
+    fn place_splits_across_windows_and_days() {
+        let office = office();
+        assert_eq!(office.place(at(4, 7, 0), Duration::hours(2)).unwrap(), vec![(at(4, 9, 0), at(4, 11, 0))]);
+        assert_eq!(office.place(at(4, 11, 0), Duration::hours(3)).unwrap(), vec![
+            (at(4, 11, 0), at(4, 12, 0)),
+            (at(4, 13, 0), at(4, 15, 0)),
+        ]);
+        assert_eq!(office.place(at(5, 16, 0), Duration::hours(3)).unwrap(), vec![
+            (at(5, 16, 0), at(5, 17, 0)),
+            (at(6, 9, 0), at(6, 11, 0)),
+        ]);
+    }
+
+    #[test]
+    fn place_fails_after_max_search_days() {
+        let mut monday_hour = office();
+        monday_hour.weekly = [vec![(hm(9, 0), hm(10, 0))], Vec::new(), Vec::new(), Vec::new(), Vec::new(), Vec::new(), Vec::new()];
+        monday_hour.exceptions.clear();
+        let weeks = MAX_SEARCH_DAYS / 7;
+        assert!(monday_hour.place(at(4, 0, 0), Duration::hours(weeks - 1)).is_ok());
+        let e = monday_hour.place(at(4, 0, 0), Duration::hours(weeks + 5)).unwrap_err();
+        assert!(e.contains("No working time"));
+
+        let mut et = EventTable::new();
+        et.add_spec(EventSpec::new("Long".to_string(), at(4, 9, 0), at(4, 9, 0) + Duration::hours(weeks + 5))).unwrap();
+        let e = WorkingHours.run(et, TestInputs::new().with("working_time", monday_hour).get()).unwrap_err();
+        assert!(e.starts_with("WorkingHours: Event"));
+    }
+
+    #[test]
+    fn invalid_working_time_is_rejected() {
+        let mut overlapping = office();
+        overlapping.exceptions.insert(may(6), vec![(hm(9, 0), hm(12, 0)), (hm(11, 0), hm(13, 0))]);
+        assert!(overlapping.check().unwrap_err().contains("overlap"));
+        let mut empty = office();
+        empty.weekly[0] = vec![(hm(12, 0), hm(9, 0))];
+        assert!(empty.check().unwrap_err().contains("is empty"));
+        let mut none = office();
+        none.weekly = Default::default();
+        assert!(none.check().is_err());
+        assert!(WorkingHours.run(EventTable::new(), TestInputs::new().with("working_time", none).get()).is_err());
+    }
+
+    #[test]
+    fn calendar_adds_holidays() {
+        let inputs = TestInputs::new().with("pattern", office()).with("holidays", vec![may(4), may(25)]);
+        let mut outputs = WorkingCalendar.run(inputs.get()).unwrap();
+        let working_time = *outputs.remove("working_time").unwrap().downcast::<WorkingTime>().unwrap();
+        assert_eq!(working_time.holidays, BTreeSet::from([may(4), may(7), may(25)]));
+        assert!(working_time.windows_on(may(4)).is_empty());
+        assert_eq!(working_time.place(at(4, 8, 0), Duration::hours(1)).unwrap(), vec![(at(5, 9, 0), at(5, 10, 0))]);
+    }
+
+    #[test]
+    fn events_are_moved_and_split_in_order() {
+        let mut et = EventTable::new();
+        let early = et.add_spec(EventSpec::new("Early".to_string(), at(4, 7, 0), at(4, 8, 0))).unwrap();
+        let next = et.add_spec(EventSpec::new("Next".to_string(), at(4, 8, 0), at(4, 9, 0))).unwrap();
+        let lunch = et.add_spec(EventSpec::new("Lunch".to_string(), at(4, 11, 30), at(4, 13, 30))).unwrap();
+        let late = et.add_spec(EventSpec::new("Late".to_string(), at(5, 16, 0), at(5, 18, 0))).unwrap();
+        let inside = et.add_spec(EventSpec::new("Inside".to_string(), at(8, 14, 0), at(8, 15, 0))).unwrap();
+        let weekend = et.add_spec(EventSpec::new_all_day("Weekend".to_string(), may(9), may(11))).unwrap();
+
+        let et = WorkingHours.run(et, TestInputs::new().with("working_time", office()).get()).unwrap();
+        // Events that did not overlap are placed one after another
+        assert_eq!(times(&et, early), (at(4, 9, 0), at(4, 10, 0)));
+        assert_eq!(times(&et, next), (at(4, 10, 0), at(4, 11, 0)));
+        assert!(et.get(lunch).is_none());
+        assert_eq!(parts(&et, lunch), vec![
+            ("Lunch (part 1/2)".to_string(), at(4, 11, 30), at(4, 12, 0)),
+            ("Lunch (part 2/2)".to_string(), at(4, 13, 0), at(4, 14, 30)),
+        ]);
+        // split across days, the exception shortens Wednesday
+        assert_eq!(parts(&et, late), vec![
+            ("Late (part 1/2)".to_string(), at(5, 16, 0), at(5, 17, 0)),
+            ("Late (part 2/2)".to_string(), at(6, 9, 0), at(6, 10, 0)),
+        ]);
+        assert_eq!(times(&et, inside), (at(8, 14, 0), at(8, 15, 0)));
+        assert_eq!(et.get(weekend).unwrap().all_day, Some((may(9), may(11))));
+    }
+}
@@ -0,0 +1,326 @@
+//Responsible for Wrapper App: preparation and wrap-up buffer Events around selected Events
+
+use std::any::{Any, TypeId};
+use std::collections::{HashMap, HashSet};
+use std::sync::LazyLock;
+
+use chrono::Duration;
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::core::{get_input, App, DateTimeType, EventSpec, EventTable, EventZone, IOListOfTypes, IOOutputType, IOType, R_E_AClass, R_E_AGeneric};
+use crate::std::filter::is_hidden;
+
+/// Namespace of UUIDs of buffers, so buffers of same Event always get same UUIDs
+const WRAPPER_NAMESPACE: Uuid = Uuid::from_u128(0x51d7_0e8a_b2c4_4f93_96e1_7a3b_c0d5_28f4);
+
+/// Field of buffer Event with UUID of Event it wraps
+pub const WRAPS_FIELD: &str = "wraps";
+/// Field of buffer Event: "before" or "after"
+pub const BUFFER_FIELD: &str = "buffer";
+
+/// What Wrapper does when buffer collides with neighbour Event
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WrapPolicy {
+    /// Buffers are cut to free time around Event (buffer cut to nothing is not added)
+    #[default]
+    Shrink,
+    /// Event is moved later until full-length before buffer fits, following neighbours that collide with it or its after buffer are moved later in cascade together with their buffers (order is kept).
+    /// Cascade stops at pinned neighbour, which is reported
+    Shift,
+    /// Buffers are added even if they overlap neighbours
+    Overlap,
+}
+
+/// Adds buffer Events before and after selected Events, buffers keep assignee field of Event.
+/// Neighbours are visible timed non-recurring Events of same assignee (all Events if assignee_field is empty), buffers added before are neighbours too
+/// Inputs: "events": Vec<Uuid>, "before": u32, "after": u32 (minutes, 0 → no buffer), "before_name": String (e.g. "Prep"), "after_name": String (e.g. "Notes"),
+///         "policy": WrapPolicy, "assignee_field": String, "pinned_field": String (e.g. "pinned", empty → nothing is pinned), "zone": Tz (zone of Floating Events)
+/// Output: ET with buffers (fields WRAPS_FIELD=UUID of Event and BUFFER_FIELD); report "blocked": Vec<(Uuid, Uuid)> (Event whose Shift cascade stopped, pinned Event it stopped at)
+pub struct Wrapper;
+
+impl R_E_AGeneric for Wrapper {
+    fn get_name(&self)->&'static str {
+        "Wrapper"
+    }
+    fn get_class(&self)->&'static R_E_AClass {
+        &R_E_AClass::App
+    }
+    fn get_inputs(&self)->&'static IOListOfTypes {
+        static INPUTS: LazyLock<IOListOfTypes> = LazyLock::new(|| HashMap::from([
+            ("events".to_string(), TypeId::of::<Vec<Uuid>>()),
+            ("before".to_string(), TypeId::of::<u32>()),
+            ("after".to_string(), TypeId::of::<u32>()),
+            ("before_name".to_string(), TypeId::of::<String>()),
+            ("after_name".to_string(), TypeId::of::<String>()),
+            ("policy".to_string(), TypeId::of::<WrapPolicy>()),
+            ("assignee_field".to_string(), TypeId::of::<String>()),
+            ("pinned_field".to_string(), TypeId::of::<String>()),
+            ("zone".to_string(), TypeId::of::<Tz>()),
+        ]));
+        &INPUTS
+    }
+}
+
+impl App for Wrapper {
+    fn get_outputs(&self)->&'static IOListOfTypes {
+        static OUTPUTS: LazyLock<IOListOfTypes> = LazyLock::new(|| HashMap::from([
+            ("blocked".to_string(), TypeId::of::<Vec<(Uuid, Uuid)>>()),
+        ]));
+        &OUTPUTS
+    }
+
+    fn run(&self,
+        et: EventTable,
+        inputs: IOType
+    )->Result<EventTable, String> {
+        self.run_with_outputs(et, inputs).map(|(et, _)| et)
+    }
+
+    fn run_with_outputs(&self,
+        mut et: EventTable,
+        inputs: IOType
+    )->Result<(EventTable, IOOutputType), String> {
+        let events = get_input::<Vec<Uuid>>(&inputs, "events")?;
+        let before = Duration::minutes(*get_input::<u32>(&inputs, "before")? as i64);
+        let after = Duration::minutes(*get_input::<u32>(&inputs, "after")? as i64);
+        let before_name = get_input::<String>(&inputs, "before_name")?;
+        let after_name = get_input::<String>(&inputs, "after_name")?;
+        let policy = *get_input::<WrapPolicy>(&inputs, "policy")?;
+        let field = get_input::<String>(&inputs, "assignee_field")?;
+        let pinned_field = get_input::<String>(&inputs, "pinned_field")?;
+        let zone = *get_input::<Tz>(&inputs, "zone")?;
+        let stored = |event_zone: EventZone, t: DateTimeType| match event_zone {
+            EventZone::Floating => EventZone::Tz(zone).to_local(t).and_utc(),
+            EventZone::Utc | EventZone::Tz(_) => t,
+        };
+
+        let mut blocked: Vec<(Uuid, Uuid)> = Vec::new();
+        for uuid in events {
+            let event = et.get(*uuid).ok_or(format!("Wrapper: missing Event {}", uuid))?;
+            if is_hidden(event) || event.all_day.is_some() {
+                continue;
+            }
+            if event.recurrence.is_some() {
+                return Err(format!("Wrapper: Event {} is recurring, materialise its occurrences first", uuid));
+            }
+            let assignee = event.fields.get(field).cloned();
+            let event_zone = event.zone;
+            let (mut start, mut end) = event.timed_in(zone);
+            let original = start;
+
+            // neighbours: (start, end, uuid, zone), absolute times; own buffers of Event (from previous run) are not neighbours
+            let own = uuid.to_string();
+            let mut neighbours: Vec<(DateTimeType, DateTimeType, Uuid, EventZone)> = et.get_events()
+                .filter(|e| e.uuid != *uuid && !is_hidden(e) && e.all_day.is_none() && e.recurrence.is_none())
+                .filter(|e| field.is_empty() || e.fields.get(field) == assignee.as_ref())
+                .filter(|e| e.fields.get(WRAPS_FIELD) != Some(&own))
+                .map(|e| {
+                    let (s, t) = e.timed_in(zone);
+                    (s, t, e.uuid, e.zone)
+                })
+                .filter(|(s, e, _, _)| s < e)
+                .collect();
+            neighbours.sort_by_key(|(s, e, uuid, _)| (*s, *e, *uuid));
+
+            let (mut from, mut to) = (start - before, end + after);
+            match policy {
+                WrapPolicy::Overlap => {}
+                WrapPolicy::Shrink => {
+                    from = neighbours.iter().filter(|(s, e, _, _)| *s < start && *e > from).map(|(_, e, _, _)| *e).max().unwrap_or(from).min(start);
+                    to = neighbours.iter().filter(|(s, e, _, _)| *e > end && *s < to).map(|(s, _, _, _)| *s).min().unwrap_or(to).max(end);
+                }
+                WrapPolicy::Shift => {
+                    let (preceding, following) = neighbours.split_at(neighbours.partition_point(|(s, _, _, _)| *s < start));
+                    let length = end - start;
+                    start = preceding.iter().filter(|(_, e, _, _)| *e > from).map(|(_, e, _, _)| *e + before).fold(start, DateTimeType::max);
+                    end = start + length;
+                    (from, to) = (start - before, end + after);
+                    if start != original {
+                        et.set_times(*uuid, stored(event_zone, start), stored(event_zone, end)).map_err(|e| format!("Wrapper: {}", e))?;
+                    }
+
+                    // following neighbours move together with their buffers: wrapped Event → (start, end, members) of Event and its buffers
+                    let uuids: HashSet<Uuid> = following.iter().map(|(_, _, neighbour, _)| *neighbour).collect();
+                    let mut units: HashMap<Uuid, (DateTimeType, DateTimeType, Vec<_>)> = HashMap::new();
+                    for &(neighbour_start, neighbour_end, neighbour, neighbour_zone) in following {
+                        let wrapped = et.get(neighbour)
+                            .and_then(|e| Uuid::parse_str(e.fields.get(WRAPS_FIELD)?).ok())
+                            .filter(|wrapped| uuids.contains(wrapped));
+                        let unit = units.entry(wrapped.unwrap_or(neighbour)).or_insert((neighbour_start, neighbour_end, Vec::new()));
+                        (unit.0, unit.1) = (unit.0.min(neighbour_start), unit.1.max(neighbour_end));
+                        unit.2.push((neighbour_start, neighbour_end, neighbour, neighbour_zone));
+                    }
+                    let mut units: Vec<_> = units.into_iter().collect();
+                    units.sort_by_key(|(neighbour, (unit_start, unit_end, _))| (*unit_start, *unit_end, *neighbour));
+
+                    // cascade: each colliding neighbour starts where previous one ends, pinned neighbour stops it
+                    let mut cursor = to;
+                    for (neighbour, (unit_start, unit_end, members)) in units {
+                        if unit_start >= cursor {
+                            break;
+                        }
+                        let pinned = !pinned_field.is_empty() && members.iter()
+                            .any(|(_, _, member, _)| et.get(*member).is_some_and(|e| e.fields.contains_key(pinned_field)));
+                        if pinned {
+                            blocked.push((*uuid, neighbour));
+                            break;
+                        }
+                        let delay = cursor - unit_start;
+                        for (member_start, member_end, member, member_zone) in members {
+                            et.set_times(member, stored(member_zone, member_start + delay), stored(member_zone, member_end + delay)).map_err(|e| format!("Wrapper: {}", e))?;
+                        }
+                        cursor = unit_end + delay;
+                    }
+                }
+            }
+
+            for (buffer_start, buffer_end, name, kind) in [(from, start, before_name, "before"), (end, to, after_name, "after")] {
+                let buffer = Uuid::new_v5(&WRAPPER_NAMESPACE, format!("{}/{}", uuid, kind).as_bytes());
+                if buffer_start >= buffer_end || et.get(buffer).is_some() {
+                    continue;
+                }
+                let mut spec = EventSpec::new(name.clone(), stored(event_zone, buffer_start), stored(event_zone, buffer_end));
+                spec.uuid = Some(buffer);
+                spec.zone = event_zone;
+                spec.fields.insert(WRAPS_FIELD.to_string(), uuid.to_string());
+                spec.fields.insert(BUFFER_FIELD.to_string(), kind.to_string());
+                if let Some(assignee) = &assignee {
+                    spec.fields.insert(field.clone(), assignee.clone());
+                }
+                et.add_spec(spec).map_err(|e| format!("Wrapper: {}", e))?;
+            }
+        }
+        Ok((et, HashMap::from([
+            ("blocked".to_string(), Box::new(blocked) as Box<dyn Any+Send+Sync>),
+        ])))
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::testing::{at, times, TestInputs};
+
+    fn add(et: &mut EventTable, name: &str, assignee: &str, start: DateTimeType, end: DateTimeType)->Uuid {
+        let mut spec = EventSpec::new(name.to_string(), start, end);
+        spec.fields.insert("assignee".to_string(), assignee.to_string());
+        et.add_spec(spec).unwrap()
+    }
+
+    /// 15 minutes buffers, Events with field "pinned" are pinned
+    fn inputs(events: Vec<Uuid>, policy: WrapPolicy, zone: Tz)->TestInputs {
+        TestInputs::new()
+            .with("events", events)
+            .with("before", 15u32)
+            .with("after", 15u32)
+            .with("before_name", "Prep".to_string())
+            .with("after_name", "Notes".to_string())
+            .with("policy", policy)
+            .with("assignee_field", "assignee".to_string())
+            .with("pinned_field", "pinned".to_string())
+            .with("zone", zone)
+    }
+
+    fn run(et: EventTable, events: Vec<Uuid>, policy: WrapPolicy, zone: Tz)->Result<EventTable, String> {
+        Wrapper.run(et, inputs(events, policy, zone).get())
+    }
+
+    /// (start, end) of buffers of Event: before, after
+    fn buffers(et: &EventTable, uuid: Uuid)->Vec<(DateTimeType, DateTimeType)> {
+        let mut buffers: Vec<_> = et.get_events()
+            .filter(|e| e.fields.get(WRAPS_FIELD) == Some(&uuid.to_string()))
+            .map(|e| (e.start, e.end))
+            .collect();
+        buffers.sort();
+        buffers
+    }
+
+    /// alice: previous 09:00-09:50, Event 10:00-11:00, next 11:10-12:00, then 12:05-13:00 and 14:00-15:00; bob: 10:55-11:30
+    fn day()->(EventTable, [Uuid; 6]) {
+        let mut et = EventTable::new();
+        let uuids = [
+            add(&mut et, "Previous", "alice", at(4, 9, 0), at(4, 9, 50)),
+            add(&mut et, "Meeting", "alice", at(4, 10, 0), at(4, 11, 0)),
+            add(&mut et, "Next", "alice", at(4, 11, 10), at(4, 12, 0)),
+            add(&mut et, "Lunch", "alice", at(4, 12, 5), at(4, 13, 0)),
+            add(&mut et, "Later", "alice", at(4, 14, 0), at(4, 15, 0)),
+            add(&mut et, "Other", "bob", at(4, 10, 55), at(4, 11, 30)),
+        ];
+        (et, uuids)
+    }
+
+    #[test]
+    fn buffers_are_shrunk_or_overlap() {
+        let (et, [_, meeting, ..]) = day();
+        let shrunk = run(et.clone(), vec![meeting], WrapPolicy::Shrink, chrono_tz::UTC).unwrap();
+        assert_eq!(buffers(&shrunk, meeting), vec![(at(4, 9, 50), at(4, 10, 0)), (at(4, 11, 0), at(4, 11, 10))]);
+        assert_eq!(times(&shrunk, meeting), (at(4, 10, 0), at(4, 11, 0)));
+        let before = shrunk.get_events().find(|e| e.start == at(4, 9, 50)).unwrap();
+        assert_eq!((before.get_name(), before.fields[BUFFER_FIELD].as_str(), before.fields["assignee"].as_str()), ("Prep", "before", "alice"));
+
+        let overlapping = run(et, vec![meeting], WrapPolicy::Overlap, chrono_tz::UTC).unwrap();
+        assert_eq!(buffers(&overlapping, meeting), vec![(at(4, 9, 45), at(4, 10, 0)), (at(4, 11, 0), at(4, 11, 15))]);
+    }
+
+    #[test]
+    fn shift_moves_following_events_in_cascade() {
+        let (et, [previous, meeting, next, lunch, later, other]) = day();
+        let shifted = run(et, vec![meeting], WrapPolicy::Shift, chrono_tz::UTC).unwrap();
+        assert_eq!(times(&shifted, previous), (at(4, 9, 0), at(4, 9, 50)));
+        assert_eq!(times(&shifted, meeting), (at(4, 10, 5), at(4, 11, 5)));
+        assert_eq!(buffers(&shifted, meeting), vec![(at(4, 9, 50), at(4, 10, 5)), (at(4, 11, 5), at(4, 11, 20))]);
+        assert_eq!(times(&shifted, next), (at(4, 11, 20), at(4, 12, 10)));
+        assert_eq!(times(&shifted, lunch), (at(4, 12, 10), at(4, 13, 5)));
+        assert_eq!(times(&shifted, later), (at(4, 14, 0), at(4, 15, 0)));
+        assert_eq!(times(&shifted, other), (at(4, 10, 55), at(4, 11, 30)));
+
+        // second run changes nothing
+        let again = run(shifted.clone(), vec![meeting], WrapPolicy::Shift, chrono_tz::UTC).unwrap();
+        assert_eq!(again.get_events().count(), shifted.get_events().count());
+        assert!(shifted.get_events().all(|e| times(&again, e.uuid) == (e.start, e.end)));
+    }
+
+    #[test]
+    fn floating_events_use_zone() {
+        // 07:00-07:50 UTC is 09:00-09:50 in Berlin, Floating Event is at 10:00-11:00 wall-clock time
+        let mut et = EventTable::new();
+        add(&mut et, "Previous", "alice", at(4, 7, 0), at(4, 7, 50));
+        let mut spec = EventSpec::new("Meeting".to_string(), at(4, 10, 0), at(4, 11, 0));
+        spec.zone = EventZone::Floating;
+        spec.fields.insert("assignee".to_string(), "alice".to_string());
+        let meeting = et.add_spec(spec).unwrap();
+
+        let shifted = run(et, vec![meeting], WrapPolicy::Shift, chrono_tz::Europe::Berlin).unwrap();
+        assert_eq!(times(&shifted, meeting), (at(4, 10, 5), at(4, 11, 5)));
+        assert_eq!(buffers(&shifted, meeting), vec![(at(4, 9, 50), at(4, 10, 5)), (at(4, 11, 5), at(4, 11, 20))]);
+        assert!(shifted.get_events().filter(|e| e.fields.contains_key(WRAPS_FIELD)).all(|e| e.zone == EventZone::Floating));
+    }
+
+    #[test]
+    fn shift_moves_buffers_of_neighbours_and_stops_at_pinned() {
+        let mut et = EventTable::new();
+        let meeting = add(&mut et, "Meeting", "alice", at(4, 10, 0), at(4, 11, 0));
+        let next = add(&mut et, "Next", "alice", at(4, 11, 10), at(4, 12, 0));
+        let mut spec = EventSpec::new("Fixed".to_string(), at(4, 12, 20), at(4, 13, 0));
+        spec.fields.insert("assignee".to_string(), "alice".to_string());
+        spec.fields.insert("pinned".to_string(), String::new());
+        let fixed = et.add_spec(spec).unwrap();
+        let wrapped = run(et, vec![next], WrapPolicy::Shrink, chrono_tz::UTC).unwrap();
+        assert_eq!(buffers(&wrapped, next), vec![(at(4, 11, 0), at(4, 11, 10)), (at(4, 12, 0), at(4, 12, 15))]);
+
+        let (shifted, mut outputs) = Wrapper.run_with_outputs(wrapped, inputs(vec![meeting], WrapPolicy::Shift, chrono_tz::UTC).get()).unwrap();
+        assert_eq!(times(&shifted, meeting), (at(4, 10, 0), at(4, 11, 0)));
+        assert_eq!(buffers(&shifted, meeting), vec![(at(4, 9, 45), at(4, 10, 0)), (at(4, 11, 0), at(4, 11, 15))]);
+        // Next is moved with its buffers, so they still wrap it
+        assert_eq!(times(&shifted, next), (at(4, 11, 25), at(4, 12, 15)));
+        assert_eq!(buffers(&shifted, next), vec![(at(4, 11, 15), at(4, 11, 25)), (at(4, 12, 15), at(4, 12, 30))]);
+        // pinned Event stays and is reported
+        assert_eq!(times(&shifted, fixed), (at(4, 12, 20), at(4, 13, 0)));
+        let blocked = *outputs.remove("blocked").unwrap().downcast::<Vec<(Uuid, Uuid)>>().unwrap();
+        assert_eq!(blocked, vec![(meeting, fixed)]);
+    }
+}